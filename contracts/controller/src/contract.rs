@@ -1,27 +1,45 @@
-use std::{cmp::Ordering, vec};
+use std::vec;
 
 use cosmwasm_std::{
-    entry_point, to_binary, Addr, BankMsg, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo,
-    Reply, Response, StdResult, SubMsg, WasmMsg,
+    entry_point, to_json_binary, Addr, Binary, Coin, Deps, DepsMut, Env, MessageInfo, Order, Reply,
+    Response, StdError, StdResult, SubMsg, Uint128, WasmMsg,
 };
-use cw2::set_contract_version;
-use cw_ownable::{assert_owner, initialize_owner};
-use cw_utils::{one_coin, parse_reply_instantiate_data};
-use utils::{
-    msg::{MessagesInstantiateMsg, ProfilesInstantiateMsg, MessagesExecuteMsg, ProfilesExecuteMsg},
-    query::{ProfileInfo, ProfilesQueryMsg},
+use cw2::{get_contract_version, set_contract_version};
+use cw_ownable::initialize_owner;
+use cw_storage_plus::{Bound, Map};
+use cw_utils::parse_reply_instantiate_data;
+use utils::msg::{MessagesInstantiateMsg, ProfilesInstantiateMsg};
+
+use crate::execute::{
+    attestations, auctions, children, config, fees, imports, keys, links, marketplace, messages,
+    pause, profiles, proposals, recovery, roles, sponsors, subnames, subscriptions,
 };
-
 use crate::{
     error::ContractError,
-    msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
-    state::{Config, CONFIG, MESSAGES_ADDRESS, PROFILES_ADDRESS},
+    msg::{
+        AllowancesResponse, ChildDeployment, ConfigHistoryEntry, ConfigHistoryResponse,
+        DeploymentResponse, EstimateCostResponse, ExecuteMsg, FeeSharesResponse, FeeTotalsResponse,
+        InstantiateMsg, ListingInfo, ListingsResponse, PlanInfo, PlansResponse, ProposalInfo,
+        ProposalsResponse, QueryMsg, RecoveryResponse, ReferrerInfo, ReferrersResponse,
+        RoleMembers, SponsorDepositResponse, SubscriptionResponse,
+    },
+    state::{
+        Allowance, Auction, Config, Listing, Role, ALLOWANCES, AUCTIONS, COLLECTED_FEES, CONFIG,
+        CONFIG_HISTORY, FEE_SHARES, LIFETIME_FEES, LISTINGS, MESSAGES_ADDRESS, PAUSE_FLAGS, PLANS,
+        PROFILES_ADDRESS, PROPOSALS, RECOVERY_REQUESTS, RECOVERY_SETUPS, REFERRALS,
+        REFERRAL_RANKING, ROLES, SPONSOR_CODES, SPONSOR_DEPOSITS, SUBSCRIPTIONS,
+    },
 };
 
 const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 const INSTANTIATE_PROFILES_REPLY_ID: u64 = 1;
 const INSTANTIATE_MESSAGES_REPLY_ID: u64 = 2;
+pub(crate) const IMPORT_PROFILES_REPLY_ID: u64 = 3;
+pub(crate) const SETTLE_AUCTION_REPLY_ID: u64 = 4;
+pub(crate) const MAX_IMPORT_ENTRIES: u64 = 50;
+const DEFAULT_QUERY_LIMIT: u64 = 10;
+const MAX_QUERY_LIMIT: u64 = 30;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -38,18 +56,32 @@ pub fn instantiate(
         Some(&info.sender.clone().into_string()),
     )?;
 
-    CONFIG.save(
-        deps.storage,
-        &Config {
-            message_max_len: msg.message_max_len,
-            message_cost: msg.send_message_cost,
-            profile_cost: msg.create_profile_cost,
-        },
-    )?;
+    let config = Config {
+        message_max_len: msg.message_max_len,
+        message_prices: msg.send_message_prices,
+        profile_cost: msg.create_profile_cost,
+        trade_fee: msg.user_id_trade_fee,
+        referral_share: msg.referral_share,
+        length_prices: msg.user_id_length_prices,
+        auction: msg.user_id_auction,
+        registration_term: msg.user_id_term,
+        change_fee: msg.user_id_change_fee,
+        recovery_delay: msg.recovery_delay,
+        key_backup_max_len: msg.key_backup_max_len,
+        timelock_delay: msg.timelock_delay,
+    };
+    config::validate_config(&config)?;
+    CONFIG.save(deps.storage, &config)?;
 
     let wasm_profiles_msg = WasmMsg::Instantiate {
         code_id: msg.code_id_profiles,
-        msg: to_binary(&ProfilesInstantiateMsg {})?,
+        msg: to_json_binary(&ProfilesInstantiateMsg {
+            user_id_cooldown: msg.user_id_cooldown,
+            max_aliases: msg.max_aliases,
+            grace_period: msg.user_id_grace_period,
+            change_cooldown: msg.user_id_change_cooldown,
+            redirect_period: msg.user_id_redirect_period,
+        })?,
         funds: vec![],
         admin: Some(env.contract.address.clone().into_string()),
         label: format!("PROFILES-INFORMATION--{}", msg.code_id_profiles,),
@@ -59,7 +91,7 @@ pub fn instantiate(
 
     let wasm_messages_msg = WasmMsg::Instantiate {
         code_id: msg.code_id_messages,
-        msg: to_binary(&MessagesInstantiateMsg {
+        msg: to_json_binary(&MessagesInstantiateMsg {
             default_query_limit: msg.message_query_default_limit,
             max_query_limit: msg.message_query_max_limit,
         })?,
@@ -85,257 +117,506 @@ pub fn execute(
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
+    if let Some(operation) = pause::paused_operation(&msg) {
+        let flags = PAUSE_FLAGS.may_load(deps.storage)?.unwrap_or_default();
+        if flags.is_paused(&operation) {
+            return Err(ContractError::Paused {
+                operation: operation.as_str().to_string(),
+            });
+        }
+    }
+
     match msg {
-        ExecuteMsg::CreateProfile { pubkey, user_id } => {
-            create_profile(deps, info, pubkey, user_id)
+        ExecuteMsg::CreateProfile {
+            pubkey,
+            user_id,
+            sponsor_code,
+            referrer,
+        } => profiles::create_profile(deps, env, info, pubkey, user_id, sponsor_code, referrer),
+        ExecuteMsg::CreatePrivateProfile {
+            user_id_hash,
+            pubkey,
+        } => profiles::create_private_profile(deps, info, user_id_hash, pubkey),
+        ExecuteMsg::ImportProfiles { entries } => imports::import_profiles(deps, info, entries),
+        ExecuteMsg::ChangeUserId { user_id } => profiles::change_user_id(deps, info, user_id),
+        ExecuteMsg::ChangePubkey { pubkey } => profiles::change_pubkey(deps, info, pubkey),
+        ExecuteMsg::AddAlias { alias } => profiles::add_alias(deps, info, alias),
+        ExecuteMsg::RemoveAlias { alias } => profiles::remove_alias(deps, info, alias),
+        ExecuteMsg::TransferUserId { user_id, to } => {
+            marketplace::transfer_user_id(deps, info, user_id, to)
+        }
+        ExecuteMsg::CancelUserIdTransfer { user_id } => {
+            marketplace::cancel_user_id_transfer(deps, info, user_id)
+        }
+        ExecuteMsg::AcceptUserId { user_id, pubkey } => {
+            marketplace::accept_user_id(deps, info, user_id, pubkey)
+        }
+        ExecuteMsg::ListUserId { user_id, price } => {
+            marketplace::list_user_id(deps, info, user_id, price)
+        }
+        ExecuteMsg::DelistUserId { user_id } => marketplace::delist_user_id(deps, info, user_id),
+        ExecuteMsg::BuyUserId { user_id, pubkey } => {
+            marketplace::buy_user_id(deps, info, user_id, pubkey)
+        }
+        ExecuteMsg::BidUserId { user_id, pubkey } => {
+            auctions::bid_user_id(deps, env, info, user_id, pubkey)
+        }
+        ExecuteMsg::SettleAuction { user_id } => auctions::settle_auction(deps, env, user_id),
+        ExecuteMsg::CreateSubname {
+            parent,
+            label,
+            holder,
+            pubkey,
+        } => subnames::create_subname(deps, info, parent, label, holder, pubkey),
+        ExecuteMsg::TransferSubname {
+            parent,
+            label,
+            holder,
+            pubkey,
+        } => subnames::transfer_subname(deps, info, parent, label, holder, pubkey),
+        ExecuteMsg::RevokeSubname { parent, label } => {
+            subnames::revoke_subname(deps, info, parent, label)
+        }
+        ExecuteMsg::ChangeSubnamePubkey {
+            parent,
+            label,
+            pubkey,
+        } => subnames::change_subname_pubkey(deps, info, parent, label, pubkey),
+        ExecuteMsg::SetSubnamePolicy {
+            parent,
+            holders_can_change_pubkey,
+        } => subnames::set_subname_policy(deps, info, parent, holders_can_change_pubkey),
+        ExecuteMsg::RenewProfile { user_id } => profiles::renew_profile(deps, info, user_id),
+        ExecuteMsg::SetDirectoryListing { listed } => {
+            profiles::set_directory_listing(deps, info, listed)
         }
-        ExecuteMsg::ChangeUserId { user_id } => change_user_id(deps, info, user_id),
-        ExecuteMsg::ChangePubkey { pubkey } => change_pubkey(deps, info, pubkey),
+        ExecuteMsg::DeactivateProfile {} => profiles::deactivate_profile(deps, info),
+        ExecuteMsg::ReactivateProfile {} => profiles::reactivate_profile(deps, info),
+        ExecuteMsg::DeleteProfile { refund_funds } => {
+            profiles::delete_profile(deps, info, refund_funds)
+        }
+        ExecuteMsg::LinkAddress {
+            external_address,
+            pubkey,
+            signature,
+        } => links::link_address(deps, info, external_address, pubkey, signature),
+        ExecuteMsg::UnlinkAddress { external_address } => {
+            links::unlink_address(deps, info, external_address)
+        }
+        ExecuteMsg::AddVerifier { verifier, pubkey } => {
+            attestations::add_verifier(deps, info, verifier, pubkey)
+        }
+        ExecuteMsg::RemoveVerifier { verifier } => {
+            attestations::remove_verifier(deps, info, verifier)
+        }
+        ExecuteMsg::Attest {
+            address,
+            kind,
+            expires,
+            signature,
+        } => attestations::attest(deps, info, address, kind, expires, signature),
+        ExecuteMsg::RevokeAttestation { address, kind } => {
+            attestations::revoke_attestation(deps, info, address, kind)
+        }
+        ExecuteMsg::SetKeyBackup {
+            data,
+            expected_version,
+        } => keys::set_key_backup(deps, info, data, expected_version),
+        ExecuteMsg::SetKeyShares { shares, threshold } => {
+            keys::set_key_shares(deps, info, shares, threshold)
+        }
+        ExecuteMsg::RequestKeyRecovery { new_pubkey } => {
+            keys::request_key_recovery(deps, info, new_pubkey)
+        }
+        ExecuteMsg::CancelKeyRecovery {} => keys::cancel_key_recovery(deps, info),
+        ExecuteMsg::SubmitKeyShare { address, share } => {
+            keys::submit_key_share(deps, info, address, share)
+        }
+        ExecuteMsg::SetRecovery {
+            guardians,
+            threshold,
+        } => recovery::set_recovery(deps, info, guardians, threshold),
+        ExecuteMsg::RemoveRecovery {} => recovery::remove_recovery(deps, info),
+        ExecuteMsg::ApproveRecovery {
+            address,
+            new_address,
+        } => recovery::approve_recovery(deps, env, info, address, new_address),
+        ExecuteMsg::CancelRecovery {} => recovery::cancel_recovery(deps, info),
+        ExecuteMsg::ExecuteRecovery { address } => recovery::execute_recovery(deps, env, address),
         ExecuteMsg::SendMessage {
             content,
             dest_address,
             dest_id,
-        } => send_message(deps, info, content, dest_address, dest_id),
+            dest_id_hash,
+        } => messages::send_message(
+            deps,
+            env,
+            info,
+            content,
+            dest_address,
+            dest_id,
+            dest_id_hash,
+        ),
+        ExecuteMsg::SetPlan {
+            name,
+            price,
+            period,
+            quota,
+        } => subscriptions::set_plan(deps, info, name, price, period, quota),
+        ExecuteMsg::RemovePlan { name } => subscriptions::remove_plan(deps, info, name),
+        ExecuteMsg::Subscribe { plan } => subscriptions::subscribe(deps, env, info, plan),
+        ExecuteMsg::DepositSponsorFunds {} => sponsors::deposit_sponsor_funds(deps, info),
+        ExecuteMsg::WithdrawSponsorFunds { amount } => {
+            sponsors::withdraw_sponsor_funds(deps, info, amount)
+        }
+        ExecuteMsg::GrantAllowance {
+            grantee,
+            limit,
+            expires,
+        } => sponsors::grant_allowance(deps, env, info, grantee, limit, expires),
+        ExecuteMsg::RevokeAllowance { grantee } => sponsors::revoke_allowance(deps, info, grantee),
+        ExecuteMsg::CreateSponsorCode {
+            code,
+            limit,
+            expires,
+            max_uses,
+        } => sponsors::create_sponsor_code(deps, env, info, code, limit, expires, max_uses),
+        ExecuteMsg::RemoveSponsorCode { code } => sponsors::remove_sponsor_code(deps, info, code),
         ExecuteMsg::ChangeMessagesConfig {
             message_query_default_limit,
             message_query_max_limit,
-        } => change_messages_config(
+        } => config::change_messages_config(
             deps,
             info,
             message_query_default_limit,
             message_query_max_limit,
         ),
-        ExecuteMsg::RetrieveFees { receiver } => retrieve_fees(deps, env, info, receiver),
-        ExecuteMsg::UpdateOwnership(action) => update_ownership(deps, env, info, action),
+        ExecuteMsg::ChangeProfilesConfig {
+            user_id_cooldown,
+            max_aliases,
+            user_id_grace_period,
+            user_id_change_cooldown,
+            user_id_redirect_period,
+        } => config::change_profiles_config(
+            deps,
+            info,
+            user_id_cooldown,
+            max_aliases,
+            user_id_grace_period,
+            user_id_change_cooldown,
+            user_id_redirect_period,
+        ),
+        ExecuteMsg::GrantRole { role, address } => roles::grant_role(deps, info, role, address),
+        ExecuteMsg::RevokeRole { role, address } => roles::revoke_role(deps, info, role, address),
+        ExecuteMsg::SetPaused { operation, paused } => {
+            pause::set_paused(deps, info, operation, paused)
+        }
+        ExecuteMsg::UpdateConfig(update) => config::update_config(deps, env, info, update),
+        ExecuteMsg::SetFeeShares { shares } => fees::set_fee_shares(deps, env, info, shares),
+        ExecuteMsg::MigrateChild {
+            contract,
+            new_code_id,
+            msg,
+        } => children::migrate_child(deps, env, info, contract, new_code_id, msg),
+        ExecuteMsg::SetChildContract { contract, address } => {
+            children::set_child_contract(deps, env, info, contract, address)
+        }
+        ExecuteMsg::CancelProposal { id } => proposals::cancel_proposal(deps, info, id),
+        ExecuteMsg::ExecuteProposal { id } => proposals::execute_proposal(deps, env, id),
+        ExecuteMsg::DistributeFees {} => fees::distribute_fees(deps, info),
+        ExecuteMsg::RetrieveFees { receiver } => fees::retrieve_fees(deps, info, receiver),
+        ExecuteMsg::SweepFunds { recipient } => fees::sweep_funds(deps, env, info, recipient),
+        ExecuteMsg::UpdateOwnership(action) => proposals::update_ownership(deps, env, info, action),
     }
 }
 
-fn create_profile(
-    deps: DepsMut,
-    info: MessageInfo,
-    pubkey: String,
-    user_id: String,
-) -> Result<Response, ContractError> {
-    let config = CONFIG.load(deps.storage)?;
-
-    if let Some(coin) = config.profile_cost {
-        let funds = one_coin(&info)?;
-        if funds != coin {
-            return Err(ContractError::InvalidFunds {
-                funds_required: coin,
-            });
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_json_binary(&query_config(deps)?),
+        QueryMsg::Deployment {} => to_json_binary(&query_deployment(deps)?),
+        QueryMsg::Proposal { id } => to_json_binary(&PROPOSALS.load(deps.storage, id)?),
+        QueryMsg::Proposals { start_after, limit } => {
+            to_json_binary(&query_proposals(deps, start_after, limit)?)
+        }
+        QueryMsg::RoleMembers {
+            role,
+            start_after,
+            limit,
+        } => to_json_binary(&query_role_members(deps, role, start_after, limit)?),
+        QueryMsg::PauseFlags {} => {
+            to_json_binary(&PAUSE_FLAGS.may_load(deps.storage)?.unwrap_or_default())
+        }
+        QueryMsg::Listing { user_id } => to_json_binary(&query_listing(deps, user_id)?),
+        QueryMsg::Auction { user_id } => to_json_binary(&query_auction(deps, user_id)?),
+        QueryMsg::Listings { start_after, limit } => {
+            to_json_binary(&query_listings(deps, start_after, limit)?)
+        }
+        QueryMsg::Recovery { address } => to_json_binary(&query_recovery(deps, address)?),
+        QueryMsg::EstimateCost { content_len, denom } => {
+            to_json_binary(&query_estimate_cost(deps, content_len, denom)?)
+        }
+        QueryMsg::Referrals { address } => to_json_binary(
+            &REFERRALS
+                .may_load(deps.storage, address)?
+                .unwrap_or_default(),
+        ),
+        QueryMsg::Referrers { start_after, limit } => {
+            to_json_binary(&query_referrers(deps, start_after, limit)?)
+        }
+        QueryMsg::Plans {} => to_json_binary(&query_plans(deps)?),
+        QueryMsg::Subscription { address } => {
+            to_json_binary(&query_subscription(deps, env, address)?)
+        }
+        QueryMsg::SponsorDeposit { sponsor } => {
+            to_json_binary(&query_sponsor_deposit(deps, sponsor)?)
+        }
+        QueryMsg::Allowances { grantee } => to_json_binary(&query_allowances(deps, grantee)?),
+        QueryMsg::SponsorCode { code } => to_json_binary(&SPONSOR_CODES.load(deps.storage, code)?),
+        QueryMsg::FeeTotals {} => to_json_binary(&query_fee_totals(deps)?),
+        QueryMsg::FeeShares {} => to_json_binary(&FeeSharesResponse {
+            shares: FEE_SHARES.may_load(deps.storage)?.unwrap_or_default(),
+        }),
+        QueryMsg::ConfigHistory { start_after, limit } => {
+            to_json_binary(&query_config_history(deps, start_after, limit)?)
         }
     }
-
-    let profile_address = PROFILES_ADDRESS.load(deps.storage)?;
-    let create_profile_msg = ProfilesExecuteMsg::CreateProfile {
-        address: info.sender.clone(),
-        user_id: user_id.clone(),
-        pubkey,
-    };
-    let msg = CosmosMsg::Wasm(WasmMsg::Execute {
-        contract_addr: profile_address.to_string(),
-        msg: to_binary(&create_profile_msg)?,
-        funds: vec![],
-    });
-
-    Ok(Response::new()
-        .add_message(msg)
-        .add_attribute("action", "create_profile")
-        .add_attribute("sender", info.sender)
-        .add_attribute("user_id", user_id))
 }
 
-fn change_user_id(
-    deps: DepsMut,
-    info: MessageInfo,
-    user_id: String,
-) -> Result<Response, ContractError> {
-    let profile_address = PROFILES_ADDRESS.load(deps.storage)?;
+fn query_config(deps: Deps) -> StdResult<Config> {
+    let config = CONFIG.load(deps.storage)?;
 
-    let change_userid_msg = ProfilesExecuteMsg::ChangeUserId {
-        address: info.sender.clone(),
-        user_id: user_id.clone(),
-    };
-    let msg = CosmosMsg::Wasm(WasmMsg::Execute {
-        contract_addr: profile_address.to_string(),
-        msg: to_binary(&change_userid_msg)?,
-        funds: vec![],
-    });
+    Ok(config)
+}
 
-    Ok(Response::new()
-        .add_message(msg)
-        .add_attribute("action", "create_userid")
-        .add_attribute("sender", info.sender)
-        .add_attribute("user_id", user_id))
+fn query_proposals(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u64>,
+) -> StdResult<ProposalsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let proposals = PROPOSALS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(id, proposal)| ProposalInfo { id, proposal }))
+        .collect::<StdResult<Vec<ProposalInfo>>>()?;
+
+    Ok(ProposalsResponse { proposals })
 }
 
-fn change_pubkey(
-    deps: DepsMut,
-    info: MessageInfo,
-    pubkey: String,
-) -> Result<Response, ContractError> {
-    let profile_address = PROFILES_ADDRESS.load(deps.storage)?;
+fn query_role_members(
+    deps: Deps,
+    role: Role,
+    start_after: Option<Addr>,
+    limit: Option<u64>,
+) -> StdResult<RoleMembers> {
+    let limit = limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let members = ROLES
+        .prefix(role.as_str().to_string())
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<Addr>>>()?;
+
+    Ok(RoleMembers { role, members })
+}
 
-    let change_pubkey_msg = ProfilesExecuteMsg::ChangePubkey {
-        address: info.sender.clone(),
-        pubkey: pubkey.clone(),
+fn query_deployment(deps: Deps) -> StdResult<DeploymentResponse> {
+    let child = |address: Addr| -> StdResult<ChildDeployment> {
+        Ok(ChildDeployment {
+            code_id: deps
+                .querier
+                .query_wasm_contract_info(address.clone())?
+                .code_id,
+            version: cw2::query_contract_info(&deps.querier, address.clone())?,
+            address,
+        })
     };
-    let msg = CosmosMsg::Wasm(WasmMsg::Execute {
-        contract_addr: profile_address.to_string(),
-        msg: to_binary(&change_pubkey_msg)?,
-        funds: vec![],
-    });
 
-    Ok(Response::new()
-        .add_message(msg)
-        .add_attribute("action", "create_pubkey")
-        .add_attribute("sender", info.sender)
-        .add_attribute("pubkey", pubkey))
+    Ok(DeploymentResponse {
+        controller: get_contract_version(deps.storage)?,
+        profiles: child(PROFILES_ADDRESS.load(deps.storage)?)?,
+        messages: child(MESSAGES_ADDRESS.load(deps.storage)?)?,
+    })
 }
 
-fn send_message(
-    deps: DepsMut,
-    info: MessageInfo,
-    content: Binary,
-    dest_address: Option<Addr>,
-    dest_id: Option<String>,
-) -> Result<Response, ContractError> {
-    let config = CONFIG.load(deps.storage)?;
-    let profile_address = PROFILES_ADDRESS.load(deps.storage)?;
+fn query_listing(deps: Deps, user_id: String) -> StdResult<Listing> {
+    LISTINGS.load(deps.storage, user_id)
+}
 
-    if content.len() > config.message_max_len.try_into().unwrap() {
-        return Err(ContractError::MessageTooLong {});
-    }
+fn query_auction(deps: Deps, user_id: String) -> StdResult<Auction> {
+    AUCTIONS.load(deps.storage, user_id)
+}
 
-    if dest_address.is_none() && dest_id.is_none() {
-        return Err(ContractError::NoDestination {});
-    }
+fn query_recovery(deps: Deps, address: Addr) -> StdResult<RecoveryResponse> {
+    Ok(RecoveryResponse {
+        setup: RECOVERY_SETUPS.may_load(deps.storage, address.clone())?,
+        request: RECOVERY_REQUESTS.may_load(deps.storage, address)?,
+    })
+}
 
-    let destination = match dest_address {
-        Some(address) => deps.api.addr_validate(address.as_ref())?,
-        None => {
-            let profile_info: ProfileInfo = deps.querier.query_wasm_smart(
-                profile_address,
-                &ProfilesQueryMsg::UserInfo {
-                    user_id: dest_id.unwrap(),
-                },
-            )?;
-            profile_info.address
-        }
-    };
+fn query_listings(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u64>,
+) -> StdResult<ListingsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let listings = LISTINGS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            item.map(|(user_id, listing)| ListingInfo {
+                user_id,
+                seller: listing.seller,
+                price: listing.price,
+            })
+        })
+        .collect::<StdResult<Vec<ListingInfo>>>()?;
+
+    Ok(ListingsResponse { listings })
+}
 
-    let mut funds_to_send = info.funds;
-
-    if let Some(cost) = config.message_cost {
-        match funds_to_send.iter().position(|c| c.denom == cost.denom) {
-            Some(index) => {
-                match funds_to_send[index].amount.cmp(&cost.amount) {
-                    Ordering::Less => return Err(ContractError::NotEnoughFundsForMessage {}),
-                    Ordering::Equal => {
-                        funds_to_send.remove(index);
-                    }
-                    Ordering::Greater => funds_to_send[index].amount -= cost.amount,
-                };
-            }
-            None => return Err(ContractError::NotEnoughFundsForMessage {}),
-        }
+fn query_estimate_cost(
+    deps: Deps,
+    content_len: u64,
+    denom: String,
+) -> StdResult<EstimateCostResponse> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if content_len > config.message_max_len {
+        return Err(StdError::generic_err(
+            ContractError::MessageTooLong {}.to_string(),
+        ));
     }
 
-    let message_address = MESSAGES_ADDRESS.load(deps.storage)?;
-    let create_send_msg = MessagesExecuteMsg::SendMessage {
-        sender: info.sender.clone(),
-        receiver: destination.clone(),
-        message: content,
+    let amount = if config.message_prices.is_empty() {
+        Uint128::zero()
+    } else {
+        let price = config
+            .message_prices
+            .iter()
+            .find(|price| price.denom == denom)
+            .ok_or_else(|| StdError::generic_err(format!("Denom {denom} is not accepted")))?;
+        messages::message_cost(price, content_len)?
     };
-    let msg = CosmosMsg::Wasm(WasmMsg::Execute {
-        contract_addr: message_address.to_string(),
-        msg: to_binary(&create_send_msg)?,
-        funds: funds_to_send,
-    });
 
-    Ok(Response::new()
-        .add_message(msg)
-        .add_attribute("action", "send_message")
-        .add_attribute("sender", info.sender)
-        .add_attribute("destination", destination))
+    Ok(EstimateCostResponse {
+        cost: Coin { denom, amount },
+    })
 }
 
-fn change_messages_config(
-    deps: DepsMut,
-    info: MessageInfo,
-    message_query_default_limit: u64,
-    message_query_max_limit: u64,
-) -> Result<Response, ContractError> {
-    assert_owner(deps.storage, &info.sender)?;
+fn query_referrers(
+    deps: Deps,
+    start_after: Option<(u64, Addr)>,
+    limit: Option<u64>,
+) -> StdResult<ReferrersResponse> {
+    let limit = limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT) as usize;
+    let end = start_after.map(Bound::exclusive);
+
+    let referrers = REFERRAL_RANKING
+        .keys(deps.storage, None, end, Order::Descending)
+        .take(limit)
+        .map(|item| {
+            let (count, address) = item?;
+            let stats = REFERRALS.load(deps.storage, address.clone())?;
+            Ok(ReferrerInfo {
+                address,
+                count,
+                earnings: stats.earnings,
+            })
+        })
+        .collect::<StdResult<Vec<ReferrerInfo>>>()?;
+
+    Ok(ReferrersResponse { referrers })
+}
 
-    let messages_address = MESSAGES_ADDRESS.load(deps.storage)?;
+fn query_plans(deps: Deps) -> StdResult<PlansResponse> {
+    let plans = PLANS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(name, plan)| PlanInfo { name, plan }))
+        .collect::<StdResult<Vec<PlanInfo>>>()?;
 
-    let change_pubkey_msg = MessagesExecuteMsg::ChangeConfig {
-        default_query_limit: message_query_default_limit,
-        max_query_limit: message_query_max_limit,
-    };
+    Ok(PlansResponse { plans })
+}
 
-    let msg = CosmosMsg::Wasm(WasmMsg::Execute {
-        contract_addr: messages_address.to_string(),
-        msg: to_binary(&change_pubkey_msg)?,
-        funds: vec![],
+fn query_subscription(deps: Deps, env: Env, address: Addr) -> StdResult<SubscriptionResponse> {
+    let subscription = SUBSCRIPTIONS.may_load(deps.storage, address)?;
+    let active = subscription.as_ref().map_or(false, |subscription| {
+        subscriptions::is_active(&env, subscription)
     });
 
-    Ok(Response::new()
-        .add_message(msg)
-        .add_attribute("action", "change_messages_config")
-        .add_attribute("sender", info.sender))
+    Ok(SubscriptionResponse {
+        subscription,
+        active,
+    })
 }
 
-fn retrieve_fees(
-    deps: DepsMut,
-    env: Env,
-    info: MessageInfo,
-    receiver: Option<Addr>,
-) -> Result<Response, ContractError> {
-    assert_owner(deps.storage, &info.sender)?;
-    let contract_balances = deps.querier.query_all_balances(env.contract.address)?;
-    let receiver_address = deps
-        .api
-        .addr_validate(receiver.unwrap_or(info.sender).as_ref())?;
-
-    let bank_msg = BankMsg::Send {
-        to_address: receiver_address.to_string(),
-        amount: contract_balances,
-    };
+fn query_sponsor_deposit(deps: Deps, sponsor: Addr) -> StdResult<SponsorDepositResponse> {
+    let funds = SPONSOR_DEPOSITS
+        .prefix(sponsor)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(denom, amount)| Coin { denom, amount }))
+        .collect::<StdResult<Vec<Coin>>>()?;
 
-    Ok(Response::new()
-        .add_message(bank_msg)
-        .add_attribute("recipient", receiver_address))
+    Ok(SponsorDepositResponse { funds })
 }
 
-fn update_ownership(
-    deps: DepsMut,
-    env: Env,
-    info: MessageInfo,
-    action: cw_ownable::Action,
-) -> Result<Response, ContractError> {
-    let ownership = cw_ownable::update_ownership(deps, &env.block, &info.sender, action)?;
-    Ok(Response::new().add_attributes(ownership.into_attributes()))
-}
+fn query_allowances(deps: Deps, grantee: Addr) -> StdResult<AllowancesResponse> {
+    let allowances = ALLOWANCES
+        .prefix(grantee)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, allowance)| allowance))
+        .collect::<StdResult<Vec<Allowance>>>()?;
 
-#[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
-    match msg {
-        QueryMsg::Config {} => to_binary(&query_config(deps)?),
-    }
+    Ok(AllowancesResponse { allowances })
 }
 
-fn query_config(deps: Deps) -> StdResult<Config> {
-    let config = CONFIG.load(deps.storage)?;
+fn query_fee_totals(deps: Deps) -> StdResult<FeeTotalsResponse> {
+    let coins = |map: Map<String, Uint128>| {
+        map.range(deps.storage, None, None, Order::Ascending)
+            .map(|item| item.map(|(denom, amount)| Coin { denom, amount }))
+            .collect::<StdResult<Vec<Coin>>>()
+    };
 
-    Ok(config)
+    Ok(FeeTotalsResponse {
+        lifetime: coins(LIFETIME_FEES)?,
+        undistributed: coins(COLLECTED_FEES)?,
+    })
+}
+
+fn query_config_history(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u64>,
+) -> StdResult<ConfigHistoryResponse> {
+    let limit = limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let entries = CONFIG_HISTORY
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(id, change)| ConfigHistoryEntry { id, change }))
+        .collect::<StdResult<Vec<ConfigHistoryEntry>>>()?;
+
+    Ok(ConfigHistoryResponse { entries })
 }
 
-// Reply callback triggered from instantiation of profiles and messages contract.
+// Reply callback triggered from instantiation of profiles and messages contract, imports and
+// failed auction settlements.
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    if msg.id == SETTLE_AUCTION_REPLY_ID {
+        return auctions::refund_auction(deps, msg);
+    }
+    if msg.id == IMPORT_PROFILES_REPLY_ID {
+        return imports::import_profiles_reply(deps, msg);
+    }
+
     let reply = parse_reply_instantiate_data(msg.clone());
     match reply {
         Ok(res) => match msg.id {
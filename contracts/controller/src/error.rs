@@ -1,6 +1,6 @@
-use cosmwasm_std::{StdError, Coin};
+use cosmwasm_std::{Coin, StdError, Timestamp};
 use cw_ownable::OwnershipError;
-use cw_utils::PaymentError;
+use cw_utils::{ParseReplyError, PaymentError};
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -14,6 +14,33 @@ pub enum ContractError {
     #[error("{0}")]
     Payment(#[from] PaymentError),
 
+    #[error("{0}")]
+    ParseReply(#[from] ParseReplyError),
+
+    #[error("Expected a {expected} contract, found {found}")]
+    ChildContractMismatch { expected: String, found: String },
+
+    #[error("Child contract must be owned by the controller or have it as pending owner")]
+    ChildNotOwned {},
+
+    #[error("Child contract must have the controller as admin")]
+    ChildNotAdministered {},
+
+    #[error("Child contract version {found} is below the current {current}")]
+    ChildVersionTooLow { current: String, found: String },
+
+    #[error("Invalid contract version: {version}")]
+    InvalidContractVersion { version: String },
+
+    #[error("Proposal can be executed at {eta}")]
+    ProposalNotReady { eta: Timestamp },
+
+    #[error("Missing role: {role}")]
+    MissingRole { role: String },
+
+    #[error("{operation} is paused")]
+    Paused { operation: String },
+
     #[error("Invalid reply ID")]
     InvalidReplyID {},
 
@@ -31,4 +58,115 @@ pub enum ContractError {
 
     #[error("Not enough funds to send message")]
     NotEnoughFundsForMessage {},
+
+    #[error("Invalid value for {field}")]
+    InvalidConfig { field: String },
+
+    #[error("Invalid denom: {denom}")]
+    InvalidDenom { denom: String },
+
+    #[error("No config field to update")]
+    EmptyConfigUpdate {},
+
+    #[error("Fee shares need distinct recipients with non-zero weights that fit a u64 in total")]
+    InvalidFeeShares {},
+
+    #[error("No fee share recipients configured")]
+    NoFeeShares {},
+
+    #[error("No fees to distribute")]
+    NoFeesToDistribute {},
+
+    #[error("No untracked funds to sweep")]
+    NothingToSweep {},
+
+    #[error("Referrer user ID does not exist")]
+    ReferrerNotFound {},
+
+    #[error("Can't refer yourself")]
+    SelfReferral {},
+
+    #[error("Plan needs a non-zero period and quota")]
+    InvalidPlan {},
+
+    #[error("Allowance must have a non-zero limit and a future expiry")]
+    InvalidAllowance {},
+
+    #[error("Not enough funds deposited")]
+    InsufficientDeposit {},
+
+    #[error("Sponsor code already exists")]
+    SponsorCodeExists {},
+
+    #[error("Sponsor code has expired")]
+    SponsorCodeExpired {},
+
+    #[error("Sponsor code has been used up")]
+    SponsorCodeUsedUp {},
+
+    #[error("Sponsor code belongs to another sponsor")]
+    NotCodeSponsor {},
+
+    #[error("Trade fee can't be greater than 1")]
+    InvalidTradeFee {},
+
+    #[error("Price must be greater than zero")]
+    InvalidPrice {},
+
+    #[error("Address does not own this user ID")]
+    NotUserIdOwner {},
+
+    #[error("Subnames can't be listed for sale")]
+    SubnameNotTradable {},
+
+    #[error("Listing is no longer valid because the seller doesn't own the user ID anymore")]
+    StaleListing {},
+
+    #[error("Can't buy your own user ID")]
+    SelfPurchase {},
+
+    #[error("User ID is the only one of its profile and can't be sold")]
+    SoleUserId {},
+
+    #[error("User ID already registered")]
+    UserIdAlreadyExists {},
+
+    #[error(
+        "User IDs shorter than {below_len} characters can only be registered through an auction"
+    )]
+    UserIdRequiresAuction { below_len: u64 },
+
+    #[error("User ID is not eligible for an auction")]
+    NotAuctioned {},
+
+    #[error("Bid must be at least {min_bid}")]
+    BidTooLow { min_bid: Coin },
+
+    #[error("Auction has already ended")]
+    AuctionEnded {},
+
+    #[error("Auction ends at {end}")]
+    AuctionNotEnded { end: Timestamp },
+
+    #[error("User IDs are registered without a term")]
+    NoRegistrationTerm {},
+
+    #[error("Key backup can't be longer than {max_len} bytes")]
+    KeyBackupTooLong { max_len: u64 },
+
+    #[error("At most {max} profiles can be imported at once")]
+    TooManyImports { max: u64 },
+
+    #[error("Recovery threshold must be between 1 and the number of guardians")]
+    InvalidRecoveryThreshold {},
+
+    #[error("Not a guardian of this address")]
+    NotGuardian {},
+
+
+    #[error("Recovery has not reached its threshold")]
+    RecoveryNotApproved {},
+
+    #[error("Recovery can be executed at {executable_at}")]
+    RecoveryLocked { executable_at: Timestamp },
 }
@@ -0,0 +1,117 @@
+use cosmwasm_std::{
+    to_json_binary, Addr, Binary, CosmosMsg, DepsMut, MessageInfo, Response, Timestamp, WasmMsg,
+};
+use utils::msg::ProfilesExecuteMsg;
+
+use crate::{
+    error::ContractError,
+    state::{Role, PROFILES_ADDRESS},
+};
+
+use super::roles::assert_role;
+
+pub(crate) fn add_verifier(
+    deps: DepsMut,
+    info: MessageInfo,
+    verifier: Addr,
+    pubkey: Binary,
+) -> Result<Response, ContractError> {
+    assert_role(deps.storage, &info.sender, Role::Moderator)?;
+
+    let profile_address = PROFILES_ADDRESS.load(deps.storage)?;
+    let add_verifier_msg = ProfilesExecuteMsg::AddVerifier {
+        verifier: deps.api.addr_validate(verifier.as_str())?,
+        pubkey,
+    };
+    let msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: profile_address.to_string(),
+        msg: to_json_binary(&add_verifier_msg)?,
+        funds: vec![],
+    });
+
+    Ok(Response::new()
+        .add_message(msg)
+        .add_attribute("action", "add_verifier")
+        .add_attribute("sender", info.sender)
+        .add_attribute("verifier", verifier))
+}
+
+pub(crate) fn remove_verifier(
+    deps: DepsMut,
+    info: MessageInfo,
+    verifier: Addr,
+) -> Result<Response, ContractError> {
+    assert_role(deps.storage, &info.sender, Role::Moderator)?;
+
+    let profile_address = PROFILES_ADDRESS.load(deps.storage)?;
+    let remove_verifier_msg = ProfilesExecuteMsg::RemoveVerifier {
+        verifier: verifier.clone(),
+    };
+    let msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: profile_address.to_string(),
+        msg: to_json_binary(&remove_verifier_msg)?,
+        funds: vec![],
+    });
+
+    Ok(Response::new()
+        .add_message(msg)
+        .add_attribute("action", "remove_verifier")
+        .add_attribute("sender", info.sender)
+        .add_attribute("verifier", verifier))
+}
+
+pub(crate) fn attest(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: Addr,
+    kind: String,
+    expires: Timestamp,
+    signature: Binary,
+) -> Result<Response, ContractError> {
+    let profile_address = PROFILES_ADDRESS.load(deps.storage)?;
+    let attest_msg = ProfilesExecuteMsg::Attest {
+        address: address.clone(),
+        verifier: info.sender.clone(),
+        kind: kind.clone(),
+        expires,
+        signature,
+    };
+    let msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: profile_address.to_string(),
+        msg: to_json_binary(&attest_msg)?,
+        funds: vec![],
+    });
+
+    Ok(Response::new()
+        .add_message(msg)
+        .add_attribute("action", "attest")
+        .add_attribute("sender", info.sender)
+        .add_attribute("address", address)
+        .add_attribute("kind", kind))
+}
+
+pub(crate) fn revoke_attestation(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: Addr,
+    kind: String,
+) -> Result<Response, ContractError> {
+    let profile_address = PROFILES_ADDRESS.load(deps.storage)?;
+    let revoke_msg = ProfilesExecuteMsg::RevokeAttestation {
+        address: address.clone(),
+        verifier: info.sender.clone(),
+        kind: kind.clone(),
+    };
+    let msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: profile_address.to_string(),
+        msg: to_json_binary(&revoke_msg)?,
+        funds: vec![],
+    });
+
+    Ok(Response::new()
+        .add_message(msg)
+        .add_attribute("action", "revoke_attestation")
+        .add_attribute("sender", info.sender)
+        .add_attribute("address", address)
+        .add_attribute("kind", kind))
+}
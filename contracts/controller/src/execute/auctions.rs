@@ -0,0 +1,224 @@
+use cosmwasm_std::{
+    to_json_binary, BankMsg, Coin, CosmosMsg, DepsMut, Empty, Env, MessageInfo, Reply, Response,
+    StdResult, SubMsg, Uint128, WasmMsg,
+};
+use cw_utils::one_coin;
+use utils::{
+    msg::ProfilesExecuteMsg,
+    query::{ExpirationResponse, ProfileInfo, ProfilesQueryMsg},
+};
+
+use crate::{
+    contract::SETTLE_AUCTION_REPLY_ID,
+    error::ContractError,
+    state::{
+        Auction, AUCTIONS, BIDDER_AUCTIONS, CONFIG, LISTINGS, PROFILES_ADDRESS, SETTLING_AUCTION,
+    },
+};
+
+use super::fees::{record_fees, unrecord_fees};
+use super::profiles::user_id_len;
+
+pub(crate) fn bid_user_id(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    user_id: String,
+    pubkey: Option<String>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let profile_address = PROFILES_ADDRESS.load(deps.storage)?;
+    let auction_config = match config.auction {
+        Some(auction)
+            if !user_id.is_empty()
+                && !user_id.contains('.')
+                && user_id_len(&user_id) < auction.below_len =>
+        {
+            auction
+        }
+        _ => return Err(ContractError::NotAuctioned {}),
+    };
+
+    let bid = one_coin(&info)?;
+    let mut refund_msgs = vec![];
+
+    let auction = match AUCTIONS.may_load(deps.storage, user_id.clone())? {
+        None => {
+            // Ids past their grace period can be auctioned again.
+            let expiration: StdResult<ExpirationResponse> = deps.querier.query_wasm_smart(
+                profile_address.clone(),
+                &ProfilesQueryMsg::Expiration {
+                    user_id: user_id.clone(),
+                },
+            );
+            if let Ok(expiration) = expiration {
+                if expiration
+                    .grace_end
+                    .map_or(true, |grace_end| env.block.time < grace_end)
+                {
+                    return Err(ContractError::UserIdAlreadyExists {});
+                }
+            }
+            if bid.denom != auction_config.min_bid.denom
+                || bid.amount < auction_config.min_bid.amount
+            {
+                return Err(ContractError::BidTooLow {
+                    min_bid: auction_config.min_bid,
+                });
+            }
+
+            Auction {
+                highest_bidder: info.sender.clone(),
+                highest_bid: bid.clone(),
+                pubkey,
+                end: env.block.time.plus_seconds(auction_config.duration),
+            }
+        }
+        Some(mut auction) => {
+            if env.block.time >= auction.end {
+                return Err(ContractError::AuctionEnded {});
+            }
+            if bid.denom != auction.highest_bid.denom || bid.amount <= auction.highest_bid.amount {
+                return Err(ContractError::BidTooLow {
+                    min_bid: Coin {
+                        denom: auction.highest_bid.denom,
+                        amount: auction.highest_bid.amount + Uint128::one(),
+                    },
+                });
+            }
+
+            refund_msgs.push(BankMsg::Send {
+                to_address: auction.highest_bidder.to_string(),
+                amount: vec![auction.highest_bid],
+            });
+            BIDDER_AUCTIONS.remove(
+                deps.storage,
+                (auction.highest_bidder.clone(), user_id.clone()),
+            );
+            auction.highest_bidder = info.sender.clone();
+            auction.highest_bid = bid.clone();
+            auction.pubkey = pubkey;
+
+            // Anti-sniping: late bids leave the other bidders at least `extension` seconds to answer.
+            let extended_end = env.block.time.plus_seconds(auction_config.extension);
+            if extended_end > auction.end {
+                auction.end = extended_end;
+            }
+            auction
+        }
+    };
+
+    // A bid the profiles contract would refuse to register can't win, so it isn't taken.
+    deps.querier.query_wasm_smart::<Empty>(
+        profile_address,
+        &ProfilesQueryMsg::CheckRegistration {
+            address: info.sender.clone(),
+            user_id: user_id.clone(),
+            pubkey: auction.pubkey.clone(),
+        },
+    )?;
+
+    AUCTIONS.save(deps.storage, user_id.clone(), &auction)?;
+    BIDDER_AUCTIONS.save(
+        deps.storage,
+        (info.sender.clone(), user_id.clone()),
+        &Empty {},
+    )?;
+
+    Ok(Response::new()
+        .add_messages(refund_msgs)
+        .add_attribute("action", "bid_user_id")
+        .add_attribute("sender", info.sender)
+        .add_attribute("user_id", user_id)
+        .add_attribute("bid", bid.to_string())
+        .add_attribute("end", auction.end.to_string()))
+}
+
+pub(crate) fn settle_auction(
+    deps: DepsMut,
+    env: Env,
+    user_id: String,
+) -> Result<Response, ContractError> {
+    let auction = AUCTIONS.load(deps.storage, user_id.clone())?;
+    if env.block.time < auction.end {
+        return Err(ContractError::AuctionNotEnded { end: auction.end });
+    }
+    AUCTIONS.remove(deps.storage, user_id.clone());
+    BIDDER_AUCTIONS.remove(
+        deps.storage,
+        (auction.highest_bidder.clone(), user_id.clone()),
+    );
+    LISTINGS.remove(deps.storage, user_id.clone());
+
+    let config = CONFIG.load(deps.storage)?;
+    let profile_address = PROFILES_ADDRESS.load(deps.storage)?;
+    let winner_profile: StdResult<ProfileInfo> = deps.querier.query_wasm_smart(
+        profile_address.clone(),
+        &ProfilesQueryMsg::AddressInfo {
+            address: auction.highest_bidder.clone(),
+        },
+    );
+    let register_msg = match (winner_profile, &auction.pubkey) {
+        (Ok(_), _) => Some(ProfilesExecuteMsg::AddAlias {
+            address: auction.highest_bidder.clone(),
+            alias: user_id.clone(),
+            term: config.registration_term,
+        }),
+        (Err(_), Some(pubkey)) => Some(ProfilesExecuteMsg::CreateProfile {
+            address: auction.highest_bidder.clone(),
+            user_id: user_id.clone(),
+            pubkey: pubkey.clone(),
+            term: config.registration_term,
+        }),
+        // The winner bid with its profile and deleted it since.
+        (Err(_), None) => None,
+    };
+    let Some(register_msg) = register_msg else {
+        let refund_msg = BankMsg::Send {
+            to_address: auction.highest_bidder.to_string(),
+            amount: vec![auction.highest_bid.clone()],
+        };
+        return Ok(Response::new()
+            .add_message(refund_msg)
+            .add_attribute("action", "settle_auction")
+            .add_attribute("user_id", user_id)
+            .add_attribute("winner", auction.highest_bidder)
+            .add_attribute("refunded", auction.highest_bid.to_string()));
+    };
+
+    // The winning bid stays in the controller as the registration fee.
+    record_fees(deps.storage, std::slice::from_ref(&auction.highest_bid))?;
+    SETTLING_AUCTION.save(deps.storage, &(user_id.clone(), auction.clone()))?;
+    let msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: profile_address.to_string(),
+        msg: to_json_binary(&register_msg)?,
+        funds: vec![],
+    });
+
+    Ok(Response::new()
+        .add_submessage(SubMsg::reply_on_error(msg, SETTLE_AUCTION_REPLY_ID))
+        .add_attribute("action", "settle_auction")
+        .add_attribute("user_id", user_id)
+        .add_attribute("winner", auction.highest_bidder)
+        .add_attribute("price", auction.highest_bid.to_string()))
+}
+
+// The winner's registration failed: the auction is dropped and its bid refunded instead of kept.
+pub(crate) fn refund_auction(deps: DepsMut, msg: Reply) -> Result<Response, ContractError> {
+    let (user_id, auction) = SETTLING_AUCTION.load(deps.storage)?;
+    SETTLING_AUCTION.remove(deps.storage);
+    unrecord_fees(deps.storage, std::slice::from_ref(&auction.highest_bid))?;
+
+    let refund_msg = BankMsg::Send {
+        to_address: auction.highest_bidder.to_string(),
+        amount: vec![auction.highest_bid.clone()],
+    };
+
+    Ok(Response::new()
+        .add_message(refund_msg)
+        .add_attribute("action", "settle_auction_refund")
+        .add_attribute("user_id", user_id)
+        .add_attribute("winner", auction.highest_bidder)
+        .add_attribute("refunded", auction.highest_bid.to_string())
+        .add_attribute("error", msg.result.unwrap_err()))
+}
@@ -0,0 +1,163 @@
+use cosmwasm_std::{
+    from_json, to_json_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Response,
+    StdResult, Storage, WasmMsg,
+};
+use cw2::ContractVersion;
+use cw_ownable::{assert_owner, Ownership};
+use utils::msg::{MessagesExecuteMsg, ProfilesExecuteMsg};
+
+use crate::{
+    error::ContractError,
+    state::{ChildContract, ProposalAction, Role, MESSAGES_ADDRESS, PROFILES_ADDRESS},
+};
+
+use super::proposals::schedule_proposal;
+use super::roles::assert_role;
+
+pub(crate) fn migrate_child(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    contract: ChildContract,
+    new_code_id: u64,
+    msg: Binary,
+) -> Result<Response, ContractError> {
+    assert_role(deps.storage, &info.sender, Role::Admin)?;
+
+    schedule_proposal(
+        deps,
+        env,
+        info,
+        ProposalAction::MigrateChild {
+            contract,
+            new_code_id,
+            msg,
+        },
+    )
+}
+
+pub(crate) fn set_child_contract(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    contract: ChildContract,
+    address: Addr,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+    let address = deps.api.addr_validate(address.as_ref())?;
+
+    // Rejects unsuitable contracts now rather than when the proposal is executed.
+    check_child_contract(deps.as_ref(), &env, &contract, &address)?;
+
+    schedule_proposal(
+        deps,
+        env,
+        info,
+        ProposalAction::SetChildContract { contract, address },
+    )
+}
+
+// Checks a replacement child. Returns its version and whether the controller is only its pending
+// owner yet.
+fn check_child_contract(
+    deps: Deps,
+    env: &Env,
+    contract: &ChildContract,
+    address: &Addr,
+) -> Result<(ContractVersion, bool), ContractError> {
+    let version = cw2::query_contract_info(&deps.querier, address.clone())?;
+    if version.contract != contract.contract_name() {
+        return Err(ContractError::ChildContractMismatch {
+            expected: contract.contract_name().to_string(),
+            found: version.contract,
+        });
+    }
+
+    let current = cw2::query_contract_info(&deps.querier, child_address(deps.storage, contract)?)?;
+    if parse_version(&version.version)? < parse_version(&current.version)? {
+        return Err(ContractError::ChildVersionTooLow {
+            current: current.version,
+            found: version.version,
+        });
+    }
+
+    let info = deps.querier.query_wasm_contract_info(address.clone())?;
+    if info.admin.as_deref() != Some(env.contract.address.as_str()) {
+        return Err(ContractError::ChildNotAdministered {});
+    }
+
+    // Raw read of the cw-ownable ownership item of the child.
+    let ownership = deps
+        .querier
+        .query_wasm_raw(address.clone(), b"ownership".as_slice())?
+        .map(|raw| from_json::<Ownership<Addr>>(&raw))
+        .transpose()?
+        .ok_or(ContractError::ChildNotOwned {})?;
+    if ownership.owner.as_ref() == Some(&env.contract.address) {
+        return Ok((version, false));
+    }
+    let pending = ownership.pending_owner.as_ref() == Some(&env.contract.address)
+        && !ownership
+            .pending_expiry
+            .map_or(false, |expiry| expiry.is_expired(&env.block));
+    if !pending {
+        return Err(ContractError::ChildNotOwned {});
+    }
+    Ok((version, true))
+}
+
+fn parse_version(version: &str) -> Result<semver::Version, ContractError> {
+    semver::Version::parse(version).map_err(|_| ContractError::InvalidContractVersion {
+        version: version.to_string(),
+    })
+}
+
+pub(crate) fn child_address(storage: &dyn Storage, contract: &ChildContract) -> StdResult<Addr> {
+    match contract {
+        ChildContract::Profiles => PROFILES_ADDRESS.load(storage),
+        ChildContract::Messages => MESSAGES_ADDRESS.load(storage),
+    }
+}
+
+pub(crate) fn apply_child_contract(
+    deps: DepsMut,
+    env: Env,
+    contract: ChildContract,
+    address: Addr,
+) -> Result<Response, ContractError> {
+    let (version, accept_ownership) =
+        check_child_contract(deps.as_ref(), &env, &contract, &address)?;
+
+    let previous = child_address(deps.storage, &contract)?;
+    match contract {
+        ChildContract::Profiles => PROFILES_ADDRESS.save(deps.storage, &address)?,
+        ChildContract::Messages => MESSAGES_ADDRESS.save(deps.storage, &address)?,
+    }
+
+    let mut msgs = vec![];
+    if accept_ownership {
+        let accept = cw_ownable::Action::AcceptOwnership;
+        let msg = match contract {
+            ChildContract::Profiles => {
+                to_json_binary(&ProfilesExecuteMsg::UpdateOwnership(accept))?
+            }
+            ChildContract::Messages => {
+                to_json_binary(&MessagesExecuteMsg::UpdateOwnership(accept))?
+            }
+        };
+        msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: address.to_string(),
+            msg,
+            funds: vec![],
+        }));
+    }
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_attribute("action", "set_child_contract")
+        .add_attribute("contract", contract.contract_name())
+        .add_attribute("previous", previous)
+        .add_attribute("address", address)
+        .add_attribute("version", version.version)
+        .add_attribute("accept_ownership", accept_ownership.to_string()))
+}
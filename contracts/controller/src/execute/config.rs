@@ -0,0 +1,289 @@
+use cosmwasm_std::{
+    to_json_binary, Addr, Coin, CosmosMsg, Decimal, DepsMut, Env, MessageInfo, Order, Response,
+    WasmMsg,
+};
+use utils::msg::{MessagesExecuteMsg, ProfilesExecuteMsg};
+
+use crate::{
+    error::ContractError,
+    state::{
+        Config, ConfigChange, ConfigFieldChange, ConfigUpdate, ProposalAction, Role, CONFIG,
+        CONFIG_HISTORY, MESSAGES_ADDRESS, PROFILES_ADDRESS,
+    },
+};
+
+use super::proposals::schedule_proposal;
+use super::roles::assert_role;
+
+pub(crate) fn change_messages_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    message_query_default_limit: u64,
+    message_query_max_limit: u64,
+) -> Result<Response, ContractError> {
+    assert_role(deps.storage, &info.sender, Role::ConfigManager)?;
+
+    let messages_address = MESSAGES_ADDRESS.load(deps.storage)?;
+
+    let change_pubkey_msg = MessagesExecuteMsg::ChangeConfig {
+        default_query_limit: message_query_default_limit,
+        max_query_limit: message_query_max_limit,
+    };
+
+    let msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: messages_address.to_string(),
+        msg: to_json_binary(&change_pubkey_msg)?,
+        funds: vec![],
+    });
+
+    Ok(Response::new()
+        .add_message(msg)
+        .add_attribute("action", "change_messages_config")
+        .add_attribute("sender", info.sender))
+}
+
+pub(crate) fn change_profiles_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    user_id_cooldown: u64,
+    max_aliases: u64,
+    user_id_grace_period: u64,
+    user_id_change_cooldown: u64,
+    user_id_redirect_period: u64,
+) -> Result<Response, ContractError> {
+    assert_role(deps.storage, &info.sender, Role::ConfigManager)?;
+
+    let profiles_address = PROFILES_ADDRESS.load(deps.storage)?;
+
+    let change_config_msg = ProfilesExecuteMsg::ChangeConfig {
+        user_id_cooldown,
+        max_aliases,
+        grace_period: user_id_grace_period,
+        change_cooldown: user_id_change_cooldown,
+        redirect_period: user_id_redirect_period,
+    };
+
+    let msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: profiles_address.to_string(),
+        msg: to_json_binary(&change_config_msg)?,
+        funds: vec![],
+    });
+
+    Ok(Response::new()
+        .add_message(msg)
+        .add_attribute("action", "change_profiles_config")
+        .add_attribute("sender", info.sender))
+}
+
+pub(crate) fn update_config(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    update: Box<ConfigUpdate>,
+) -> Result<Response, ContractError> {
+    assert_role(deps.storage, &info.sender, Role::ConfigManager)?;
+
+    // Rejects invalid updates now rather than when the proposal is executed.
+    updated_config(CONFIG.load(deps.storage)?, (*update).clone())?;
+
+    schedule_proposal(deps, env, info, ProposalAction::UpdateConfig(update))
+}
+
+pub(crate) fn apply_config_update(
+    deps: DepsMut,
+    env: Env,
+    proposer: Addr,
+    update: ConfigUpdate,
+) -> Result<Response, ContractError> {
+    let (config, changes) = updated_config(CONFIG.load(deps.storage)?, update)?;
+    CONFIG.save(deps.storage, &config)?;
+
+    let id = CONFIG_HISTORY
+        .keys(deps.storage, None, None, Order::Descending)
+        .next()
+        .transpose()?
+        .map_or(0, |last| last + 1);
+    CONFIG_HISTORY.save(
+        deps.storage,
+        id,
+        &ConfigChange {
+            sender: proposer.clone(),
+            time: env.block.time,
+            changes: changes.clone(),
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_config")
+        .add_attribute("sender", proposer)
+        .add_attributes(
+            changes
+                .into_iter()
+                .map(|change| (change.field, change.value)),
+        ))
+}
+
+// Applies `update` to `config`, returning the validated config and the changed fields.
+fn updated_config(
+    mut config: Config,
+    update: ConfigUpdate,
+) -> Result<(Config, Vec<ConfigFieldChange>), ContractError> {
+    let mut changes = vec![];
+
+    if let Some(message_max_len) = update.message_max_len {
+        config.message_max_len = message_max_len;
+        changes.push(("message_max_len", message_max_len.to_string()));
+    }
+    if let Some(message_prices) = update.message_prices {
+        let value = message_prices
+            .iter()
+            .map(|price| format!("{}:{}+{}/byte", price.denom, price.base, price.per_byte))
+            .collect::<Vec<String>>()
+            .join(",");
+        config.message_prices = message_prices;
+        changes.push(("message_prices", value));
+    }
+    if let Some(profile_cost) = update.profile_cost {
+        config.profile_cost = non_zero_coin(profile_cost);
+        changes.push(("profile_cost", coin_value(&config.profile_cost)));
+    }
+    if let Some(trade_fee) = update.trade_fee {
+        config.trade_fee = trade_fee;
+        changes.push(("trade_fee", trade_fee.to_string()));
+    }
+    if let Some(referral_share) = update.referral_share {
+        config.referral_share = referral_share;
+        changes.push(("referral_share", referral_share.to_string()));
+    }
+    if let Some(length_prices) = update.length_prices {
+        let value = length_prices
+            .iter()
+            .map(|price| format!("{}:{}", price.max_len, price.cost))
+            .collect::<Vec<String>>()
+            .join(",");
+        config.length_prices = length_prices;
+        changes.push(("length_prices", value));
+    }
+    if let Some(auction) = update.auction {
+        let value = if auction.below_len == 0 {
+            "none".to_string()
+        } else {
+            format!(
+                "below_len:{},duration:{},extension:{},min_bid:{}",
+                auction.below_len, auction.duration, auction.extension, auction.min_bid
+            )
+        };
+        config.auction = Some(auction).filter(|auction| auction.below_len != 0);
+        changes.push(("auction", value));
+    }
+    if let Some(registration_term) = update.registration_term {
+        config.registration_term = Some(registration_term).filter(|term| *term != 0);
+        changes.push(("registration_term", registration_term.to_string()));
+    }
+    if let Some(change_fee) = update.change_fee {
+        config.change_fee = non_zero_coin(change_fee);
+        changes.push(("change_fee", coin_value(&config.change_fee)));
+    }
+    if let Some(recovery_delay) = update.recovery_delay {
+        config.recovery_delay = recovery_delay;
+        changes.push(("recovery_delay", recovery_delay.to_string()));
+    }
+    if let Some(key_backup_max_len) = update.key_backup_max_len {
+        config.key_backup_max_len = key_backup_max_len;
+        changes.push(("key_backup_max_len", key_backup_max_len.to_string()));
+    }
+    if let Some(timelock_delay) = update.timelock_delay {
+        config.timelock_delay = timelock_delay;
+        changes.push(("timelock_delay", timelock_delay.to_string()));
+    }
+
+    if changes.is_empty() {
+        return Err(ContractError::EmptyConfigUpdate {});
+    }
+    validate_config(&config)?;
+
+    let changes = changes
+        .into_iter()
+        .map(|(field, value)| ConfigFieldChange {
+            field: field.to_string(),
+            value,
+        })
+        .collect();
+    Ok((config, changes))
+}
+
+pub(crate) fn validate_config(config: &Config) -> Result<(), ContractError> {
+    if config.message_max_len == 0 {
+        return Err(invalid_config("message_max_len"));
+    }
+    if config.key_backup_max_len == 0 {
+        return Err(invalid_config("key_backup_max_len"));
+    }
+    if config.trade_fee > Decimal::one() {
+        return Err(ContractError::InvalidTradeFee {});
+    }
+    if config.referral_share > Decimal::one() {
+        return Err(invalid_config("referral_share"));
+    }
+    if config.registration_term == Some(0) {
+        return Err(invalid_config("registration_term"));
+    }
+    if config.length_prices.iter().any(|price| price.max_len == 0) {
+        return Err(invalid_config("length_prices"));
+    }
+    if let Some(auction) = &config.auction {
+        if auction.below_len == 0 || auction.duration == 0 {
+            return Err(invalid_config("auction"));
+        }
+    }
+
+    for (i, price) in config.message_prices.iter().enumerate() {
+        validate_denom(&price.denom)?;
+        if config.message_prices[..i]
+            .iter()
+            .any(|other| other.denom == price.denom)
+        {
+            return Err(invalid_config("message_prices"));
+        }
+    }
+
+    let coins = [&config.profile_cost, &config.change_fee]
+        .into_iter()
+        .flatten()
+        .chain(config.length_prices.iter().map(|price| &price.cost))
+        .chain(config.auction.iter().map(|auction| &auction.min_bid));
+    for coin in coins {
+        validate_denom(&coin.denom)?;
+    }
+
+    Ok(())
+}
+
+/// Follows the Cosmos SDK denom format: a letter followed by 2 to 127 letters, digits or `/:._-`.
+pub(crate) fn validate_denom(denom: &str) -> Result<(), ContractError> {
+    let mut chars = denom.chars();
+    let valid = (3..=128).contains(&denom.len())
+        && matches!(chars.next(), Some(c) if c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || "/:._-".contains(c));
+    if !valid {
+        return Err(ContractError::InvalidDenom {
+            denom: denom.to_string(),
+        });
+    }
+    Ok(())
+}
+
+fn invalid_config(field: &str) -> ContractError {
+    ContractError::InvalidConfig {
+        field: field.to_string(),
+    }
+}
+
+fn non_zero_coin(coin: Coin) -> Option<Coin> {
+    Some(coin).filter(|coin| !coin.amount.is_zero())
+}
+
+fn coin_value(coin: &Option<Coin>) -> String {
+    coin.as_ref()
+        .map_or_else(|| "none".to_string(), |coin| coin.to_string())
+}
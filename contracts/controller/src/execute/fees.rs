@@ -0,0 +1,251 @@
+use cosmwasm_std::{
+    Addr, BankMsg, Coin, DepsMut, Env, MessageInfo, Order, Response, StdResult, Storage, Uint128,
+};
+use cw_ownable::assert_owner;
+
+use crate::{
+    error::ContractError,
+    state::{
+        FeeShare, ProposalAction, Role, AUCTIONS, COLLECTED_FEES, FEE_SHARES, LIFETIME_FEES,
+        SPONSOR_DEPOSITS,
+    },
+};
+
+use super::proposals::schedule_proposal;
+use super::roles::assert_role;
+
+pub(crate) fn set_fee_shares(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    shares: Vec<FeeShare>,
+) -> Result<Response, ContractError> {
+    assert_role(deps.storage, &info.sender, Role::Admin)?;
+
+    for (i, share) in shares.iter().enumerate() {
+        deps.api.addr_validate(share.recipient.as_ref())?;
+        if share.weight == 0
+            || shares[..i]
+                .iter()
+                .any(|other| other.recipient == share.recipient)
+        {
+            return Err(ContractError::InvalidFeeShares {});
+        }
+    }
+    total_weight(&shares)?;
+
+    schedule_proposal(deps, env, info, ProposalAction::SetFeeShares { shares })
+}
+
+fn total_weight(shares: &[FeeShare]) -> Result<u64, ContractError> {
+    shares
+        .iter()
+        .try_fold(0u64, |total, share| total.checked_add(share.weight))
+        .ok_or(ContractError::InvalidFeeShares {})
+}
+
+pub(crate) fn apply_fee_shares(
+    deps: DepsMut,
+    proposer: Addr,
+    shares: Vec<FeeShare>,
+) -> Result<Response, ContractError> {
+    FEE_SHARES.save(deps.storage, &shares)?;
+
+    let recipients = shares
+        .iter()
+        .map(|share| format!("{}:{}", share.recipient, share.weight))
+        .collect::<Vec<String>>()
+        .join(",");
+
+    Ok(Response::new()
+        .add_attribute("action", "set_fee_shares")
+        .add_attribute("sender", proposer)
+        .add_attribute("shares", recipients))
+}
+
+pub(crate) fn distribute_fees(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    assert_role(deps.storage, &info.sender, Role::FeeCollector)?;
+
+    let shares = FEE_SHARES.may_load(deps.storage)?.unwrap_or_default();
+    if shares.is_empty() {
+        return Err(ContractError::NoFeeShares {});
+    }
+    let fees = take_collected_fees(deps.storage)?;
+
+    let total_weight = total_weight(&shares)?;
+    let mut payouts: Vec<Vec<Coin>> = vec![vec![]; shares.len()];
+    for (denom, amount) in &fees {
+        let mut remaining = *amount;
+        for (i, share) in shares.iter().enumerate().skip(1) {
+            let part = amount.multiply_ratio(share.weight, total_weight);
+            remaining -= part;
+            payouts[i].push(Coin {
+                denom: denom.clone(),
+                amount: part,
+            });
+        }
+        // Rounding leftovers go to the first recipient so the exact tracked amount is paid out.
+        payouts[0].push(Coin {
+            denom: denom.clone(),
+            amount: remaining,
+        });
+    }
+
+    let msgs = shares
+        .iter()
+        .zip(payouts)
+        .filter_map(|(share, mut amount)| {
+            amount.retain(|coin| !coin.amount.is_zero());
+            (!amount.is_empty()).then(|| BankMsg::Send {
+                to_address: share.recipient.to_string(),
+                amount,
+            })
+        })
+        .collect::<Vec<BankMsg>>();
+
+    let distributed = fees
+        .into_iter()
+        .map(|(denom, amount)| Coin { denom, amount }.to_string())
+        .collect::<Vec<String>>()
+        .join(",");
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_attribute("action", "distribute_fees")
+        .add_attribute("sender", info.sender)
+        .add_attribute("amount", distributed))
+}
+
+// Pays all collected fees to a single receiver, the sender by default, as before fee shares.
+pub(crate) fn retrieve_fees(
+    deps: DepsMut,
+    info: MessageInfo,
+    receiver: Option<Addr>,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+    let receiver = deps
+        .api
+        .addr_validate(receiver.unwrap_or_else(|| info.sender.clone()).as_str())?;
+
+    let amount = take_collected_fees(deps.storage)?
+        .into_iter()
+        .map(|(denom, amount)| Coin { denom, amount })
+        .collect::<Vec<Coin>>();
+    let retrieved = amount
+        .iter()
+        .map(|coin| coin.to_string())
+        .collect::<Vec<String>>()
+        .join(",");
+
+    Ok(Response::new()
+        .add_message(BankMsg::Send {
+            to_address: receiver.to_string(),
+            amount,
+        })
+        .add_attribute("action", "retrieve_fees")
+        .add_attribute("sender", info.sender)
+        .add_attribute("recipient", receiver)
+        .add_attribute("amount", retrieved))
+}
+
+// Empties `COLLECTED_FEES`, returning what it held.
+fn take_collected_fees(storage: &mut dyn Storage) -> Result<Vec<(String, Uint128)>, ContractError> {
+    let fees = COLLECTED_FEES
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<(String, Uint128)>>>()?;
+    if fees.is_empty() {
+        return Err(ContractError::NoFeesToDistribute {});
+    }
+    COLLECTED_FEES.clear(storage);
+
+    Ok(fees)
+}
+
+// Funds the controller holds for someone are left: undistributed fees, bids and sponsor deposits.
+pub(crate) fn sweep_funds(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipient: Addr,
+) -> Result<Response, ContractError> {
+    assert_role(deps.storage, &info.sender, Role::Admin)?;
+    let recipient = deps.api.addr_validate(recipient.as_str())?;
+
+    let mut tracked = COLLECTED_FEES
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<(String, Uint128)>>>()?;
+    for item in AUCTIONS.range(deps.storage, None, None, Order::Ascending) {
+        let (_, auction) = item?;
+        tracked.push((auction.highest_bid.denom, auction.highest_bid.amount));
+    }
+    for item in SPONSOR_DEPOSITS.range(deps.storage, None, None, Order::Ascending) {
+        let ((_, denom), amount) = item?;
+        tracked.push((denom, amount));
+    }
+
+    let mut untracked = vec![];
+    for balance in deps.querier.query_all_balances(env.contract.address)? {
+        let held = tracked
+            .iter()
+            .filter(|(denom, _)| *denom == balance.denom)
+            .try_fold(Uint128::zero(), |total, (_, amount)| -> StdResult<_> {
+                Ok(total.checked_add(*amount)?)
+            })?;
+        let amount = balance.amount.saturating_sub(held);
+        if !amount.is_zero() {
+            untracked.push(Coin {
+                denom: balance.denom,
+                amount,
+            });
+        }
+    }
+    if untracked.is_empty() {
+        return Err(ContractError::NothingToSweep {});
+    }
+
+    let swept = untracked
+        .iter()
+        .map(Coin::to_string)
+        .collect::<Vec<String>>()
+        .join(",");
+
+    Ok(Response::new()
+        .add_message(BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: untracked,
+        })
+        .add_attribute("action", "sweep_funds")
+        .add_attribute("sender", info.sender)
+        .add_attribute("recipient", recipient)
+        .add_attribute("amount", swept))
+}
+
+// Adds fees kept by the controller to the undistributed and lifetime totals.
+pub(crate) fn record_fees(storage: &mut dyn Storage, fees: &[Coin]) -> StdResult<()> {
+    for fee in fees.iter().filter(|fee| !fee.amount.is_zero()) {
+        for map in [COLLECTED_FEES, LIFETIME_FEES] {
+            map.update(storage, fee.denom.clone(), |total| -> StdResult<_> {
+                Ok(total.unwrap_or_default().checked_add(fee.amount)?)
+            })?;
+        }
+    }
+    Ok(())
+}
+
+// Reverses `record_fees`, for fees taken in a message whose effects are undone afterwards.
+pub(crate) fn unrecord_fees(storage: &mut dyn Storage, fees: &[Coin]) -> StdResult<()> {
+    for fee in fees.iter().filter(|fee| !fee.amount.is_zero()) {
+        for map in [COLLECTED_FEES, LIFETIME_FEES] {
+            let total = map
+                .may_load(storage, fee.denom.clone())?
+                .unwrap_or_default()
+                .checked_sub(fee.amount)?;
+            if total.is_zero() {
+                map.remove(storage, fee.denom.clone());
+            } else {
+                map.save(storage, fee.denom.clone(), &total)?;
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,90 @@
+use cosmwasm_std::{
+    from_json, to_json_binary, CosmosMsg, DepsMut, MessageInfo, Reply, Response, SubMsg, WasmMsg,
+};
+use cw_utils::parse_reply_execute_data;
+use utils::msg::{ImportEntry, ImportFailure, ImportProfilesResponse, ProfilesExecuteMsg};
+
+use crate::{
+    contract::{IMPORT_PROFILES_REPLY_ID, MAX_IMPORT_ENTRIES},
+    error::ContractError,
+    state::{Role, CONFIG, IMPORT_FAILURES, PROFILES_ADDRESS},
+};
+
+use super::profiles::{assert_not_auctioned, remove_listings};
+use super::roles::assert_role;
+
+pub(crate) fn import_profiles(
+    deps: DepsMut,
+    info: MessageInfo,
+    entries: Vec<ImportEntry>,
+) -> Result<Response, ContractError> {
+    assert_role(deps.storage, &info.sender, Role::Admin)?;
+
+    if entries.len() as u64 > MAX_IMPORT_ENTRIES {
+        return Err(ContractError::TooManyImports {
+            max: MAX_IMPORT_ENTRIES,
+        });
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+    let profile_address = PROFILES_ADDRESS.load(deps.storage)?;
+    let entry_count = entries.len();
+    let mut forwarded = vec![];
+    let mut failures = vec![];
+    for entry in entries {
+        match assert_not_auctioned(&config, &entry.user_id) {
+            Ok(()) => forwarded.push(entry),
+            Err(error) => failures.push(ImportFailure {
+                address: entry.address,
+                user_id: entry.user_id,
+                error: error.to_string(),
+            }),
+        }
+    }
+
+    let response = Response::new()
+        .add_attribute("action", "import_profiles")
+        .add_attribute("sender", info.sender)
+        .add_attribute("entries", entry_count.to_string());
+    if forwarded.is_empty() {
+        return Ok(response.set_data(to_json_binary(&ImportProfilesResponse {
+            imported: vec![],
+            failures,
+        })?));
+    }
+
+    IMPORT_FAILURES.save(deps.storage, &failures)?;
+    let import_msg = ProfilesExecuteMsg::ImportProfiles {
+        entries: forwarded,
+        term: config.registration_term,
+    };
+    let msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: profile_address.to_string(),
+        msg: to_json_binary(&import_msg)?,
+        funds: vec![],
+    });
+
+    // The reply merges the per-entry results of the profiles contract into our response data.
+    Ok(response.add_submessage(SubMsg::reply_on_success(msg, IMPORT_PROFILES_REPLY_ID)))
+}
+
+// Removes the listings left by previous holders of the imported user ids and reports the entries
+// rejected here along with those rejected by the profiles contract.
+pub(crate) fn import_profiles_reply(deps: DepsMut, msg: Reply) -> Result<Response, ContractError> {
+    let res = parse_reply_execute_data(msg)?;
+    let result: ImportProfilesResponse = from_json(res.data.unwrap_or_default())?;
+    remove_listings(deps.storage, &result.imported);
+
+    let mut failures = IMPORT_FAILURES.load(deps.storage)?;
+    IMPORT_FAILURES.remove(deps.storage);
+    failures.extend(result.failures);
+
+    Ok(Response::default()
+        .add_attribute("action", "import_profiles_reply")
+        .add_attribute("imported", result.imported.len().to_string())
+        .add_attribute("failed", failures.len().to_string())
+        .set_data(to_json_binary(&ImportProfilesResponse {
+            imported: result.imported,
+            failures,
+        })?))
+}
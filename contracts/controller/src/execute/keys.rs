@@ -0,0 +1,131 @@
+use cosmwasm_std::{
+    to_json_binary, Addr, Binary, CosmosMsg, DepsMut, MessageInfo, Response, WasmMsg,
+};
+use utils::{elements::KeyShare, msg::ProfilesExecuteMsg};
+
+use crate::{
+    error::ContractError,
+    state::{CONFIG, PROFILES_ADDRESS},
+};
+
+pub(crate) fn set_key_backup(
+    deps: DepsMut,
+    info: MessageInfo,
+    data: Binary,
+    expected_version: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if data.len() as u64 > config.key_backup_max_len {
+        return Err(ContractError::KeyBackupTooLong {
+            max_len: config.key_backup_max_len,
+        });
+    }
+
+    let profile_address = PROFILES_ADDRESS.load(deps.storage)?;
+    let set_backup_msg = ProfilesExecuteMsg::SetKeyBackup {
+        address: info.sender.clone(),
+        data,
+        expected_version,
+    };
+    let msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: profile_address.to_string(),
+        msg: to_json_binary(&set_backup_msg)?,
+        funds: vec![],
+    });
+
+    Ok(Response::new()
+        .add_message(msg)
+        .add_attribute("action", "set_key_backup")
+        .add_attribute("sender", info.sender))
+}
+
+pub(crate) fn set_key_shares(
+    deps: DepsMut,
+    info: MessageInfo,
+    shares: Vec<KeyShare>,
+    threshold: u64,
+) -> Result<Response, ContractError> {
+    let profile_address = PROFILES_ADDRESS.load(deps.storage)?;
+    let set_shares_msg = ProfilesExecuteMsg::SetKeyShares {
+        address: info.sender.clone(),
+        shares,
+        threshold,
+    };
+    let msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: profile_address.to_string(),
+        msg: to_json_binary(&set_shares_msg)?,
+        funds: vec![],
+    });
+
+    Ok(Response::new()
+        .add_message(msg)
+        .add_attribute("action", "set_key_shares")
+        .add_attribute("sender", info.sender))
+}
+
+pub(crate) fn request_key_recovery(
+    deps: DepsMut,
+    info: MessageInfo,
+    new_pubkey: String,
+) -> Result<Response, ContractError> {
+    let profile_address = PROFILES_ADDRESS.load(deps.storage)?;
+    let request_msg = ProfilesExecuteMsg::RequestKeyRecovery {
+        address: info.sender.clone(),
+        new_pubkey,
+    };
+    let msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: profile_address.to_string(),
+        msg: to_json_binary(&request_msg)?,
+        funds: vec![],
+    });
+
+    Ok(Response::new()
+        .add_message(msg)
+        .add_attribute("action", "request_key_recovery")
+        .add_attribute("sender", info.sender))
+}
+
+pub(crate) fn cancel_key_recovery(
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let profile_address = PROFILES_ADDRESS.load(deps.storage)?;
+    let cancel_msg = ProfilesExecuteMsg::CancelKeyRecovery {
+        address: info.sender.clone(),
+    };
+    let msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: profile_address.to_string(),
+        msg: to_json_binary(&cancel_msg)?,
+        funds: vec![],
+    });
+
+    Ok(Response::new()
+        .add_message(msg)
+        .add_attribute("action", "cancel_key_recovery")
+        .add_attribute("sender", info.sender))
+}
+
+pub(crate) fn submit_key_share(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: Addr,
+    share: Binary,
+) -> Result<Response, ContractError> {
+    let profile_address = PROFILES_ADDRESS.load(deps.storage)?;
+    let submit_msg = ProfilesExecuteMsg::SubmitKeyShare {
+        address: address.clone(),
+        guardian: info.sender.clone(),
+        share,
+    };
+    let msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: profile_address.to_string(),
+        msg: to_json_binary(&submit_msg)?,
+        funds: vec![],
+    });
+
+    Ok(Response::new()
+        .add_message(msg)
+        .add_attribute("action", "submit_key_share")
+        .add_attribute("sender", info.sender)
+        .add_attribute("address", address))
+}
@@ -0,0 +1,54 @@
+use cosmwasm_std::{to_json_binary, Binary, CosmosMsg, DepsMut, MessageInfo, Response, WasmMsg};
+use utils::msg::ProfilesExecuteMsg;
+
+use crate::{error::ContractError, state::PROFILES_ADDRESS};
+
+pub(crate) fn link_address(
+    deps: DepsMut,
+    info: MessageInfo,
+    external_address: String,
+    pubkey: Option<Binary>,
+    signature: Binary,
+) -> Result<Response, ContractError> {
+    let profile_address = PROFILES_ADDRESS.load(deps.storage)?;
+    let link_msg = ProfilesExecuteMsg::LinkAddress {
+        address: info.sender.clone(),
+        external_address: external_address.clone(),
+        pubkey,
+        signature,
+    };
+    let msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: profile_address.to_string(),
+        msg: to_json_binary(&link_msg)?,
+        funds: vec![],
+    });
+
+    Ok(Response::new()
+        .add_message(msg)
+        .add_attribute("action", "link_address")
+        .add_attribute("sender", info.sender)
+        .add_attribute("external_address", external_address))
+}
+
+pub(crate) fn unlink_address(
+    deps: DepsMut,
+    info: MessageInfo,
+    external_address: String,
+) -> Result<Response, ContractError> {
+    let profile_address = PROFILES_ADDRESS.load(deps.storage)?;
+    let unlink_msg = ProfilesExecuteMsg::UnlinkAddress {
+        address: info.sender.clone(),
+        external_address: external_address.clone(),
+    };
+    let msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: profile_address.to_string(),
+        msg: to_json_binary(&unlink_msg)?,
+        funds: vec![],
+    });
+
+    Ok(Response::new()
+        .add_message(msg)
+        .add_attribute("action", "unlink_address")
+        .add_attribute("sender", info.sender)
+        .add_attribute("external_address", external_address))
+}
@@ -0,0 +1,231 @@
+use cosmwasm_std::{
+    to_json_binary, Addr, BankMsg, Coin, CosmosMsg, DepsMut, MessageInfo, Response, WasmMsg,
+};
+use cw_utils::one_coin;
+use utils::{
+    msg::ProfilesExecuteMsg,
+    query::{ProfileInfo, ProfilesQueryMsg},
+};
+
+use crate::{
+    error::ContractError,
+    state::{Listing, CONFIG, LISTINGS, PROFILES_ADDRESS},
+};
+
+use super::fees::record_fees;
+
+pub(crate) fn transfer_user_id(
+    deps: DepsMut,
+    info: MessageInfo,
+    user_id: String,
+    to: Addr,
+) -> Result<Response, ContractError> {
+    let profile_address = PROFILES_ADDRESS.load(deps.storage)?;
+    let to = deps.api.addr_validate(to.as_ref())?;
+
+    let transfer_msg = ProfilesExecuteMsg::TransferUserId {
+        address: info.sender.clone(),
+        user_id: user_id.clone(),
+        to: to.clone(),
+    };
+    let msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: profile_address.to_string(),
+        msg: to_json_binary(&transfer_msg)?,
+        funds: vec![],
+    });
+
+    Ok(Response::new()
+        .add_message(msg)
+        .add_attribute("action", "transfer_user_id")
+        .add_attribute("sender", info.sender)
+        .add_attribute("user_id", user_id)
+        .add_attribute("to", to))
+}
+
+pub(crate) fn cancel_user_id_transfer(
+    deps: DepsMut,
+    info: MessageInfo,
+    user_id: String,
+) -> Result<Response, ContractError> {
+    let profile_address = PROFILES_ADDRESS.load(deps.storage)?;
+
+    let cancel_msg = ProfilesExecuteMsg::CancelUserIdTransfer {
+        address: info.sender.clone(),
+        user_id: user_id.clone(),
+    };
+    let msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: profile_address.to_string(),
+        msg: to_json_binary(&cancel_msg)?,
+        funds: vec![],
+    });
+
+    Ok(Response::new()
+        .add_message(msg)
+        .add_attribute("action", "cancel_user_id_transfer")
+        .add_attribute("sender", info.sender)
+        .add_attribute("user_id", user_id))
+}
+
+pub(crate) fn accept_user_id(
+    deps: DepsMut,
+    info: MessageInfo,
+    user_id: String,
+    pubkey: Option<String>,
+) -> Result<Response, ContractError> {
+    let profile_address = PROFILES_ADDRESS.load(deps.storage)?;
+    // A listing by the previous owner doesn't survive the transfer.
+    LISTINGS.remove(deps.storage, user_id.clone());
+
+    let accept_msg = ProfilesExecuteMsg::AcceptUserId {
+        address: info.sender.clone(),
+        user_id: user_id.clone(),
+        pubkey,
+    };
+    let msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: profile_address.to_string(),
+        msg: to_json_binary(&accept_msg)?,
+        funds: vec![],
+    });
+
+    Ok(Response::new()
+        .add_message(msg)
+        .add_attribute("action", "accept_user_id")
+        .add_attribute("sender", info.sender)
+        .add_attribute("user_id", user_id))
+}
+
+pub(crate) fn list_user_id(
+    deps: DepsMut,
+    info: MessageInfo,
+    user_id: String,
+    price: Coin,
+) -> Result<Response, ContractError> {
+    if user_id.contains('.') {
+        return Err(ContractError::SubnameNotTradable {});
+    }
+    if price.amount.is_zero() {
+        return Err(ContractError::InvalidPrice {});
+    }
+
+    let profile_address = PROFILES_ADDRESS.load(deps.storage)?;
+    let profile_info: ProfileInfo = deps.querier.query_wasm_smart(
+        profile_address,
+        &ProfilesQueryMsg::UserInfo {
+            user_id: user_id.clone(),
+        },
+    )?;
+    // A replaced user id still resolves to its former holder during its redirect period.
+    if profile_info.address != info.sender
+        || (profile_info.user_id != user_id && !profile_info.aliases.contains(&user_id))
+    {
+        return Err(ContractError::NotUserIdOwner {});
+    }
+    // Selling the last user id of a profile would delete the profile.
+    if profile_info.user_id == user_id && profile_info.aliases.is_empty() {
+        return Err(ContractError::SoleUserId {});
+    }
+
+    LISTINGS.save(
+        deps.storage,
+        user_id.clone(),
+        &Listing {
+            seller: info.sender.clone(),
+            price: price.clone(),
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "list_user_id")
+        .add_attribute("sender", info.sender)
+        .add_attribute("user_id", user_id)
+        .add_attribute("price", price.to_string()))
+}
+
+pub(crate) fn delist_user_id(
+    deps: DepsMut,
+    info: MessageInfo,
+    user_id: String,
+) -> Result<Response, ContractError> {
+    let listing = LISTINGS.load(deps.storage, user_id.clone())?;
+    if listing.seller != info.sender {
+        return Err(ContractError::NotUserIdOwner {});
+    }
+    LISTINGS.remove(deps.storage, user_id.clone());
+
+    Ok(Response::new()
+        .add_attribute("action", "delist_user_id")
+        .add_attribute("sender", info.sender)
+        .add_attribute("user_id", user_id))
+}
+
+pub(crate) fn buy_user_id(
+    deps: DepsMut,
+    info: MessageInfo,
+    user_id: String,
+    pubkey: Option<String>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let listing = LISTINGS.load(deps.storage, user_id.clone())?;
+
+    if one_coin(&info)? != listing.price {
+        return Err(ContractError::InvalidFunds {
+            funds_required: listing.price,
+        });
+    }
+    if listing.seller == info.sender {
+        return Err(ContractError::SelfPurchase {});
+    }
+
+    // The seller may have transferred the user id away since listing it.
+    let profile_address = PROFILES_ADDRESS.load(deps.storage)?;
+    let profile_info: ProfileInfo = deps.querier.query_wasm_smart(
+        profile_address.clone(),
+        &ProfilesQueryMsg::UserInfo {
+            user_id: user_id.clone(),
+        },
+    )?;
+    if profile_info.address != listing.seller {
+        return Err(ContractError::StaleListing {});
+    }
+
+    LISTINGS.remove(deps.storage, user_id.clone());
+
+    let fee = listing.price.amount * config.trade_fee;
+    let proceeds = listing.price.amount - fee;
+    record_fees(
+        deps.storage,
+        &[Coin {
+            denom: listing.price.denom.clone(),
+            amount: fee,
+        }],
+    )?;
+
+    let move_msg = ProfilesExecuteMsg::MoveUserId {
+        user_id: user_id.clone(),
+        from: listing.seller.clone(),
+        to: info.sender.clone(),
+        pubkey,
+    };
+    let mut msgs = vec![CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: profile_address.to_string(),
+        msg: to_json_binary(&move_msg)?,
+        funds: vec![],
+    })];
+    if !proceeds.is_zero() {
+        msgs.push(CosmosMsg::Bank(BankMsg::Send {
+            to_address: listing.seller.to_string(),
+            amount: vec![Coin {
+                denom: listing.price.denom,
+                amount: proceeds,
+            }],
+        }));
+    }
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_attribute("action", "buy_user_id")
+        .add_attribute("sender", info.sender)
+        .add_attribute("seller", listing.seller)
+        .add_attribute("user_id", user_id)
+        .add_attribute("fee", fee))
+}
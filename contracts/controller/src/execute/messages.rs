@@ -0,0 +1,151 @@
+use cosmwasm_std::{
+    to_json_binary, Addr, Binary, Coin, CosmosMsg, DepsMut, Env, MessageInfo, Response, StdResult,
+    Uint128, WasmMsg,
+};
+use utils::{
+    msg::MessagesExecuteMsg,
+    query::{ProfileInfo, ProfilesQueryMsg},
+};
+
+use crate::{
+    error::ContractError,
+    state::{MessagePrice, CONFIG, MESSAGES_ADDRESS, PROFILES_ADDRESS},
+};
+
+use super::fees::record_fees;
+use super::sponsors::sponsored_payment;
+use super::subscriptions::use_subscription_quota;
+
+pub(crate) fn send_message(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    content: Binary,
+    dest_address: Option<Addr>,
+    dest_id: Option<String>,
+    dest_id_hash: Option<String>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let profile_address = PROFILES_ADDRESS.load(deps.storage)?;
+
+    if content.len() > config.message_max_len.try_into().unwrap() {
+        return Err(ContractError::MessageTooLong {});
+    }
+
+    // Set when `dest_id` was replaced by a user id change and now redirects to the profile's new id.
+    let mut redirect = None;
+    let destination = match (dest_address, dest_id, dest_id_hash) {
+        (Some(address), _, _) => deps.api.addr_validate(address.as_ref())?,
+        (None, Some(user_id), _) => {
+            let profile_info: ProfileInfo = deps.querier.query_wasm_smart(
+                profile_address,
+                &ProfilesQueryMsg::UserInfo {
+                    user_id: user_id.clone(),
+                },
+            )?;
+            if profile_info.user_id != user_id && !profile_info.aliases.contains(&user_id) {
+                redirect = Some(profile_info.user_id);
+            }
+            profile_info.address
+        }
+        (None, None, Some(user_id_hash)) => {
+            let profile_info: ProfileInfo = deps.querier.query_wasm_smart(
+                profile_address,
+                &ProfilesQueryMsg::UserInfoByHash { user_id_hash },
+            )?;
+            profile_info.address
+        }
+        (None, None, None) => return Err(ContractError::NoDestination {}),
+    };
+
+    let mut funds_to_send = info.funds;
+    let mut cost = None;
+    // Messages are paid from the sender's subscription first, then from an allowance, then from the
+    // attached funds.
+    let subscription = if config.message_prices.is_empty() {
+        None
+    } else {
+        use_subscription_quota(deps.storage, &env, &info.sender)?
+    };
+    let sponsored = if config.message_prices.is_empty() || subscription.is_some() {
+        None
+    } else {
+        sponsored_payment(
+            deps.storage,
+            &env,
+            &info.sender,
+            &config.message_prices,
+            content.len() as u64,
+        )?
+    };
+
+    if let Some((_, paid)) = &sponsored {
+        record_fees(deps.storage, std::slice::from_ref(paid))?;
+        cost = Some(paid.clone());
+    } else if subscription.is_none() && !config.message_prices.is_empty() {
+        // The first accepted denom attached with enough funds pays for the message.
+        let mut payment = None;
+        for price in &config.message_prices {
+            let amount = message_cost(price, content.len() as u64)?;
+            if let Some(index) = funds_to_send
+                .iter()
+                .position(|c| c.denom == price.denom && c.amount >= amount)
+            {
+                payment = Some((index, amount));
+                break;
+            }
+        }
+        let (index, amount) = payment.ok_or(ContractError::NotEnoughFundsForMessage {})?;
+
+        let paid = Coin {
+            denom: funds_to_send[index].denom.clone(),
+            amount,
+        };
+        record_fees(deps.storage, std::slice::from_ref(&paid))?;
+        cost = Some(paid);
+        if funds_to_send[index].amount == amount {
+            funds_to_send.remove(index);
+        } else {
+            funds_to_send[index].amount -= amount;
+        }
+    }
+
+    let message_address = MESSAGES_ADDRESS.load(deps.storage)?;
+    let create_send_msg = MessagesExecuteMsg::SendMessage {
+        sender: info.sender.clone(),
+        receiver: destination.clone(),
+        message: content,
+    };
+    let msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: message_address.to_string(),
+        msg: to_json_binary(&create_send_msg)?,
+        funds: funds_to_send,
+    });
+
+    let mut response = Response::new()
+        .add_message(msg)
+        .add_attribute("action", "send_message")
+        .add_attribute("sender", info.sender)
+        .add_attribute("destination", destination);
+    if let Some(cost) = cost {
+        response = response.add_attribute("cost", cost.to_string());
+    }
+    if let Some(plan) = subscription {
+        response = response.add_attribute("subscription", plan);
+    }
+    if let Some((sponsor, _)) = sponsored {
+        response = response.add_attribute("sponsor", sponsor);
+    }
+    if let Some(user_id) = redirect {
+        response = response.add_attribute("redirect", user_id);
+    }
+
+    Ok(response)
+}
+
+pub(crate) fn message_cost(price: &MessagePrice, content_len: u64) -> StdResult<Uint128> {
+    Ok(price
+        .per_byte
+        .checked_mul(Uint128::from(content_len))?
+        .checked_add(price.base)?)
+}
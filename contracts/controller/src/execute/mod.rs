@@ -0,0 +1,18 @@
+pub mod attestations;
+pub mod auctions;
+pub mod children;
+pub mod config;
+pub mod fees;
+pub mod imports;
+pub mod keys;
+pub mod links;
+pub mod marketplace;
+pub mod messages;
+pub mod pause;
+pub mod profiles;
+pub mod proposals;
+pub mod recovery;
+pub mod roles;
+pub mod sponsors;
+pub mod subnames;
+pub mod subscriptions;
@@ -0,0 +1,120 @@
+use cosmwasm_std::{to_json_binary, CosmosMsg, DepsMut, MessageInfo, Response, WasmMsg};
+use utils::msg::MessagesExecuteMsg;
+
+use crate::{
+    error::ContractError,
+    msg::ExecuteMsg,
+    state::{Operation, Role, MESSAGES_ADDRESS, PAUSE_FLAGS},
+};
+
+use super::roles::assert_role;
+
+// The pausable operation an execute message belongs to. Administration is never paused. No
+// catch-all, so that new messages have to be placed here.
+pub(crate) fn paused_operation(msg: &ExecuteMsg) -> Option<Operation> {
+    match msg {
+        ExecuteMsg::CreateProfile { .. }
+        | ExecuteMsg::CreatePrivateProfile { .. }
+        | ExecuteMsg::ImportProfiles { .. } => Some(Operation::ProfileCreation),
+        ExecuteMsg::ChangeUserId { .. }
+        | ExecuteMsg::ChangePubkey { .. }
+        | ExecuteMsg::AddAlias { .. }
+        | ExecuteMsg::RemoveAlias { .. }
+        | ExecuteMsg::TransferUserId { .. }
+        | ExecuteMsg::CancelUserIdTransfer { .. }
+        | ExecuteMsg::AcceptUserId { .. }
+        | ExecuteMsg::ListUserId { .. }
+        | ExecuteMsg::DelistUserId { .. }
+        | ExecuteMsg::BuyUserId { .. }
+        | ExecuteMsg::BidUserId { .. }
+        | ExecuteMsg::SettleAuction { .. }
+        | ExecuteMsg::CreateSubname { .. }
+        | ExecuteMsg::TransferSubname { .. }
+        | ExecuteMsg::RevokeSubname { .. }
+        | ExecuteMsg::ChangeSubnamePubkey { .. }
+        | ExecuteMsg::SetSubnamePolicy { .. }
+        | ExecuteMsg::RenewProfile { .. }
+        | ExecuteMsg::SetDirectoryListing { .. }
+        | ExecuteMsg::DeactivateProfile {}
+        | ExecuteMsg::ReactivateProfile {}
+        | ExecuteMsg::DeleteProfile { .. }
+        | ExecuteMsg::LinkAddress { .. }
+        | ExecuteMsg::UnlinkAddress { .. }
+        | ExecuteMsg::Attest { .. }
+        | ExecuteMsg::RevokeAttestation { .. }
+        | ExecuteMsg::SetKeyBackup { .. }
+        | ExecuteMsg::SetKeyShares { .. }
+        | ExecuteMsg::RequestKeyRecovery { .. }
+        | ExecuteMsg::CancelKeyRecovery {}
+        | ExecuteMsg::SubmitKeyShare { .. }
+        | ExecuteMsg::SetRecovery { .. }
+        | ExecuteMsg::RemoveRecovery {}
+        | ExecuteMsg::ApproveRecovery { .. }
+        | ExecuteMsg::CancelRecovery {}
+        | ExecuteMsg::ExecuteRecovery { .. } => Some(Operation::ProfileChanges),
+        // Subscriptions and sponsoring pay for sending.
+        ExecuteMsg::SendMessage { .. }
+        | ExecuteMsg::Subscribe { .. }
+        | ExecuteMsg::DepositSponsorFunds { .. }
+        | ExecuteMsg::WithdrawSponsorFunds { .. }
+        | ExecuteMsg::GrantAllowance { .. }
+        | ExecuteMsg::RevokeAllowance { .. }
+        | ExecuteMsg::CreateSponsorCode { .. }
+        | ExecuteMsg::RemoveSponsorCode { .. } => Some(Operation::Sending),
+        ExecuteMsg::AddVerifier { .. }
+        | ExecuteMsg::RemoveVerifier { .. }
+        | ExecuteMsg::ChangeMessagesConfig { .. }
+        | ExecuteMsg::ChangeProfilesConfig { .. } => Some(Operation::ConfigForwarding),
+        ExecuteMsg::UpdateConfig(_)
+        | ExecuteMsg::SetPlan { .. }
+        | ExecuteMsg::RemovePlan { .. }
+        | ExecuteMsg::SetPaused { .. }
+        | ExecuteMsg::GrantRole { .. }
+        | ExecuteMsg::RevokeRole { .. }
+        | ExecuteMsg::SetFeeShares { .. }
+        | ExecuteMsg::MigrateChild { .. }
+        | ExecuteMsg::SetChildContract { .. }
+        | ExecuteMsg::CancelProposal { .. }
+        | ExecuteMsg::ExecuteProposal { .. }
+        | ExecuteMsg::DistributeFees {}
+        | ExecuteMsg::RetrieveFees { .. }
+        | ExecuteMsg::SweepFunds { .. }
+        | ExecuteMsg::UpdateOwnership(_) => None,
+    }
+}
+
+pub(crate) fn set_paused(
+    deps: DepsMut,
+    info: MessageInfo,
+    operation: Operation,
+    paused: bool,
+) -> Result<Response, ContractError> {
+    assert_role(deps.storage, &info.sender, Role::Pauser)?;
+
+    let mut flags = PAUSE_FLAGS.may_load(deps.storage)?.unwrap_or_default();
+    *flags.flag(&operation) = paused;
+    PAUSE_FLAGS.save(deps.storage, &flags)?;
+
+    // Claims are paused in the messages contract itself.
+    let mut msgs = vec![];
+    if operation == Operation::Claims {
+        let messages_address = MESSAGES_ADDRESS.load(deps.storage)?;
+        msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: messages_address.to_string(),
+            msg: to_json_binary(&MessagesExecuteMsg::SetClaimsPaused { paused })?,
+            funds: vec![],
+        }));
+    }
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_attribute("action", "set_paused")
+        .add_attribute("sender", info.sender)
+        .add_attribute("operation", operation.as_str())
+        .add_attribute("paused", paused.to_string())
+        .add_attribute("profile_creation", flags.profile_creation.to_string())
+        .add_attribute("profile_changes", flags.profile_changes.to_string())
+        .add_attribute("sending", flags.sending.to_string())
+        .add_attribute("config_forwarding", flags.config_forwarding.to_string())
+        .add_attribute("claims", flags.claims.to_string()))
+}
@@ -0,0 +1,559 @@
+use cosmwasm_std::{
+    to_json_binary, Addr, BankMsg, Coin, CosmosMsg, DepsMut, Empty, Env, MessageInfo, Response,
+    StdResult, Storage, WasmMsg,
+};
+use cw_utils::{nonpayable, one_coin};
+use utils::{
+    msg::{MessagesExecuteMsg, ProfilesExecuteMsg},
+    query::{ProfileInfo, ProfilesQueryMsg},
+};
+
+use crate::{
+    error::ContractError,
+    state::{
+        Allowance, Config, ALLOWANCES, CONFIG, LISTINGS, MESSAGES_ADDRESS, PROFILES_ADDRESS,
+        REFERRALS, REFERRAL_RANKING, SPONSOR_CODES,
+    },
+};
+
+use super::fees::record_fees;
+use super::sponsors::is_expired;
+
+pub(crate) fn create_profile(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    pubkey: String,
+    user_id: String,
+    sponsor_code: Option<String>,
+    referrer: Option<String>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    assert_user_id_cost(&config, &info, &user_id)?;
+    let profile_address = PROFILES_ADDRESS.load(deps.storage)?;
+
+    let mut referral_msg = None;
+    let mut fees = info.funds.clone();
+    if let Some(referrer) = &referrer {
+        let referrer_info: ProfileInfo = deps
+            .querier
+            .query_wasm_smart(
+                profile_address.clone(),
+                &ProfilesQueryMsg::UserInfo {
+                    user_id: referrer.clone(),
+                },
+            )
+            .map_err(|_| ContractError::ReferrerNotFound {})?;
+        if referrer_info.address == info.sender {
+            return Err(ContractError::SelfReferral {});
+        }
+
+        let mut reward = vec![];
+        for fee in fees.iter_mut() {
+            let amount = fee.amount * config.referral_share;
+            if !amount.is_zero() {
+                fee.amount -= amount;
+                reward.push(Coin {
+                    denom: fee.denom.clone(),
+                    amount,
+                });
+            }
+        }
+        // Free registrations aren't counted, or throwaway profiles could farm the ranking.
+        if info.funds.iter().any(|fee| !fee.amount.is_zero()) {
+            record_referral(deps.storage, &referrer_info.address, &reward)?;
+        }
+        if !reward.is_empty() {
+            referral_msg = Some(BankMsg::Send {
+                to_address: referrer_info.address.to_string(),
+                amount: reward,
+            });
+        }
+    }
+    record_fees(deps.storage, &fees)?;
+
+    let mut sponsor = None;
+    if let Some(code) = sponsor_code {
+        let mut sponsor_code = SPONSOR_CODES.load(deps.storage, code.clone())?;
+        if is_expired(&env, sponsor_code.expires) {
+            return Err(ContractError::SponsorCodeExpired {});
+        }
+        if sponsor_code
+            .max_uses
+            .map_or(false, |max_uses| sponsor_code.uses >= max_uses)
+        {
+            return Err(ContractError::SponsorCodeUsedUp {});
+        }
+        sponsor_code.uses += 1;
+        SPONSOR_CODES.save(deps.storage, code, &sponsor_code)?;
+        ALLOWANCES.save(
+            deps.storage,
+            (info.sender.clone(), sponsor_code.sponsor.clone()),
+            &Allowance {
+                sponsor: sponsor_code.sponsor.clone(),
+                limit: sponsor_code.limit,
+                expires: sponsor_code.expires,
+            },
+        )?;
+        sponsor = Some(sponsor_code.sponsor);
+    }
+    // Left by a previous holder whose registration expired.
+    LISTINGS.remove(deps.storage, user_id.clone());
+
+    let create_profile_msg = ProfilesExecuteMsg::CreateProfile {
+        address: info.sender.clone(),
+        user_id: user_id.clone(),
+        pubkey,
+        term: config.registration_term,
+    };
+    let msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: profile_address.to_string(),
+        msg: to_json_binary(&create_profile_msg)?,
+        funds: vec![],
+    });
+
+    let mut response = Response::new()
+        .add_message(msg)
+        .add_messages(referral_msg)
+        .add_attribute("action", "create_profile")
+        .add_attribute("sender", info.sender)
+        .add_attribute("user_id", user_id);
+    if let Some(sponsor) = sponsor {
+        response = response.add_attribute("sponsor", sponsor);
+    }
+    if let Some(referrer) = referrer {
+        response = response.add_attribute("referrer", referrer);
+    }
+
+    Ok(response)
+}
+
+// Counts a referral and its reward, keeping the ranking by count in sync.
+fn record_referral(storage: &mut dyn Storage, referrer: &Addr, reward: &[Coin]) -> StdResult<()> {
+    let mut stats = REFERRALS
+        .may_load(storage, referrer.clone())?
+        .unwrap_or_default();
+    REFERRAL_RANKING.remove(storage, (stats.count, referrer.clone()));
+
+    stats.count += 1;
+    for coin in reward {
+        match stats.earnings.iter_mut().find(|c| c.denom == coin.denom) {
+            Some(earned) => earned.amount += coin.amount,
+            None => stats.earnings.push(coin.clone()),
+        }
+    }
+    REFERRALS.save(storage, referrer.clone(), &stats)?;
+    REFERRAL_RANKING.save(storage, (stats.count, referrer.clone()), &Empty {})
+}
+
+pub(crate) fn create_private_profile(
+    deps: DepsMut,
+    info: MessageInfo,
+    user_id_hash: String,
+    pubkey: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let highest_price = config
+        .length_prices
+        .iter()
+        .map(|price| price.cost.clone())
+        .chain(config.profile_cost.clone())
+        .max_by_key(|cost| cost.amount);
+    if let Some(cost) = highest_price {
+        assert_exact_funds(&info, cost)?;
+    }
+    record_fees(deps.storage, &info.funds)?;
+
+    let profile_address = PROFILES_ADDRESS.load(deps.storage)?;
+    let create_profile_msg = ProfilesExecuteMsg::CreatePrivateProfile {
+        address: info.sender.clone(),
+        user_id_hash,
+        pubkey,
+    };
+    let msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: profile_address.to_string(),
+        msg: to_json_binary(&create_profile_msg)?,
+        funds: vec![],
+    });
+
+    Ok(Response::new()
+        .add_message(msg)
+        .add_attribute("action", "create_private_profile")
+        .add_attribute("sender", info.sender))
+}
+
+pub(crate) fn add_alias(
+    deps: DepsMut,
+    info: MessageInfo,
+    alias: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    assert_user_id_cost(&config, &info, &alias)?;
+    record_fees(deps.storage, &info.funds)?;
+    LISTINGS.remove(deps.storage, alias.clone());
+
+    let profile_address = PROFILES_ADDRESS.load(deps.storage)?;
+    let add_alias_msg = ProfilesExecuteMsg::AddAlias {
+        address: info.sender.clone(),
+        alias: alias.clone(),
+        term: config.registration_term,
+    };
+    let msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: profile_address.to_string(),
+        msg: to_json_binary(&add_alias_msg)?,
+        funds: vec![],
+    });
+
+    Ok(Response::new()
+        .add_message(msg)
+        .add_attribute("action", "add_alias")
+        .add_attribute("sender", info.sender)
+        .add_attribute("alias", alias))
+}
+
+pub(crate) fn remove_alias(
+    deps: DepsMut,
+    info: MessageInfo,
+    alias: String,
+) -> Result<Response, ContractError> {
+    LISTINGS.remove(deps.storage, alias.clone());
+
+    let profile_address = PROFILES_ADDRESS.load(deps.storage)?;
+    let remove_alias_msg = ProfilesExecuteMsg::RemoveAlias {
+        address: info.sender.clone(),
+        alias: alias.clone(),
+    };
+    let msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: profile_address.to_string(),
+        msg: to_json_binary(&remove_alias_msg)?,
+        funds: vec![],
+    });
+
+    Ok(Response::new()
+        .add_message(msg)
+        .add_attribute("action", "remove_alias")
+        .add_attribute("sender", info.sender)
+        .add_attribute("alias", alias))
+}
+
+// Registering any user id, primary or alias, requires exactly its length price, or the profile cost
+// if no length price applies. Ids in the auction range can't be registered directly.
+fn assert_user_id_cost(
+    config: &Config,
+    info: &MessageInfo,
+    user_id: &str,
+) -> Result<(), ContractError> {
+    assert_not_auctioned(config, user_id)?;
+    assert_registration_price(config, info, user_id)
+}
+
+fn assert_registration_price(
+    config: &Config,
+    info: &MessageInfo,
+    user_id: &str,
+) -> Result<(), ContractError> {
+    match length_price(config, user_id).or_else(|| config.profile_cost.clone()) {
+        Some(coin) => assert_exact_funds(info, coin),
+        None => Ok(()),
+    }
+}
+
+pub(crate) fn assert_not_auctioned(config: &Config, user_id: &str) -> Result<(), ContractError> {
+    match &config.auction {
+        Some(auction) if user_id_len(user_id) < auction.below_len => {
+            Err(ContractError::UserIdRequiresAuction {
+                below_len: auction.below_len,
+            })
+        }
+        _ => Ok(()),
+    }
+}
+
+pub(crate) fn assert_exact_funds(info: &MessageInfo, coin: Coin) -> Result<(), ContractError> {
+    if one_coin(info)? != coin {
+        return Err(ContractError::InvalidFunds {
+            funds_required: coin,
+        });
+    }
+
+    Ok(())
+}
+
+// Checks that the funds are exactly the sum of `required`, which can mix denoms or be empty.
+fn assert_exact_payment(info: &MessageInfo, required: Vec<Coin>) -> Result<(), ContractError> {
+    let mut total: Vec<Coin> = vec![];
+    for coin in required {
+        match total.iter_mut().find(|c| c.denom == coin.denom) {
+            Some(c) => c.amount += coin.amount,
+            None => total.push(coin),
+        }
+    }
+    if total.is_empty() {
+        nonpayable(info)?;
+        return Ok(());
+    }
+    if total.len() == 1 {
+        return assert_exact_funds(info, total.remove(0));
+    }
+
+    for coin in &total {
+        if !info.funds.contains(coin) || info.funds.len() != total.len() {
+            return Err(ContractError::InvalidFunds {
+                funds_required: coin.clone(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+// The most specific entry of the price schedule that covers the user id's length.
+fn length_price(config: &Config, user_id: &str) -> Option<Coin> {
+    let len = user_id_len(user_id);
+    config
+        .length_prices
+        .iter()
+        .filter(|price| len <= price.max_len)
+        .min_by_key(|price| price.max_len)
+        .map(|price| price.cost.clone())
+}
+
+// Listings of user ids that are released or change hands can't be bought anymore.
+pub(crate) fn remove_listings(storage: &mut dyn Storage, user_ids: &[String]) {
+    for user_id in user_ids {
+        LISTINGS.remove(storage, user_id.clone());
+    }
+}
+
+pub(crate) fn user_id_len(user_id: &str) -> u64 {
+    user_id.chars().count() as u64
+}
+
+pub(crate) fn change_user_id(
+    deps: DepsMut,
+    info: MessageInfo,
+    user_id: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let profile_address = PROFILES_ADDRESS.load(deps.storage)?;
+
+    // Switching to one of the caller's own aliases only pays the change fee, any other id also pays its
+    // length price so the price schedule can't be bypassed by registering a long id and renaming it.
+    let profile_info: ProfileInfo = deps.querier.query_wasm_smart(
+        profile_address.clone(),
+        &ProfilesQueryMsg::AddressInfo {
+            address: info.sender.clone(),
+        },
+    )?;
+    let mut required = vec![];
+    if !profile_info.aliases.contains(&user_id) {
+        assert_not_auctioned(&config, &user_id)?;
+        required.extend(length_price(&config, &user_id));
+        // The replaced id is released and the new one may have been listed by an expired holder.
+        remove_listings(deps.storage, &[profile_info.user_id, user_id.clone()]);
+    }
+    required.extend(config.change_fee.clone());
+    assert_exact_payment(&info, required)?;
+    record_fees(deps.storage, &info.funds)?;
+
+    let change_userid_msg = ProfilesExecuteMsg::ChangeUserId {
+        address: info.sender.clone(),
+        user_id: user_id.clone(),
+    };
+    let msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: profile_address.to_string(),
+        msg: to_json_binary(&change_userid_msg)?,
+        funds: vec![],
+    });
+
+    Ok(Response::new()
+        .add_message(msg)
+        .add_attribute("action", "create_userid")
+        .add_attribute("sender", info.sender)
+        .add_attribute("user_id", user_id))
+}
+
+pub(crate) fn change_pubkey(
+    deps: DepsMut,
+    info: MessageInfo,
+    pubkey: String,
+) -> Result<Response, ContractError> {
+    let profile_address = PROFILES_ADDRESS.load(deps.storage)?;
+
+    let change_pubkey_msg = ProfilesExecuteMsg::ChangePubkey {
+        address: info.sender.clone(),
+        pubkey: pubkey.clone(),
+    };
+    let msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: profile_address.to_string(),
+        msg: to_json_binary(&change_pubkey_msg)?,
+        funds: vec![],
+    });
+
+    Ok(Response::new()
+        .add_message(msg)
+        .add_attribute("action", "create_pubkey")
+        .add_attribute("sender", info.sender)
+        .add_attribute("pubkey", pubkey))
+}
+
+pub(crate) fn renew_profile(
+    deps: DepsMut,
+    info: MessageInfo,
+    user_id: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let term = config
+        .registration_term
+        .ok_or(ContractError::NoRegistrationTerm {})?;
+    assert_registration_price(&config, &info, &user_id)?;
+    record_fees(deps.storage, &info.funds)?;
+
+    let profile_address = PROFILES_ADDRESS.load(deps.storage)?;
+    let renew_msg = ProfilesExecuteMsg::RenewUserId {
+        user_id: user_id.clone(),
+        term,
+    };
+    let msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: profile_address.to_string(),
+        msg: to_json_binary(&renew_msg)?,
+        funds: vec![],
+    });
+
+    Ok(Response::new()
+        .add_message(msg)
+        .add_attribute("action", "renew_profile")
+        .add_attribute("sender", info.sender)
+        .add_attribute("user_id", user_id))
+}
+
+pub(crate) fn set_directory_listing(
+    deps: DepsMut,
+    info: MessageInfo,
+    listed: bool,
+) -> Result<Response, ContractError> {
+    let profile_address = PROFILES_ADDRESS.load(deps.storage)?;
+    let listing_msg = ProfilesExecuteMsg::SetDirectoryListing {
+        address: info.sender.clone(),
+        listed,
+    };
+    let msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: profile_address.to_string(),
+        msg: to_json_binary(&listing_msg)?,
+        funds: vec![],
+    });
+
+    Ok(Response::new()
+        .add_message(msg)
+        .add_attribute("action", "set_directory_listing")
+        .add_attribute("sender", info.sender)
+        .add_attribute("listed", listed.to_string()))
+}
+
+pub(crate) fn deactivate_profile(
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let profile_address = PROFILES_ADDRESS.load(deps.storage)?;
+    let message_address = MESSAGES_ADDRESS.load(deps.storage)?;
+
+    let deactivate_msg = ProfilesExecuteMsg::DeactivateProfile {
+        address: info.sender.clone(),
+    };
+    let close_inbox_msg = MessagesExecuteMsg::CloseInbox {
+        address: info.sender.clone(),
+    };
+    let msgs = vec![
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: profile_address.to_string(),
+            msg: to_json_binary(&deactivate_msg)?,
+            funds: vec![],
+        }),
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: message_address.to_string(),
+            msg: to_json_binary(&close_inbox_msg)?,
+            funds: vec![],
+        }),
+    ];
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_attribute("action", "deactivate_profile")
+        .add_attribute("sender", info.sender))
+}
+
+pub(crate) fn reactivate_profile(
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let profile_address = PROFILES_ADDRESS.load(deps.storage)?;
+    let message_address = MESSAGES_ADDRESS.load(deps.storage)?;
+
+    let reactivate_msg = ProfilesExecuteMsg::ReactivateProfile {
+        address: info.sender.clone(),
+    };
+    let open_inbox_msg = MessagesExecuteMsg::OpenInbox {
+        address: info.sender.clone(),
+    };
+    let msgs = vec![
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: profile_address.to_string(),
+            msg: to_json_binary(&reactivate_msg)?,
+            funds: vec![],
+        }),
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: message_address.to_string(),
+            msg: to_json_binary(&open_inbox_msg)?,
+            funds: vec![],
+        }),
+    ];
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_attribute("action", "reactivate_profile")
+        .add_attribute("sender", info.sender))
+}
+
+pub(crate) fn delete_profile(
+    deps: DepsMut,
+    info: MessageInfo,
+    refund_funds: bool,
+) -> Result<Response, ContractError> {
+    let profile_address = PROFILES_ADDRESS.load(deps.storage)?;
+    let message_address = MESSAGES_ADDRESS.load(deps.storage)?;
+
+    let profile_info: ProfileInfo = deps.querier.query_wasm_smart(
+        profile_address.clone(),
+        &ProfilesQueryMsg::AddressInfo {
+            address: info.sender.clone(),
+        },
+    )?;
+    remove_listings(deps.storage, &profile_info.aliases);
+    remove_listings(deps.storage, &[profile_info.user_id]);
+
+    let delete_msg = ProfilesExecuteMsg::DeleteProfile {
+        address: info.sender.clone(),
+    };
+    let clear_inbox_msg = MessagesExecuteMsg::ClearInbox {
+        address: info.sender.clone(),
+        refund: refund_funds,
+    };
+    let msgs = vec![
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: profile_address.to_string(),
+            msg: to_json_binary(&delete_msg)?,
+            funds: vec![],
+        }),
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: message_address.to_string(),
+            msg: to_json_binary(&clear_inbox_msg)?,
+            funds: vec![],
+        }),
+    ];
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_attribute("action", "delete_profile")
+        .add_attribute("sender", info.sender)
+        .add_attribute("refund_funds", refund_funds.to_string()))
+}
@@ -0,0 +1,128 @@
+use cosmwasm_std::{DepsMut, Env, MessageInfo, Response, WasmMsg};
+use cw_ownable::assert_owner;
+
+use crate::{
+    error::ContractError,
+    state::{Proposal, ProposalAction, Role, CONFIG, NEXT_PROPOSAL_ID, PROPOSALS},
+};
+
+use super::children::{apply_child_contract, child_address};
+use super::config::apply_config_update;
+use super::fees::apply_fee_shares;
+use super::roles::assert_role;
+
+// Transferring or renouncing ownership is scheduled, accepting a transfer is immediate.
+pub(crate) fn update_ownership(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    action: cw_ownable::Action,
+) -> Result<Response, ContractError> {
+    if action == cw_ownable::Action::AcceptOwnership {
+        let ownership = cw_ownable::update_ownership(deps, &env.block, &info.sender, action)?;
+        return Ok(Response::new().add_attributes(ownership.into_attributes()));
+    }
+
+    assert_owner(deps.storage, &info.sender)?;
+    schedule_proposal(deps, env, info, ProposalAction::UpdateOwnership(action))
+}
+
+pub(crate) fn schedule_proposal(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    action: ProposalAction,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let id = NEXT_PROPOSAL_ID.may_load(deps.storage)?.unwrap_or_default();
+    NEXT_PROPOSAL_ID.save(deps.storage, &(id + 1))?;
+
+    let eta = env.block.time.plus_seconds(config.timelock_delay);
+    PROPOSALS.save(
+        deps.storage,
+        id,
+        &Proposal {
+            action: action.clone(),
+            proposer: info.sender.clone(),
+            eta,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "schedule_proposal")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", id.to_string())
+        .add_attribute("proposal", action.kind())
+        .add_attribute("eta", eta.to_string()))
+}
+
+pub(crate) fn cancel_proposal(
+    deps: DepsMut,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    let proposal = PROPOSALS.load(deps.storage, id)?;
+    if proposal.proposer != info.sender {
+        assert_role(deps.storage, &info.sender, Role::Admin)?;
+    }
+    PROPOSALS.remove(deps.storage, id);
+
+    Ok(Response::new()
+        .add_attribute("action", "cancel_proposal")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", id.to_string())
+        .add_attribute("proposal", proposal.action.kind()))
+}
+
+// Anyone can execute a proposal once its eta has passed. The action runs as its proposer, who
+// must still hold the role it was scheduled with.
+pub(crate) fn execute_proposal(
+    deps: DepsMut,
+    env: Env,
+    id: u64,
+) -> Result<Response, ContractError> {
+    let proposal = PROPOSALS.load(deps.storage, id)?;
+    if env.block.time < proposal.eta {
+        return Err(ContractError::ProposalNotReady { eta: proposal.eta });
+    }
+    match proposal.action.role() {
+        Some(role) => assert_role(deps.storage, &proposal.proposer, role)?,
+        None => assert_owner(deps.storage, &proposal.proposer)?,
+    }
+    PROPOSALS.remove(deps.storage, id);
+
+    let response = match proposal.action {
+        ProposalAction::UpdateConfig(update) => {
+            apply_config_update(deps, env, proposal.proposer, *update)?
+        }
+        ProposalAction::SetFeeShares { shares } => {
+            apply_fee_shares(deps, proposal.proposer, shares)?
+        }
+        ProposalAction::MigrateChild {
+            contract,
+            new_code_id,
+            msg,
+        } => {
+            let contract_addr = child_address(deps.storage, &contract)?;
+            Response::new()
+                .add_message(WasmMsg::Migrate {
+                    contract_addr: contract_addr.to_string(),
+                    new_code_id,
+                    msg,
+                })
+                .add_attribute("action", "migrate_child")
+                .add_attribute("contract", contract_addr)
+                .add_attribute("new_code_id", new_code_id.to_string())
+        }
+        ProposalAction::UpdateOwnership(action) => {
+            let ownership =
+                cw_ownable::update_ownership(deps, &env.block, &proposal.proposer, action)?;
+            Response::new().add_attributes(ownership.into_attributes())
+        }
+        ProposalAction::SetChildContract { contract, address } => {
+            apply_child_contract(deps, env, contract, address)?
+        }
+    };
+
+    Ok(response.add_attribute("proposal_id", id.to_string()))
+}
@@ -0,0 +1,210 @@
+use cosmwasm_std::{
+    to_json_binary, Addr, CosmosMsg, DepsMut, Empty, Env, MessageInfo, Order, Response, StdResult,
+    WasmMsg,
+};
+use utils::{
+    msg::{MessagesExecuteMsg, ProfilesExecuteMsg},
+    query::{ProfileInfo, ProfilesQueryMsg},
+};
+
+use crate::{
+    error::ContractError,
+    state::{
+        RecoveryApproval, RecoveryRequest, RecoverySetup, AUCTIONS, BIDDER_AUCTIONS, CONFIG,
+        LISTINGS, MESSAGES_ADDRESS, PROFILES_ADDRESS, RECOVERY_REQUESTS, RECOVERY_SETUPS,
+        SUBSCRIPTIONS,
+    },
+};
+
+pub(crate) fn set_recovery(
+    deps: DepsMut,
+    info: MessageInfo,
+    mut guardians: Vec<Addr>,
+    threshold: u64,
+) -> Result<Response, ContractError> {
+    guardians.sort();
+    guardians.dedup();
+    if threshold == 0 || threshold > guardians.len() as u64 {
+        return Err(ContractError::InvalidRecoveryThreshold {});
+    }
+
+    RECOVERY_SETUPS.save(
+        deps.storage,
+        info.sender.clone(),
+        &RecoverySetup {
+            guardians,
+            threshold,
+        },
+    )?;
+    // Approvals collected under the previous guardians don't carry over.
+    RECOVERY_REQUESTS.remove(deps.storage, info.sender.clone());
+
+    Ok(Response::new()
+        .add_attribute("action", "set_recovery")
+        .add_attribute("sender", info.sender)
+        .add_attribute("threshold", threshold.to_string()))
+}
+
+pub(crate) fn remove_recovery(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    RECOVERY_SETUPS.remove(deps.storage, info.sender.clone());
+    RECOVERY_REQUESTS.remove(deps.storage, info.sender.clone());
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_recovery")
+        .add_attribute("sender", info.sender))
+}
+
+pub(crate) fn approve_recovery(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    address: Addr,
+    new_address: String,
+) -> Result<Response, ContractError> {
+    let new_address = deps.api.addr_validate(&new_address)?;
+    let setup = RECOVERY_SETUPS
+        .may_load(deps.storage, address.clone())?
+        .ok_or(ContractError::NotGuardian {})?;
+    if !setup.guardians.contains(&info.sender) {
+        return Err(ContractError::NotGuardian {});
+    }
+
+    let mut request = RECOVERY_REQUESTS
+        .may_load(deps.storage, address.clone())?
+        .unwrap_or(RecoveryRequest {
+            approvals: vec![],
+            new_address: None,
+            executable_at: None,
+        });
+    // A guardian approving another address moves its approval there.
+    request
+        .approvals
+        .retain(|approval| approval.guardian != info.sender);
+    request.approvals.push(RecoveryApproval {
+        guardian: info.sender.clone(),
+        new_address: new_address.clone(),
+    });
+    let approvals = request
+        .approvals
+        .iter()
+        .filter(|approval| approval.new_address == new_address)
+        .count() as u64;
+
+    // The delay starts when the threshold is reached. The threshold approving another address, for
+    // instance because the approved one got a profile of its own, replaces the request and restarts
+    // the delay.
+    if approvals >= setup.threshold && request.new_address.as_ref() != Some(&new_address) {
+        let config = CONFIG.load(deps.storage)?;
+        request.new_address = Some(new_address.clone());
+        request.executable_at = Some(env.block.time.plus_seconds(config.recovery_delay));
+    }
+    RECOVERY_REQUESTS.save(deps.storage, address.clone(), &request)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "approve_recovery")
+        .add_attribute("sender", info.sender)
+        .add_attribute("address", address)
+        .add_attribute("new_address", new_address)
+        .add_attribute("approvals", approvals.to_string()))
+}
+
+pub(crate) fn cancel_recovery(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    RECOVERY_REQUESTS.load(deps.storage, info.sender.clone())?;
+    RECOVERY_REQUESTS.remove(deps.storage, info.sender.clone());
+
+    Ok(Response::new()
+        .add_attribute("action", "cancel_recovery")
+        .add_attribute("sender", info.sender))
+}
+
+pub(crate) fn execute_recovery(
+    deps: DepsMut,
+    env: Env,
+    address: Addr,
+) -> Result<Response, ContractError> {
+    let request = RECOVERY_REQUESTS.load(deps.storage, address.clone())?;
+    let (new_address, executable_at) = match (request.new_address, request.executable_at) {
+        (Some(new_address), Some(executable_at)) => (new_address, executable_at),
+        _ => return Err(ContractError::RecoveryNotApproved {}),
+    };
+    if env.block.time < executable_at {
+        return Err(ContractError::RecoveryLocked { executable_at });
+    }
+
+    RECOVERY_REQUESTS.remove(deps.storage, address.clone());
+    // The guardians keep protecting the recovered profile.
+    if let Some(setup) = RECOVERY_SETUPS.may_load(deps.storage, address.clone())? {
+        RECOVERY_SETUPS.remove(deps.storage, address.clone());
+        RECOVERY_SETUPS.save(deps.storage, new_address.clone(), &setup)?;
+    }
+
+    // Bids won by the lost address are registered for, or refunded to, the new one.
+    let won_auctions = BIDDER_AUCTIONS
+        .prefix(address.clone())
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<String>>>()?;
+    for user_id in won_auctions {
+        let mut auction = AUCTIONS.load(deps.storage, user_id.clone())?;
+        auction.highest_bidder = new_address.clone();
+        AUCTIONS.save(deps.storage, user_id.clone(), &auction)?;
+        BIDDER_AUCTIONS.remove(deps.storage, (address.clone(), user_id.clone()));
+        BIDDER_AUCTIONS.save(deps.storage, (new_address.clone(), user_id), &Empty {})?;
+    }
+
+    // A subscription of the new address is kept if it runs longer.
+    if let Some(subscription) = SUBSCRIPTIONS.may_load(deps.storage, address.clone())? {
+        SUBSCRIPTIONS.remove(deps.storage, address.clone());
+        let current = SUBSCRIPTIONS.may_load(deps.storage, new_address.clone())?;
+        if current.map_or(true, |current| current.expires < subscription.expires) {
+            SUBSCRIPTIONS.save(deps.storage, new_address.clone(), &subscription)?;
+        }
+    }
+
+    let profile_address = PROFILES_ADDRESS.load(deps.storage)?;
+    let message_address = MESSAGES_ADDRESS.load(deps.storage)?;
+
+    // Listings follow the profile to its new address.
+    let profile_info: ProfileInfo = deps.querier.query_wasm_smart(
+        profile_address.clone(),
+        &ProfilesQueryMsg::AddressInfo {
+            address: address.clone(),
+        },
+    )?;
+    for user_id in profile_info
+        .aliases
+        .into_iter()
+        .chain([profile_info.user_id])
+    {
+        if let Some(mut listing) = LISTINGS.may_load(deps.storage, user_id.clone())? {
+            listing.seller = new_address.clone();
+            LISTINGS.save(deps.storage, user_id, &listing)?;
+        }
+    }
+
+    let move_profile_msg = ProfilesExecuteMsg::MoveProfile {
+        from: address.clone(),
+        to: new_address.clone(),
+    };
+    let move_inbox_msg = MessagesExecuteMsg::MoveInbox {
+        from: address.clone(),
+        to: new_address.clone(),
+    };
+    let msgs = vec![
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: profile_address.to_string(),
+            msg: to_json_binary(&move_profile_msg)?,
+            funds: vec![],
+        }),
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: message_address.to_string(),
+            msg: to_json_binary(&move_inbox_msg)?,
+            funds: vec![],
+        }),
+    ];
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_attribute("action", "execute_recovery")
+        .add_attribute("address", address)
+        .add_attribute("new_address", new_address))
+}
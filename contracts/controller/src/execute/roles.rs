@@ -0,0 +1,80 @@
+use cosmwasm_std::{Addr, DepsMut, Empty, MessageInfo, Response, Storage};
+use cw_ownable::{assert_owner, is_owner};
+
+use crate::{
+    error::ContractError,
+    state::{Role, ROLES},
+};
+
+// The owner passes every role check.
+pub(crate) fn assert_role(
+    storage: &dyn Storage,
+    sender: &Addr,
+    role: Role,
+) -> Result<(), ContractError> {
+    if is_owner(storage, sender)? || ROLES.has(storage, (role.as_str().to_string(), sender.clone()))
+    {
+        return Ok(());
+    }
+    Err(ContractError::MissingRole {
+        role: role.as_str().to_string(),
+    })
+}
+
+// Admins manage the other roles. Only the owner manages admins, so an admin can't grant itself
+// accomplices or lock the other admins out.
+fn assert_role_manager(
+    storage: &dyn Storage,
+    sender: &Addr,
+    role: &Role,
+) -> Result<(), ContractError> {
+    match role {
+        Role::Admin => Ok(assert_owner(storage, sender)?),
+        _ => assert_role(storage, sender, Role::Admin),
+    }
+}
+
+pub(crate) fn grant_role(
+    deps: DepsMut,
+    info: MessageInfo,
+    role: Role,
+    address: Addr,
+) -> Result<Response, ContractError> {
+    assert_role_manager(deps.storage, &info.sender, &role)?;
+
+    let address = deps.api.addr_validate(address.as_ref())?;
+    ROLES.save(
+        deps.storage,
+        (role.as_str().to_string(), address.clone()),
+        &Empty {},
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "grant_role")
+        .add_attribute("sender", info.sender)
+        .add_attribute("role", role.as_str())
+        .add_attribute("address", address))
+}
+
+pub(crate) fn revoke_role(
+    deps: DepsMut,
+    info: MessageInfo,
+    role: Role,
+    address: Addr,
+) -> Result<Response, ContractError> {
+    assert_role_manager(deps.storage, &info.sender, &role)?;
+
+    let key = (role.as_str().to_string(), address.clone());
+    if !ROLES.has(deps.storage, key.clone()) {
+        return Err(ContractError::MissingRole {
+            role: role.as_str().to_string(),
+        });
+    }
+    ROLES.remove(deps.storage, key);
+
+    Ok(Response::new()
+        .add_attribute("action", "revoke_role")
+        .add_attribute("sender", info.sender)
+        .add_attribute("role", role.as_str())
+        .add_attribute("address", address))
+}
@@ -0,0 +1,262 @@
+use cosmwasm_std::{
+    Addr, BankMsg, Coin, DepsMut, Env, MessageInfo, Order, Response, StdResult, Storage, Timestamp,
+};
+use cw_utils::PaymentError;
+
+use crate::{
+    error::ContractError,
+    state::{
+        Allowance, AllowanceLimit, MessagePrice, SponsorCode, ALLOWANCES, SPONSOR_CODES,
+        SPONSOR_DEPOSITS,
+    },
+};
+
+use super::config::validate_denom;
+use super::messages::message_cost;
+
+// Pays the message from the first of the sender's allowances whose sponsor has deposited enough in
+// an accepted denom, and updates the deposit and the allowance.
+pub(crate) fn sponsored_payment(
+    storage: &mut dyn Storage,
+    env: &Env,
+    sender: &Addr,
+    prices: &[MessagePrice],
+    content_len: u64,
+) -> Result<Option<(Addr, Coin)>, ContractError> {
+    let allowances = ALLOWANCES
+        .prefix(sender.clone())
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<(Addr, Allowance)>>>()?;
+
+    for (sponsor, mut allowance) in allowances {
+        if is_expired(env, allowance.expires) {
+            continue;
+        }
+        for price in prices {
+            let amount = message_cost(price, content_len)?;
+            let allowed = match &allowance.limit {
+                AllowanceLimit::Messages(count) => *count > 0,
+                AllowanceLimit::Coins(cap) => cap.denom == price.denom && cap.amount >= amount,
+            };
+            let deposit_key = (sponsor.clone(), price.denom.clone());
+            let deposit = SPONSOR_DEPOSITS
+                .may_load(storage, deposit_key.clone())?
+                .unwrap_or_default();
+            if !allowed || deposit < amount {
+                continue;
+            }
+
+            if deposit == amount {
+                SPONSOR_DEPOSITS.remove(storage, deposit_key);
+            } else {
+                SPONSOR_DEPOSITS.save(storage, deposit_key, &(deposit - amount))?;
+            }
+            let exhausted = match &mut allowance.limit {
+                AllowanceLimit::Messages(count) => {
+                    *count -= 1;
+                    *count == 0
+                }
+                AllowanceLimit::Coins(cap) => {
+                    cap.amount -= amount;
+                    cap.amount.is_zero()
+                }
+            };
+            if exhausted {
+                ALLOWANCES.remove(storage, (sender.clone(), sponsor.clone()));
+            } else {
+                ALLOWANCES.save(storage, (sender.clone(), sponsor.clone()), &allowance)?;
+            }
+
+            let paid = Coin {
+                denom: price.denom.clone(),
+                amount,
+            };
+            return Ok(Some((sponsor, paid)));
+        }
+    }
+
+    Ok(None)
+}
+
+pub(crate) fn is_expired(env: &Env, expires: Option<Timestamp>) -> bool {
+    expires.map_or(false, |expires| env.block.time >= expires)
+}
+
+fn assert_valid_allowance(
+    env: &Env,
+    limit: &AllowanceLimit,
+    expires: Option<Timestamp>,
+) -> Result<(), ContractError> {
+    let empty = match limit {
+        AllowanceLimit::Messages(count) => *count == 0,
+        AllowanceLimit::Coins(cap) => cap.amount.is_zero(),
+    };
+    if empty || is_expired(env, expires) {
+        return Err(ContractError::InvalidAllowance {});
+    }
+    if let AllowanceLimit::Coins(cap) = limit {
+        validate_denom(&cap.denom)?;
+    }
+
+    Ok(())
+}
+
+pub(crate) fn deposit_sponsor_funds(
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    if info.funds.is_empty() {
+        return Err(PaymentError::NoFunds {}.into());
+    }
+
+    for coin in &info.funds {
+        SPONSOR_DEPOSITS.update(
+            deps.storage,
+            (info.sender.clone(), coin.denom.clone()),
+            |deposit| -> StdResult<_> { Ok(deposit.unwrap_or_default().checked_add(coin.amount)?) },
+        )?;
+    }
+
+    let amount = info
+        .funds
+        .iter()
+        .map(|coin| coin.to_string())
+        .collect::<Vec<String>>()
+        .join(",");
+
+    Ok(Response::new()
+        .add_attribute("action", "deposit_sponsor_funds")
+        .add_attribute("sender", info.sender)
+        .add_attribute("amount", amount))
+}
+
+pub(crate) fn withdraw_sponsor_funds(
+    deps: DepsMut,
+    info: MessageInfo,
+    amount: Vec<Coin>,
+) -> Result<Response, ContractError> {
+    for coin in &amount {
+        let key = (info.sender.clone(), coin.denom.clone());
+        let deposit = SPONSOR_DEPOSITS
+            .may_load(deps.storage, key.clone())?
+            .unwrap_or_default();
+        if deposit < coin.amount {
+            return Err(ContractError::InsufficientDeposit {});
+        }
+        if deposit == coin.amount {
+            SPONSOR_DEPOSITS.remove(deps.storage, key);
+        } else {
+            SPONSOR_DEPOSITS.save(deps.storage, key, &(deposit - coin.amount))?;
+        }
+    }
+
+    let mut withdrawn = amount;
+    withdrawn.retain(|coin| !coin.amount.is_zero());
+    if withdrawn.is_empty() {
+        return Err(ContractError::InsufficientDeposit {});
+    }
+    let attribute = withdrawn
+        .iter()
+        .map(|coin| coin.to_string())
+        .collect::<Vec<String>>()
+        .join(",");
+
+    Ok(Response::new()
+        .add_message(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: withdrawn,
+        })
+        .add_attribute("action", "withdraw_sponsor_funds")
+        .add_attribute("sender", info.sender)
+        .add_attribute("amount", attribute))
+}
+
+pub(crate) fn grant_allowance(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    grantee: Addr,
+    limit: AllowanceLimit,
+    expires: Option<Timestamp>,
+) -> Result<Response, ContractError> {
+    let grantee = deps.api.addr_validate(grantee.as_ref())?;
+    assert_valid_allowance(&env, &limit, expires)?;
+
+    ALLOWANCES.save(
+        deps.storage,
+        (grantee.clone(), info.sender.clone()),
+        &Allowance {
+            sponsor: info.sender.clone(),
+            limit,
+            expires,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "grant_allowance")
+        .add_attribute("sender", info.sender)
+        .add_attribute("grantee", grantee))
+}
+
+pub(crate) fn revoke_allowance(
+    deps: DepsMut,
+    info: MessageInfo,
+    grantee: Addr,
+) -> Result<Response, ContractError> {
+    ALLOWANCES.load(deps.storage, (grantee.clone(), info.sender.clone()))?;
+    ALLOWANCES.remove(deps.storage, (grantee.clone(), info.sender.clone()));
+
+    Ok(Response::new()
+        .add_attribute("action", "revoke_allowance")
+        .add_attribute("sender", info.sender)
+        .add_attribute("grantee", grantee))
+}
+
+pub(crate) fn create_sponsor_code(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    code: String,
+    limit: AllowanceLimit,
+    expires: Option<Timestamp>,
+    max_uses: Option<u64>,
+) -> Result<Response, ContractError> {
+    assert_valid_allowance(&env, &limit, expires)?;
+    if SPONSOR_CODES.has(deps.storage, code.clone()) {
+        return Err(ContractError::SponsorCodeExists {});
+    }
+
+    SPONSOR_CODES.save(
+        deps.storage,
+        code.clone(),
+        &SponsorCode {
+            sponsor: info.sender.clone(),
+            limit,
+            expires,
+            max_uses,
+            uses: 0,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "create_sponsor_code")
+        .add_attribute("sender", info.sender)
+        .add_attribute("code", code))
+}
+
+pub(crate) fn remove_sponsor_code(
+    deps: DepsMut,
+    info: MessageInfo,
+    code: String,
+) -> Result<Response, ContractError> {
+    let sponsor_code = SPONSOR_CODES.load(deps.storage, code.clone())?;
+    if sponsor_code.sponsor != info.sender {
+        return Err(ContractError::NotCodeSponsor {});
+    }
+    SPONSOR_CODES.remove(deps.storage, code.clone());
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_sponsor_code")
+        .add_attribute("sender", info.sender)
+        .add_attribute("code", code))
+}
@@ -0,0 +1,149 @@
+use cosmwasm_std::{to_json_binary, Addr, CosmosMsg, DepsMut, MessageInfo, Response, WasmMsg};
+use utils::msg::ProfilesExecuteMsg;
+
+use crate::{error::ContractError, state::PROFILES_ADDRESS};
+
+pub(crate) fn create_subname(
+    deps: DepsMut,
+    info: MessageInfo,
+    parent: String,
+    label: String,
+    holder: Addr,
+    pubkey: String,
+) -> Result<Response, ContractError> {
+    let profile_address = PROFILES_ADDRESS.load(deps.storage)?;
+    let holder = deps.api.addr_validate(holder.as_ref())?;
+
+    let create_subname_msg = ProfilesExecuteMsg::CreateSubname {
+        address: info.sender.clone(),
+        parent: parent.clone(),
+        label: label.clone(),
+        holder: holder.clone(),
+        pubkey,
+    };
+    let msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: profile_address.to_string(),
+        msg: to_json_binary(&create_subname_msg)?,
+        funds: vec![],
+    });
+
+    Ok(Response::new()
+        .add_message(msg)
+        .add_attribute("action", "create_subname")
+        .add_attribute("sender", info.sender)
+        .add_attribute("subname", format!("{}.{}", label, parent))
+        .add_attribute("holder", holder))
+}
+
+pub(crate) fn transfer_subname(
+    deps: DepsMut,
+    info: MessageInfo,
+    parent: String,
+    label: String,
+    holder: Addr,
+    pubkey: String,
+) -> Result<Response, ContractError> {
+    let profile_address = PROFILES_ADDRESS.load(deps.storage)?;
+    let holder = deps.api.addr_validate(holder.as_ref())?;
+
+    let transfer_subname_msg = ProfilesExecuteMsg::TransferSubname {
+        address: info.sender.clone(),
+        parent: parent.clone(),
+        label: label.clone(),
+        holder: holder.clone(),
+        pubkey,
+    };
+    let msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: profile_address.to_string(),
+        msg: to_json_binary(&transfer_subname_msg)?,
+        funds: vec![],
+    });
+
+    Ok(Response::new()
+        .add_message(msg)
+        .add_attribute("action", "transfer_subname")
+        .add_attribute("sender", info.sender)
+        .add_attribute("subname", format!("{}.{}", label, parent))
+        .add_attribute("holder", holder))
+}
+
+pub(crate) fn revoke_subname(
+    deps: DepsMut,
+    info: MessageInfo,
+    parent: String,
+    label: String,
+) -> Result<Response, ContractError> {
+    let profile_address = PROFILES_ADDRESS.load(deps.storage)?;
+
+    let revoke_subname_msg = ProfilesExecuteMsg::RevokeSubname {
+        address: info.sender.clone(),
+        parent: parent.clone(),
+        label: label.clone(),
+    };
+    let msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: profile_address.to_string(),
+        msg: to_json_binary(&revoke_subname_msg)?,
+        funds: vec![],
+    });
+
+    Ok(Response::new()
+        .add_message(msg)
+        .add_attribute("action", "revoke_subname")
+        .add_attribute("sender", info.sender)
+        .add_attribute("subname", format!("{}.{}", label, parent)))
+}
+
+pub(crate) fn change_subname_pubkey(
+    deps: DepsMut,
+    info: MessageInfo,
+    parent: String,
+    label: String,
+    pubkey: String,
+) -> Result<Response, ContractError> {
+    let profile_address = PROFILES_ADDRESS.load(deps.storage)?;
+
+    let change_pubkey_msg = ProfilesExecuteMsg::ChangeSubnamePubkey {
+        address: info.sender.clone(),
+        parent: parent.clone(),
+        label: label.clone(),
+        pubkey: pubkey.clone(),
+    };
+    let msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: profile_address.to_string(),
+        msg: to_json_binary(&change_pubkey_msg)?,
+        funds: vec![],
+    });
+
+    Ok(Response::new()
+        .add_message(msg)
+        .add_attribute("action", "change_subname_pubkey")
+        .add_attribute("sender", info.sender)
+        .add_attribute("subname", format!("{}.{}", label, parent))
+        .add_attribute("pubkey", pubkey))
+}
+
+pub(crate) fn set_subname_policy(
+    deps: DepsMut,
+    info: MessageInfo,
+    parent: String,
+    holders_can_change_pubkey: bool,
+) -> Result<Response, ContractError> {
+    let profile_address = PROFILES_ADDRESS.load(deps.storage)?;
+
+    let set_policy_msg = ProfilesExecuteMsg::SetSubnamePolicy {
+        address: info.sender.clone(),
+        parent: parent.clone(),
+        holders_can_change_pubkey,
+    };
+    let msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: profile_address.to_string(),
+        msg: to_json_binary(&set_policy_msg)?,
+        funds: vec![],
+    });
+
+    Ok(Response::new()
+        .add_message(msg)
+        .add_attribute("action", "set_subname_policy")
+        .add_attribute("sender", info.sender)
+        .add_attribute("parent", parent))
+}
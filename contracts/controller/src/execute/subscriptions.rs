@@ -0,0 +1,118 @@
+use cosmwasm_std::{Addr, Coin, DepsMut, Env, MessageInfo, Response, StdResult, Storage};
+
+use crate::{
+    error::ContractError,
+    state::{Plan, Role, Subscription, PLANS, SUBSCRIPTIONS},
+};
+
+use super::config::validate_denom;
+use super::fees::record_fees;
+use super::profiles::assert_exact_funds;
+use super::roles::assert_role;
+
+// Takes one message from the sender's quota if its subscription is active, returning the plan name.
+pub(crate) fn use_subscription_quota(
+    storage: &mut dyn Storage,
+    env: &Env,
+    sender: &Addr,
+) -> StdResult<Option<String>> {
+    match SUBSCRIPTIONS.may_load(storage, sender.clone())? {
+        Some(mut subscription) if is_active(env, &subscription) => {
+            subscription.remaining -= 1;
+            SUBSCRIPTIONS.save(storage, sender.clone(), &subscription)?;
+            Ok(Some(subscription.plan))
+        }
+        _ => Ok(None),
+    }
+}
+
+pub(crate) fn is_active(env: &Env, subscription: &Subscription) -> bool {
+    env.block.time < subscription.expires && subscription.remaining > 0
+}
+
+pub(crate) fn set_plan(
+    deps: DepsMut,
+    info: MessageInfo,
+    name: String,
+    price: Coin,
+    period: u64,
+    quota: u64,
+) -> Result<Response, ContractError> {
+    assert_role(deps.storage, &info.sender, Role::ConfigManager)?;
+
+    if period == 0 || quota == 0 {
+        return Err(ContractError::InvalidPlan {});
+    }
+    if price.amount.is_zero() {
+        return Err(ContractError::InvalidPrice {});
+    }
+    validate_denom(&price.denom)?;
+
+    PLANS.save(
+        deps.storage,
+        name.clone(),
+        &Plan {
+            price: price.clone(),
+            period,
+            quota,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_plan")
+        .add_attribute("sender", info.sender)
+        .add_attribute("plan", name)
+        .add_attribute("price", price.to_string())
+        .add_attribute("period", period.to_string())
+        .add_attribute("quota", quota.to_string()))
+}
+
+pub(crate) fn remove_plan(
+    deps: DepsMut,
+    info: MessageInfo,
+    name: String,
+) -> Result<Response, ContractError> {
+    assert_role(deps.storage, &info.sender, Role::ConfigManager)?;
+
+    PLANS.load(deps.storage, name.clone())?;
+    PLANS.remove(deps.storage, name.clone());
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_plan")
+        .add_attribute("sender", info.sender)
+        .add_attribute("plan", name))
+}
+
+pub(crate) fn subscribe(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    plan_name: String,
+) -> Result<Response, ContractError> {
+    let plan = PLANS.load(deps.storage, plan_name.clone())?;
+    assert_exact_funds(&info, plan.price)?;
+    record_fees(deps.storage, &info.funds)?;
+
+    // An unexpired subscription is extended from its current end, with its remaining quota kept,
+    // even if that quota is used up.
+    let subscription = match SUBSCRIPTIONS.may_load(deps.storage, info.sender.clone())? {
+        Some(current) if env.block.time < current.expires => Subscription {
+            plan: plan_name.clone(),
+            expires: current.expires.plus_seconds(plan.period),
+            remaining: current.remaining + plan.quota,
+        },
+        _ => Subscription {
+            plan: plan_name.clone(),
+            expires: env.block.time.plus_seconds(plan.period),
+            remaining: plan.quota,
+        },
+    };
+    SUBSCRIPTIONS.save(deps.storage, info.sender.clone(), &subscription)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "subscribe")
+        .add_attribute("sender", info.sender)
+        .add_attribute("plan", plan_name)
+        .add_attribute("expires", subscription.expires.to_string())
+        .add_attribute("remaining", subscription.remaining.to_string()))
+}
@@ -1,4 +1,8 @@
 pub mod contract;
 pub mod error;
+mod execute;
 pub mod msg;
-pub mod state;
\ No newline at end of file
+pub mod state;
+
+#[cfg(test)]
+mod tests;
@@ -1,8 +1,14 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, Coin, Binary};
+use cosmwasm_std::{Addr, Binary, Coin, Decimal, Timestamp};
+use cw2::ContractVersion;
 use cw_ownable::cw_ownable_execute;
+use utils::{elements::KeyShare, msg::ImportEntry};
 
-use crate::state::Config;
+use crate::state::{
+    Allowance, AllowanceLimit, Auction, AuctionConfig, ChildContract, Config, ConfigChange,
+    ConfigUpdate, FeeShare, LengthPrice, Listing, MessagePrice, Operation, PauseFlags, Plan,
+    Proposal, RecoveryRequest, RecoverySetup, ReferralStats, Role, SponsorCode, Subscription,
+};
 
 #[cw_serde]
 pub struct InstantiateMsg {
@@ -11,16 +17,56 @@ pub struct InstantiateMsg {
     pub message_max_len: u64,
     pub message_query_default_limit: u64,
     pub message_query_max_limit: u64,
+    pub user_id_cooldown: u64,
+    pub max_aliases: u64,
     pub create_profile_cost: Option<Coin>,
-    pub send_message_cost: Option<Coin>,
+    pub send_message_prices: Vec<MessagePrice>,
+    pub user_id_trade_fee: Decimal,
+    pub referral_share: Decimal,
+    pub user_id_length_prices: Vec<LengthPrice>,
+    pub user_id_auction: Option<AuctionConfig>,
+    pub user_id_term: Option<u64>,
+    /// Seconds an expired user id stays with its holder before anyone can register it again.
+    pub user_id_grace_period: u64,
+    /// Minimum seconds between two changes of a profile's user id.
+    pub user_id_change_cooldown: u64,
+    /// Charged on every user id change, on top of the new id's price if it isn't one of the caller's aliases.
+    pub user_id_change_fee: Option<Coin>,
+    /// Seconds a replaced user id keeps resolving to the profile that changed it.
+    pub user_id_redirect_period: u64,
+    pub recovery_delay: u64,
+    pub key_backup_max_len: u64,
+    /// Seconds config, fee share, child migration and ownership changes wait before they apply.
+    pub timelock_delay: u64,
 }
 
 #[cw_ownable_execute]
 #[cw_serde]
 pub enum ExecuteMsg {
+    /// `sponsor_code` grants the new profile the allowance of that sponsor code. The profile of the
+    /// `referrer` user id receives the referral share of the registration price. Only paid
+    /// registrations count as referrals.
     CreateProfile {
         user_id: String,
         pubkey: String,
+        sponsor_code: Option<String>,
+        referrer: Option<String>,
+    },
+    /// Registers a profile under a blinded user id, the hex encoded sha256 of the profiles contract
+    /// address followed by the user id. As its length is unknown, it costs the highest registration
+    /// price, and short user ids can be registered this way without going through an auction. A
+    /// user id can't be held both privately and publicly. The hash only hides user ids that can't
+    /// be guessed: the contract address is public, so anyone can hash a list of likely names and
+    /// find which of them are registered.
+    CreatePrivateProfile {
+        user_id_hash: String,
+        pubkey: String,
+    },
+    /// Registers profiles migrated from the legacy directory, at most 50 per call. Only for admins.
+    /// Entries that fail, including user ids that can only be registered through an auction, are
+    /// skipped and reported in the `ImportProfilesResponse` set as response data.
+    ImportProfiles {
+        entries: Vec<ImportEntry>,
     },
     ChangeUserId {
         user_id: String,
@@ -28,18 +74,290 @@ pub enum ExecuteMsg {
     ChangePubkey {
         pubkey: String,
     },
+    /// Registers an extra user id for the caller's profile. Costs the same as creating a profile.
+    AddAlias {
+        alias: String,
+    },
+    RemoveAlias {
+        alias: String,
+    },
+    /// Offers one of the caller's user ids to `to`, who must accept it with `AcceptUserId`.
+    TransferUserId {
+        user_id: String,
+        to: Addr,
+    },
+    CancelUserIdTransfer {
+        user_id: String,
+    },
+    /// `pubkey` is required if the caller doesn't have a profile yet.
+    AcceptUserId {
+        user_id: String,
+        pubkey: Option<String>,
+    },
+    /// Lists one of the caller's user ids for sale. The only user id of a profile can't be listed.
+    /// The listing is removed once the user id is transferred, renamed, deleted or re-registered.
+    ListUserId {
+        user_id: String,
+        price: Coin,
+    },
+    DelistUserId {
+        user_id: String,
+    },
+    /// Buys a listed user id, paying exactly its price. `pubkey` is required if the caller doesn't have
+    /// a profile yet.
+    BuyUserId {
+        user_id: String,
+        pubkey: Option<String>,
+    },
+    /// Bids on a premium user id, starting its auction if there is none. Outbid funds are refunded.
+    /// `pubkey` is used to create the winner's profile if it has none when the auction is settled,
+    /// and is required if the caller doesn't have a profile yet.
+    BidUserId {
+        user_id: String,
+        pubkey: Option<String>,
+    },
+    /// Registers the user id for the highest bidder once the auction has ended. Anyone can settle.
+    /// The winning bid is refunded if the registration fails.
+    SettleAuction {
+        user_id: String,
+    },
+    /// Creates the subname `label.parent` under one of the caller's user ids, which must not have
+    /// expired.
+    CreateSubname {
+        parent: String,
+        label: String,
+        holder: Addr,
+        pubkey: String,
+    },
+    TransferSubname {
+        parent: String,
+        label: String,
+        holder: Addr,
+        pubkey: String,
+    },
+    RevokeSubname {
+        parent: String,
+        label: String,
+    },
+    ChangeSubnamePubkey {
+        parent: String,
+        label: String,
+        pubkey: String,
+    },
+    /// Sets whether holders of subnames under `parent` can change their own pubkey.
+    SetSubnamePolicy {
+        parent: String,
+        holders_can_change_pubkey: bool,
+    },
+    /// Extends the registration of a user id by one term, paying its registration price. Anyone can renew.
+    RenewProfile {
+        user_id: String,
+    },
+    /// Shows or hides the caller's profile in directory listings. Profiles are listed by default.
+    SetDirectoryListing {
+        listed: bool,
+    },
+    /// Keeps the user id but closes the inbox to new messages.
+    DeactivateProfile {},
+    ReactivateProfile {},
+    /// Removes the profile and the inbox. The user id is freed after the configured cooldown.
+    /// Unclaimed funds are sent to the caller, or back to the senders if `refund_funds` is set.
+    /// Fails while claims are paused.
+    DeleteProfile {
+        refund_funds: bool,
+    },
+    /// Links an address on another chain to the caller's profile. `signature` is over the challenge
+    /// returned by the profiles `LinkChallenge` query. `pubkey` is required for Cosmos addresses.
+    LinkAddress {
+        external_address: String,
+        pubkey: Option<Binary>,
+        signature: Binary,
+    },
+    UnlinkAddress {
+        external_address: String,
+    },
+    /// Only for moderators, as is `RemoveVerifier`. `pubkey` is the compressed secp256k1 pubkey the
+    /// verifier signs its attestations with.
+    AddVerifier {
+        verifier: Addr,
+        pubkey: Binary,
+    },
+    RemoveVerifier {
+        verifier: Addr,
+    },
+    /// Attests `kind`, e.g. "domain verified", about the profile of `address`. Only for verifiers,
+    /// with a `signature` made as described in the profiles contract's `Attest`.
+    Attest {
+        address: Addr,
+        kind: String,
+        expires: Timestamp,
+        signature: Binary,
+    },
+    RevokeAttestation {
+        address: Addr,
+        kind: String,
+    },
+    /// Stores an encrypted backup of the caller's private key. `expected_version` must match the stored
+    /// backup, 0 if there is none, so that concurrent writes don't silently overwrite each other.
+    SetKeyBackup {
+        data: Binary,
+        expected_version: u64,
+    },
+    /// Stores shares of the caller's private key, each encrypted to its guardian's profile pubkey.
+    SetKeyShares {
+        shares: Vec<KeyShare>,
+        threshold: u64,
+    },
+    /// Asks the caller's guardians to re-encrypt their key shares to `new_pubkey`.
+    RequestKeyRecovery {
+        new_pubkey: String,
+    },
+    CancelKeyRecovery {},
+    /// Approves the key recovery of `address` with the caller's share re-encrypted to the new pubkey.
+    SubmitKeyShare {
+        address: Addr,
+        share: Binary,
+    },
+    /// Sets the guardians that can move the caller's profile and inbox to a new address.
+    SetRecovery {
+        guardians: Vec<Addr>,
+        threshold: u64,
+    },
+    RemoveRecovery {},
+    /// Approves moving the profile of `address` to `new_address`. The first approval opens the request.
+    /// Approving another address moves the caller's approval. Once the threshold approves another
+    /// address than the one of the request, the request is replaced and its delay restarts.
+    ApproveRecovery {
+        address: Addr,
+        new_address: String,
+    },
+    /// Cancels a pending recovery of the caller's profile.
+    CancelRecovery {},
+    /// Moves the profile and inbox once the recovery delay has passed, along with the listings,
+    /// winning bids and subscription of the recovered address. Anyone can execute.
+    ExecuteRecovery {
+        address: Addr,
+    },
     SendMessage {
         content: Binary,
         dest_address: Option<Addr>,
         dest_id: Option<String>,
+        dest_id_hash: Option<String>,
+    },
+    /// Creates or replaces a subscription plan. Only for config managers.
+    SetPlan {
+        name: String,
+        price: Coin,
+        period: u64,
+        quota: u64,
+    },
+    /// Stops selling a plan. Existing subscriptions are kept.
+    RemovePlan {
+        name: String,
+    },
+    /// Buys a plan, paying exactly its price. Buying while subscribed adds the plan's quota and
+    /// period to the current subscription.
+    Subscribe {
+        plan: String,
+    },
+    /// Adds the attached funds to the caller's sponsor deposit.
+    DepositSponsorFunds {},
+    WithdrawSponsorFunds {
+        amount: Vec<Coin>,
+    },
+    /// Lets `grantee` send messages paid from the caller's deposit. Replaces any previous
+    /// allowance from the caller.
+    GrantAllowance {
+        grantee: Addr,
+        limit: AllowanceLimit,
+        expires: Option<Timestamp>,
+    },
+    RevokeAllowance {
+        grantee: Addr,
+    },
+    /// Creates a code that grants the allowance to every profile created with it, up to `max_uses`
+    /// profiles. Sponsoring goes through its own code rather than referrals, as anyone holding a
+    /// user id can refer and free registrations don't count as referrals: the sponsor hands the
+    /// code only to the users it onboards and caps how many allowances its deposit backs.
+    CreateSponsorCode {
+        code: String,
+        limit: AllowanceLimit,
+        expires: Option<Timestamp>,
+        max_uses: Option<u64>,
+    },
+    RemoveSponsorCode {
+        code: String,
     },
     ChangeMessagesConfig {
         message_query_default_limit: u64,
         message_query_max_limit: u64,
     },
+    ChangeProfilesConfig {
+        user_id_cooldown: u64,
+        max_aliases: u64,
+        user_id_grace_period: u64,
+        user_id_change_cooldown: u64,
+        user_id_redirect_period: u64,
+    },
+    /// Only for admins, and only for the owner when `role` is `Admin`. The owner has every role
+    /// without being granted them.
+    GrantRole {
+        role: Role,
+        address: Addr,
+    },
+    /// Like `GrantRole`.
+    RevokeRole {
+        role: Role,
+        address: Addr,
+    },
+    /// Pauses or resumes one operation. Only for pausers.
+    SetPaused {
+        operation: Operation,
+        paused: bool,
+    },
+    /// Schedules a change of the controller settings. Only for config managers, as are the
+    /// `Change*Config` messages.
+    UpdateConfig(Box<ConfigUpdate>),
+    /// Schedules who receives the collected fees and in which proportions. Only for admins.
+    SetFeeShares {
+        shares: Vec<FeeShare>,
+    },
+    /// Schedules the migration of the profiles or messages contract. Only for admins.
+    MigrateChild {
+        contract: ChildContract,
+        new_code_id: u64,
+        msg: Binary,
+    },
+    /// Points the controller at a replacement profiles or messages contract. Only for the owner.
+    /// The contract must report the expected cw2 name with a version not below the current
+    /// child's, have the controller as wasm admin and as owner or pending owner. A pending
+    /// ownership is accepted when the proposal is executed.
+    SetChildContract {
+        contract: ChildContract,
+        address: Addr,
+    },
+    /// Only for the proposer and admins.
+    CancelProposal {
+        id: u64,
+    },
+    /// Applies a scheduled change once its eta has passed. Anyone can execute.
+    ExecuteProposal {
+        id: u64,
+    },
+    /// Pays the fees collected since the last distribution to the fee share recipients. Only for
+    /// fee collectors.
+    DistributeFees {},
+    /// Deprecated, use `DistributeFees`. Pays all the fees collected since the last distribution to
+    /// `receiver`, the owner by default, without the fee shares. Unlike before, funds that are not
+    /// tracked fees stay in the controller. Only for the owner.
     RetrieveFees {
         receiver: Option<Addr>,
     },
+    /// Sends `recipient` the funds held by the controller beyond the undistributed fees, the
+    /// running auction bids and the sponsor deposits, e.g. sent by mistake. Only for admins.
+    SweepFunds {
+        recipient: Addr,
+    },
 }
 
 #[cw_serde]
@@ -47,4 +365,184 @@ pub enum ExecuteMsg {
 pub enum QueryMsg {
     #[returns(Config)]
     Config {},
+    /// Addresses, code ids and cw2 versions of the child contracts, and the controller's version.
+    #[returns(DeploymentResponse)]
+    Deployment {},
+    #[returns(PauseFlags)]
+    PauseFlags {},
+    #[returns(Proposal)]
+    Proposal { id: u64 },
+    /// Pending proposals, oldest first.
+    #[returns(ProposalsResponse)]
+    Proposals {
+        start_after: Option<u64>,
+        limit: Option<u64>,
+    },
+    /// Members of `role` in ascending order.
+    #[returns(RoleMembers)]
+    RoleMembers {
+        role: Role,
+        start_after: Option<Addr>,
+        limit: Option<u64>,
+    },
+    #[returns(Listing)]
+    Listing { user_id: String },
+    #[returns(Auction)]
+    Auction { user_id: String },
+    #[returns(ListingsResponse)]
+    Listings {
+        start_after: Option<String>,
+        limit: Option<u64>,
+    },
+    #[returns(RecoveryResponse)]
+    Recovery { address: Addr },
+    /// Cost of sending a message of `content_len` bytes paid in `denom`.
+    #[returns(EstimateCostResponse)]
+    EstimateCost { content_len: u64, denom: String },
+    #[returns(ReferralStats)]
+    Referrals { address: Addr },
+    /// Referrers with the most referrals first.
+    #[returns(ReferrersResponse)]
+    Referrers {
+        start_after: Option<(u64, Addr)>,
+        limit: Option<u64>,
+    },
+    #[returns(PlansResponse)]
+    Plans {},
+    #[returns(SubscriptionResponse)]
+    Subscription { address: Addr },
+    #[returns(SponsorDepositResponse)]
+    SponsorDeposit { sponsor: Addr },
+    #[returns(AllowancesResponse)]
+    Allowances { grantee: Addr },
+    #[returns(SponsorCode)]
+    SponsorCode { code: String },
+    #[returns(FeeTotalsResponse)]
+    FeeTotals {},
+    #[returns(FeeSharesResponse)]
+    FeeShares {},
+    /// Past `UpdateConfig` calls, oldest first.
+    #[returns(ConfigHistoryResponse)]
+    ConfigHistory {
+        start_after: Option<u64>,
+        limit: Option<u64>,
+    },
+}
+
+#[cw_serde]
+pub struct DeploymentResponse {
+    pub controller: ContractVersion,
+    pub profiles: ChildDeployment,
+    pub messages: ChildDeployment,
+}
+
+#[cw_serde]
+pub struct ChildDeployment {
+    pub address: Addr,
+    pub code_id: u64,
+    pub version: ContractVersion,
+}
+
+#[cw_serde]
+pub struct ProposalsResponse {
+    pub proposals: Vec<ProposalInfo>,
+}
+
+#[cw_serde]
+pub struct ProposalInfo {
+    pub id: u64,
+    pub proposal: Proposal,
+}
+
+#[cw_serde]
+pub struct RoleMembers {
+    pub role: Role,
+    pub members: Vec<Addr>,
+}
+
+#[cw_serde]
+pub struct ListingsResponse {
+    pub listings: Vec<ListingInfo>,
+}
+
+#[cw_serde]
+pub struct ListingInfo {
+    pub user_id: String,
+    pub seller: Addr,
+    pub price: Coin,
+}
+
+#[cw_serde]
+pub struct EstimateCostResponse {
+    pub cost: Coin,
+}
+
+#[cw_serde]
+pub struct ReferrersResponse {
+    pub referrers: Vec<ReferrerInfo>,
+}
+
+#[cw_serde]
+pub struct ReferrerInfo {
+    pub address: Addr,
+    pub count: u64,
+    pub earnings: Vec<Coin>,
+}
+
+#[cw_serde]
+pub struct PlansResponse {
+    pub plans: Vec<PlanInfo>,
+}
+
+#[cw_serde]
+pub struct PlanInfo {
+    pub name: String,
+    pub plan: Plan,
+}
+
+#[cw_serde]
+pub struct SubscriptionResponse {
+    pub subscription: Option<Subscription>,
+    /// Whether the subscription is unexpired and has quota left.
+    pub active: bool,
+}
+
+#[cw_serde]
+pub struct SponsorDepositResponse {
+    pub funds: Vec<Coin>,
+}
+
+#[cw_serde]
+pub struct AllowancesResponse {
+    pub allowances: Vec<Allowance>,
+}
+
+#[cw_serde]
+pub struct FeeTotalsResponse {
+    /// Every fee collected since instantiation.
+    pub lifetime: Vec<Coin>,
+    /// Fees waiting for the next `DistributeFees`.
+    pub undistributed: Vec<Coin>,
+}
+
+#[cw_serde]
+pub struct FeeSharesResponse {
+    pub shares: Vec<FeeShare>,
+}
+
+#[cw_serde]
+pub struct ConfigHistoryResponse {
+    pub entries: Vec<ConfigHistoryEntry>,
+}
+
+#[cw_serde]
+pub struct ConfigHistoryEntry {
+    pub id: u64,
+    pub change: ConfigChange,
+}
+
+#[cw_serde]
+pub struct RecoveryResponse {
+    pub setup: Option<RecoverySetup>,
+    pub request: Option<RecoveryRequest>,
 }
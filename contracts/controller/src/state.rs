@@ -1,16 +1,402 @@
-use cosmwasm_std::{Addr, Coin};
-use cw_storage_plus::Item;
+use cosmwasm_std::{Addr, Binary, Coin, Decimal, Empty, Timestamp, Uint128};
+use cw_storage_plus::{Item, Map};
 use cosmwasm_schema::cw_serde;
+use utils::msg::ImportFailure;
 
 #[cw_serde]
 pub struct Config {
     pub message_max_len: u64,
-    pub message_cost: Option<Coin>,
+    /// Accepted denoms for paying messages. Messages are free if empty.
+    pub message_prices: Vec<MessagePrice>,
     pub profile_cost: Option<Coin>,
+    /// Share of every marketplace sale kept by the controller.
+    pub trade_fee: Decimal,
+    /// Share of a profile's registration price paid to the user id that referred it.
+    pub referral_share: Decimal,
+    /// Registration prices for short user ids. Ids longer than every entry cost `profile_cost`.
+    pub length_prices: Vec<LengthPrice>,
+    pub auction: Option<AuctionConfig>,
+    /// Seconds a user id stays registered before it must be renewed. `None` registers ids forever.
+    pub registration_term: Option<u64>,
+    /// Charged on every user id change.
+    pub change_fee: Option<Coin>,
+    /// Seconds between a recovery reaching its threshold and the moment it can be executed.
+    pub recovery_delay: u64,
+    /// Maximum size in bytes of a profile's key backup.
+    pub key_backup_max_len: u64,
+    /// Seconds between scheduling a sensitive change and the moment it can be executed.
+    pub timelock_delay: u64,
+}
+
+/// A message costs `base + per_byte * content length` in `denom`.
+#[cw_serde]
+pub struct MessagePrice {
+    pub denom: String,
+    pub base: Uint128,
+    pub per_byte: Uint128,
+}
+
+/// Price of user ids with at most `max_len` characters.
+#[cw_serde]
+pub struct LengthPrice {
+    pub max_len: u64,
+    pub cost: Coin,
+}
+
+/// User ids shorter than `below_len` characters can only be registered through an auction.
+#[cw_serde]
+pub struct AuctionConfig {
+    pub below_len: u64,
+    /// Seconds an auction lasts after its first bid.
+    pub duration: u64,
+    /// A bid placed less than this many seconds before the end pushes the end to this far away.
+    pub extension: u64,
+    pub min_bid: Coin,
+}
+
+#[cw_serde]
+pub struct Auction {
+    pub highest_bidder: Addr,
+    pub highest_bid: Coin,
+    /// Used to create the winner's profile if it doesn't have one when the auction is settled.
+    pub pubkey: Option<String>,
+    pub end: Timestamp,
+}
+
+#[cw_serde]
+pub struct Listing {
+    pub seller: Addr,
+    pub price: Coin,
+}
+
+/// Guardians allowed to move a profile to a new address. A single recovery address is a set of one
+/// guardian with a threshold of 1.
+#[cw_serde]
+pub struct RecoverySetup {
+    pub guardians: Vec<Addr>,
+    pub threshold: u64,
+}
+
+#[cw_serde]
+pub struct RecoveryApproval {
+    pub guardian: Addr,
+    pub new_address: Addr,
+}
+
+#[cw_serde]
+pub struct RecoveryRequest {
+    /// The latest approval of each guardian. Approving another address replaces the previous one.
+    pub approvals: Vec<RecoveryApproval>,
+    /// The address approved by the threshold of guardians. Replaced when the threshold approves
+    /// another address.
+    pub new_address: Option<Addr>,
+    /// Set when the threshold is reached. The current address can cancel the request until then.
+    pub executable_at: Option<Timestamp>,
+}
+
+/// A sensitive change waiting for its timelock.
+#[cw_serde]
+pub struct Proposal {
+    pub action: ProposalAction,
+    pub proposer: Addr,
+    /// Earliest time the proposal can be executed.
+    pub eta: Timestamp,
+}
+
+#[cw_serde]
+pub enum ChildContract {
+    Profiles,
+    Messages,
+}
+
+impl ChildContract {
+    /// The cw2 contract name of the child.
+    pub fn contract_name(&self) -> &'static str {
+        match self {
+            ChildContract::Profiles => "profiles",
+            ChildContract::Messages => "messages",
+        }
+    }
+}
+
+/// Sensitive changes, applied through a timelocked proposal.
+#[cw_serde]
+pub enum ProposalAction {
+    UpdateConfig(Box<ConfigUpdate>),
+    SetFeeShares {
+        shares: Vec<FeeShare>,
+    },
+    MigrateChild {
+        contract: ChildContract,
+        new_code_id: u64,
+        msg: Binary,
+    },
+    /// Transfers or renounces ownership. Runs as the proposer, who must still be the owner.
+    UpdateOwnership(cw_ownable::Action),
+    /// Repoints the controller at a replacement child. The checks run again on execution.
+    SetChildContract {
+        contract: ChildContract,
+        address: Addr,
+    },
+}
+
+impl ProposalAction {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            ProposalAction::UpdateConfig(_) => "update_config",
+            ProposalAction::SetFeeShares { .. } => "set_fee_shares",
+            ProposalAction::MigrateChild { .. } => "migrate_child",
+            ProposalAction::UpdateOwnership(_) => "update_ownership",
+            ProposalAction::SetChildContract { .. } => "set_child_contract",
+        }
+    }
+
+    /// The role needed to schedule and execute the action. `None` if only the owner can.
+    pub fn role(&self) -> Option<Role> {
+        match self {
+            ProposalAction::UpdateConfig(_) => Some(Role::ConfigManager),
+            ProposalAction::SetFeeShares { .. } | ProposalAction::MigrateChild { .. } => {
+                Some(Role::Admin)
+            }
+            ProposalAction::UpdateOwnership(_) | ProposalAction::SetChildContract { .. } => None,
+        }
+    }
+}
+
+/// Fields left out keep their current value. A zero amount removes a cost, a zero
+/// `registration_term` registers user ids forever and a zero auction `below_len` disables auctions.
+#[cw_serde]
+pub struct ConfigUpdate {
+    pub message_max_len: Option<u64>,
+    pub message_prices: Option<Vec<MessagePrice>>,
+    pub profile_cost: Option<Coin>,
+    pub trade_fee: Option<Decimal>,
+    pub referral_share: Option<Decimal>,
+    pub length_prices: Option<Vec<LengthPrice>>,
+    pub auction: Option<AuctionConfig>,
+    pub registration_term: Option<u64>,
+    pub change_fee: Option<Coin>,
+    pub recovery_delay: Option<u64>,
+    pub key_backup_max_len: Option<u64>,
+    pub timelock_delay: Option<u64>,
+}
+
+/// Roles that can run administrative executes. The cw-ownable owner has every role.
+#[cw_serde]
+pub enum Role {
+    /// Grants and revokes the other roles, sets fee shares and imports profiles.
+    Admin,
+    FeeCollector,
+    /// Manages the verifiers of the profiles contract.
+    Moderator,
+    Pauser,
+    /// Changes the settings of the controller and its contracts, and the subscription plans.
+    ConfigManager,
+}
+
+impl Role {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::Admin => "admin",
+            Role::FeeCollector => "fee_collector",
+            Role::Moderator => "moderator",
+            Role::Pauser => "pauser",
+            Role::ConfigManager => "config_manager",
+        }
+    }
+}
+
+/// Operations that can be paused separately in an emergency.
+#[cw_serde]
+pub enum Operation {
+    ProfileCreation,
+    ProfileChanges,
+    Sending,
+    ConfigForwarding,
+    /// Payouts of message funds in the messages contract: claims, deleted messages and the inbox
+    /// of a deleted profile.
+    Claims,
+}
+
+impl Operation {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Operation::ProfileCreation => "profile_creation",
+            Operation::ProfileChanges => "profile_changes",
+            Operation::Sending => "sending",
+            Operation::ConfigForwarding => "config_forwarding",
+            Operation::Claims => "claims",
+        }
+    }
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct PauseFlags {
+    pub profile_creation: bool,
+    pub profile_changes: bool,
+    pub sending: bool,
+    pub config_forwarding: bool,
+    pub claims: bool,
+}
+
+impl PauseFlags {
+    pub fn is_paused(&self, operation: &Operation) -> bool {
+        match operation {
+            Operation::ProfileCreation => self.profile_creation,
+            Operation::ProfileChanges => self.profile_changes,
+            Operation::Sending => self.sending,
+            Operation::ConfigForwarding => self.config_forwarding,
+            Operation::Claims => self.claims,
+        }
+    }
+
+    pub fn flag(&mut self, operation: &Operation) -> &mut bool {
+        match operation {
+            Operation::ProfileCreation => &mut self.profile_creation,
+            Operation::ProfileChanges => &mut self.profile_changes,
+            Operation::Sending => &mut self.sending,
+            Operation::ConfigForwarding => &mut self.config_forwarding,
+            Operation::Claims => &mut self.claims,
+        }
+    }
+}
+
+/// Receives `weight` parts of the distributed fees, e.g. a treasury, a dev fund or a burn address.
+#[cw_serde]
+pub struct FeeShare {
+    pub recipient: Addr,
+    pub weight: u64,
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct ReferralStats {
+    pub count: u64,
+    pub earnings: Vec<Coin>,
+}
+
+/// Messaging subscription sold by the controller. Each purchase adds `quota` messages and `period`
+/// seconds to the buyer's subscription.
+#[cw_serde]
+pub struct Plan {
+    pub price: Coin,
+    pub period: u64,
+    pub quota: u64,
+}
+
+#[cw_serde]
+pub struct Subscription {
+    /// Last plan bought.
+    pub plan: String,
+    pub expires: Timestamp,
+    pub remaining: u64,
+}
+
+/// Lets `sponsor` pay for messages of another address from its deposit.
+#[cw_serde]
+pub struct Allowance {
+    pub sponsor: Addr,
+    pub limit: AllowanceLimit,
+    pub expires: Option<Timestamp>,
+}
+
+#[cw_serde]
+pub enum AllowanceLimit {
+    /// Number of messages left.
+    Messages(u64),
+    /// Amount left to spend, only in this denom.
+    Coins(Coin),
+}
+
+/// Allowance granted to every profile created with the code.
+#[cw_serde]
+pub struct SponsorCode {
+    pub sponsor: Addr,
+    pub limit: AllowanceLimit,
+    pub expires: Option<Timestamp>,
+    /// Number of profiles that can be created with the code. Unlimited if None.
+    pub max_uses: Option<u64>,
+    /// Number of profiles created with the code.
+    pub uses: u64,
+}
+
+/// Settings changed by one `UpdateConfig` call.
+#[cw_serde]
+pub struct ConfigChange {
+    pub sender: Addr,
+    pub time: Timestamp,
+    pub changes: Vec<ConfigFieldChange>,
+}
+
+#[cw_serde]
+pub struct ConfigFieldChange {
+    pub field: String,
+    pub value: String,
 }
 
 pub const CONFIG: Item<Config> = Item::new("config");
 
+pub const PAUSE_FLAGS: Item<PauseFlags> = Item::new("pause_flags");
+
+/// Pending proposals. Executed and cancelled proposals are removed.
+pub const PROPOSALS: Map<u64, Proposal> = Map::new("proposals");
+
+pub const NEXT_PROPOSAL_ID: Item<u64> = Item::new("next_proposal_id");
+
+/// Role members keyed by role name and address.
+pub const ROLES: Map<(String, Addr), Empty> = Map::new("roles");
+
+/// Every `UpdateConfig` call, keyed by an increasing id.
+pub const CONFIG_HISTORY: Map<u64, ConfigChange> = Map::new("config_history");
+
+/// Fees collected since the last distribution, by denom.
+pub const COLLECTED_FEES: Map<String, Uint128> = Map::new("collected_fees");
+
+/// Every fee ever collected, by denom.
+pub const LIFETIME_FEES: Map<String, Uint128> = Map::new("lifetime_fees");
+
+pub const FEE_SHARES: Item<Vec<FeeShare>> = Item::new("fee_shares");
+
+/// Referral statistics keyed by the referrer's address.
+pub const REFERRALS: Map<Addr, ReferralStats> = Map::new("referrals");
+
+/// Referrers keyed by their referral count, to list them by count.
+pub const REFERRAL_RANKING: Map<(u64, Addr), Empty> = Map::new("referral_ranking");
+
+pub const PLANS: Map<String, Plan> = Map::new("plans");
+
+pub const SUBSCRIPTIONS: Map<Addr, Subscription> = Map::new("subscriptions");
+
+/// Funds deposited by sponsors to pay for messages, keyed by sponsor and denom.
+pub const SPONSOR_DEPOSITS: Map<(Addr, String), Uint128> = Map::new("sponsor_deposits");
+
+/// Allowances keyed by grantee and sponsor.
+pub const ALLOWANCES: Map<(Addr, Addr), Allowance> = Map::new("allowances");
+
+pub const SPONSOR_CODES: Map<String, SponsorCode> = Map::new("sponsor_codes");
+
+/// User ids listed for sale on the marketplace.
+pub const LISTINGS: Map<String, Listing> = Map::new("listings");
+
+/// Running auctions for premium user ids. Bids stay in the controller until outbid or settled.
+pub const AUCTIONS: Map<String, Auction> = Map::new("auctions");
+
+/// User ids of the running auctions each address is the highest bidder of.
+pub const BIDDER_AUCTIONS: Map<(Addr, String), Empty> = Map::new("bidder_auctions");
+
+/// Import entries rejected by the controller, reported by the reply along with the results of the
+/// profiles contract.
+pub const IMPORT_FAILURES: Item<Vec<ImportFailure>> = Item::new("import_failures");
+
+/// The auction being settled, read by the reply that refunds the winner if its registration fails.
+pub const SETTLING_AUCTION: Item<(String, Auction)> = Item::new("settling_auction");
+
+pub const RECOVERY_SETUPS: Map<Addr, RecoverySetup> = Map::new("recovery_setups");
+
+/// Pending recoveries keyed by the address being recovered.
+pub const RECOVERY_REQUESTS: Map<Addr, RecoveryRequest> = Map::new("recovery_requests");
+
 /// This is saved after handling a reply in instantiation. It's the address of the profiles contract.
 pub const PROFILES_ADDRESS: Item<Addr> = Item::new("profiles_address");
 
@@ -0,0 +1,137 @@
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::{
+    coin, coins, Addr, BankMsg, CosmosMsg, Env, Reply, ReplyOn, SubMsgResult, Uint128,
+};
+
+use super::{exec, instantiate_msg, mock_profiles, profile, setup, TestDeps};
+use crate::contract::{execute, reply};
+use crate::error::ContractError;
+use crate::msg::ExecuteMsg;
+use crate::state::{
+    AuctionConfig, AUCTIONS, BIDDER_AUCTIONS, COLLECTED_FEES, LIFETIME_FEES, SETTLING_AUCTION,
+};
+
+fn auction_setup() -> TestDeps {
+    let mut msg = instantiate_msg();
+    msg.user_id_auction = Some(AuctionConfig {
+        below_len: 4,
+        duration: 100,
+        extension: 10,
+        min_bid: coin(10, "ucore"),
+    });
+    setup(msg)
+}
+
+fn bid(
+    deps: &mut TestDeps,
+    bidder: &str,
+    amount: u128,
+    pubkey: Option<&str>,
+) -> Result<(), ContractError> {
+    exec(
+        deps,
+        bidder,
+        &coins(amount, "ucore"),
+        ExecuteMsg::BidUserId {
+            user_id: "ace".to_string(),
+            pubkey: pubkey.map(str::to_string),
+        },
+    )
+    .map(|_| ())
+}
+
+fn after_auction() -> Env {
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(100);
+    env
+}
+
+fn settle(deps: &mut TestDeps) -> cosmwasm_std::Response {
+    let msg = ExecuteMsg::SettleAuction {
+        user_id: "ace".to_string(),
+    };
+    execute(
+        deps.as_mut(),
+        after_auction(),
+        mock_info("anyone", &[]),
+        msg,
+    )
+    .unwrap()
+}
+
+fn collected(deps: &TestDeps) -> Option<Uint128> {
+    COLLECTED_FEES
+        .may_load(&deps.storage, "ucore".to_string())
+        .unwrap()
+}
+
+#[test]
+fn bids_that_couldnt_be_registered_are_rejected() {
+    let mut deps = auction_setup();
+    mock_profiles(&mut deps, vec![profile("alice", "alice", &[])]);
+
+    // Without a profile, the winner's profile is created with the bid pubkey.
+    bid(&mut deps, "bob", 10, None).unwrap_err();
+    bid(&mut deps, "bob", 10, Some("bob-pubkey")).unwrap();
+    // Holders bid with the profile they have.
+    bid(&mut deps, "alice", 11, None).unwrap();
+    assert_eq!(
+        AUCTIONS
+            .load(&deps.storage, "ace".to_string())
+            .unwrap()
+            .pubkey,
+        None
+    );
+}
+
+#[test]
+fn settling_registers_the_winner_and_refunds_it_if_that_fails() {
+    let mut deps = auction_setup();
+    mock_profiles(&mut deps, vec![]);
+    bid(&mut deps, "bob", 10, Some("bob-pubkey")).unwrap();
+    assert!(BIDDER_AUCTIONS.has(&deps.storage, (Addr::unchecked("bob"), "ace".to_string())));
+
+    let res = settle(&mut deps);
+    assert_eq!(res.messages.len(), 1);
+    assert_eq!(res.messages[0].reply_on, ReplyOn::Error);
+    assert_eq!(collected(&deps), Some(Uint128::new(10)));
+    assert!(!AUCTIONS.has(&deps.storage, "ace".to_string()));
+    assert!(!BIDDER_AUCTIONS.has(&deps.storage, (Addr::unchecked("bob"), "ace".to_string())));
+
+    let failed = Reply {
+        id: res.messages[0].id,
+        result: SubMsgResult::Err("User ID already registered".to_string()),
+    };
+    let res = reply(deps.as_mut(), after_auction(), failed).unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "bob".to_string(),
+            amount: coins(10, "ucore"),
+        })
+    );
+    assert_eq!(collected(&deps), None);
+    assert!(LIFETIME_FEES
+        .may_load(&deps.storage, "ucore".to_string())
+        .unwrap()
+        .is_none());
+    assert!(SETTLING_AUCTION.may_load(&deps.storage).unwrap().is_none());
+}
+
+#[test]
+fn settling_refunds_a_winner_that_deleted_its_profile() {
+    let mut deps = auction_setup();
+    mock_profiles(&mut deps, vec![profile("alice", "alice", &[])]);
+    bid(&mut deps, "alice", 10, None).unwrap();
+    mock_profiles(&mut deps, vec![]);
+
+    let res = settle(&mut deps);
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "alice".to_string(),
+            amount: coins(10, "ucore"),
+        })
+    );
+    assert_eq!(collected(&deps), None);
+}
@@ -0,0 +1,156 @@
+use cosmwasm_std::testing::{mock_env, mock_info, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    to_json_binary, Addr, ContractInfoResponse, ContractResult, CosmosMsg, SystemError,
+    SystemResult, WasmMsg, WasmQuery,
+};
+use cw2::ContractVersion;
+use cw_ownable::Ownership;
+use utils::msg::ProfilesExecuteMsg;
+
+use super::{exec, instantiate_msg, setup, TestDeps, OWNER, PROFILES};
+use crate::contract::execute;
+use crate::error::ContractError;
+use crate::msg::ExecuteMsg;
+use crate::state::{ChildContract, PROFILES_ADDRESS};
+
+const REPLACEMENT: &str = "profiles2";
+
+/// The current profiles contract at 0.2.0 and a replacement with the given version, wasm admin
+/// and ownership.
+fn mock_children(
+    deps: &mut TestDeps,
+    version: &'static str,
+    admin: &'static str,
+    ownership: Ownership<Addr>,
+) {
+    deps.querier.update_wasm(move |query| {
+        let response = match query {
+            WasmQuery::Raw { contract_addr, key } if key.as_slice() == b"contract_info" => {
+                let version = if contract_addr == PROFILES {
+                    "0.2.0"
+                } else {
+                    version
+                };
+                to_json_binary(&ContractVersion {
+                    contract: "profiles".to_string(),
+                    version: version.to_string(),
+                })
+            }
+            WasmQuery::Raw { key, .. } if key.as_slice() == b"ownership" => {
+                to_json_binary(&ownership)
+            }
+            WasmQuery::ContractInfo { .. } => {
+                let mut info = ContractInfoResponse::default();
+                info.admin = Some(admin.to_string());
+                to_json_binary(&info)
+            }
+            _ => return SystemResult::Err(SystemError::Unknown {}),
+        };
+        SystemResult::Ok(ContractResult::Ok(response.unwrap()))
+    });
+}
+
+fn owned_by(owner: &str, pending_owner: Option<&str>) -> Ownership<Addr> {
+    Ownership {
+        owner: Some(Addr::unchecked(owner)),
+        pending_owner: pending_owner.map(Addr::unchecked),
+        pending_expiry: None,
+    }
+}
+
+fn set_child() -> ExecuteMsg {
+    ExecuteMsg::SetChildContract {
+        contract: ChildContract::Profiles,
+        address: Addr::unchecked(REPLACEMENT),
+    }
+}
+
+#[test]
+fn replacement_waits_for_the_timelock_and_accepts_pending_ownership() {
+    let mut msg = instantiate_msg();
+    msg.timelock_delay = 100;
+    let mut deps = setup(msg);
+    let ownership = owned_by("deployer", Some(MOCK_CONTRACT_ADDR));
+    mock_children(&mut deps, "0.2.1", MOCK_CONTRACT_ADDR, ownership);
+
+    exec(&mut deps, OWNER, &[], set_child()).unwrap();
+    assert_eq!(PROFILES_ADDRESS.load(&deps.storage).unwrap(), PROFILES);
+    let err = exec(
+        &mut deps,
+        "anyone",
+        &[],
+        ExecuteMsg::ExecuteProposal { id: 0 },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::ProposalNotReady { .. }));
+
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(100);
+    let info = mock_info("anyone", &[]);
+    let res = execute(
+        deps.as_mut(),
+        env,
+        info,
+        ExecuteMsg::ExecuteProposal { id: 0 },
+    )
+    .unwrap();
+    assert_eq!(PROFILES_ADDRESS.load(&deps.storage).unwrap(), REPLACEMENT);
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: REPLACEMENT.to_string(),
+            msg: to_json_binary(&ProfilesExecuteMsg::UpdateOwnership(
+                cw_ownable::Action::AcceptOwnership
+            ))
+            .unwrap(),
+            funds: vec![],
+        })
+    );
+}
+
+#[test]
+fn owned_replacement_needs_no_acceptance() {
+    let mut deps = setup(instantiate_msg());
+    let ownership = owned_by(MOCK_CONTRACT_ADDR, None);
+    mock_children(&mut deps, "0.2.0", MOCK_CONTRACT_ADDR, ownership);
+
+    exec(&mut deps, OWNER, &[], set_child()).unwrap();
+    let res = exec(
+        &mut deps,
+        "anyone",
+        &[],
+        ExecuteMsg::ExecuteProposal { id: 0 },
+    )
+    .unwrap();
+    assert!(res.messages.is_empty());
+    assert_eq!(PROFILES_ADDRESS.load(&deps.storage).unwrap(), REPLACEMENT);
+}
+
+#[test]
+fn unsuitable_replacements_are_rejected() {
+    let mut deps = setup(instantiate_msg());
+
+    let ownership = owned_by(MOCK_CONTRACT_ADDR, None);
+    mock_children(&mut deps, "0.1.9", MOCK_CONTRACT_ADDR, ownership.clone());
+    let err = exec(&mut deps, OWNER, &[], set_child()).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::ChildVersionTooLow {
+            current: "0.2.0".to_string(),
+            found: "0.1.9".to_string()
+        }
+    );
+
+    mock_children(&mut deps, "0.3.0", "deployer", ownership);
+    let err = exec(&mut deps, OWNER, &[], set_child()).unwrap_err();
+    assert_eq!(err, ContractError::ChildNotAdministered {});
+
+    mock_children(
+        &mut deps,
+        "0.3.0",
+        MOCK_CONTRACT_ADDR,
+        owned_by("deployer", None),
+    );
+    let err = exec(&mut deps, OWNER, &[], set_child()).unwrap_err();
+    assert_eq!(err, ContractError::ChildNotOwned {});
+}
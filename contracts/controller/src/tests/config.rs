@@ -0,0 +1,175 @@
+use cosmwasm_std::testing::mock_env;
+use cosmwasm_std::{coin, from_json, Addr, Decimal, Uint128};
+
+use super::{exec, instantiate_msg, setup, TestDeps, OWNER};
+use crate::contract::query;
+use crate::error::ContractError;
+use crate::msg::{ConfigHistoryResponse, ExecuteMsg, QueryMsg};
+use crate::state::{
+    AuctionConfig, ConfigFieldChange, ConfigUpdate, LengthPrice, MessagePrice, CONFIG, PROPOSALS,
+};
+
+fn no_update() -> ConfigUpdate {
+    ConfigUpdate {
+        message_max_len: None,
+        message_prices: None,
+        profile_cost: None,
+        trade_fee: None,
+        referral_share: None,
+        length_prices: None,
+        auction: None,
+        registration_term: None,
+        change_fee: None,
+        recovery_delay: None,
+        key_backup_max_len: None,
+        timelock_delay: None,
+    }
+}
+
+fn price(denom: &str) -> MessagePrice {
+    MessagePrice {
+        denom: denom.to_string(),
+        base: Uint128::new(1),
+        per_byte: Uint128::zero(),
+    }
+}
+
+#[test]
+fn invalid_config_updates_are_rejected_before_being_proposed() {
+    let mut deps = setup(instantiate_msg());
+    let invalid = |field: &str| ContractError::InvalidConfig {
+        field: field.to_string(),
+    };
+    let cases = [
+        (
+            ConfigUpdate {
+                message_max_len: Some(0),
+                ..no_update()
+            },
+            invalid("message_max_len"),
+        ),
+        (
+            ConfigUpdate {
+                key_backup_max_len: Some(0),
+                ..no_update()
+            },
+            invalid("key_backup_max_len"),
+        ),
+        (
+            ConfigUpdate {
+                trade_fee: Some(Decimal::percent(101)),
+                ..no_update()
+            },
+            ContractError::InvalidTradeFee {},
+        ),
+        (
+            ConfigUpdate {
+                referral_share: Some(Decimal::percent(101)),
+                ..no_update()
+            },
+            invalid("referral_share"),
+        ),
+        (
+            ConfigUpdate {
+                message_prices: Some(vec![price("ucore"), price("ucore")]),
+                ..no_update()
+            },
+            invalid("message_prices"),
+        ),
+        (
+            ConfigUpdate {
+                length_prices: Some(vec![LengthPrice {
+                    max_len: 0,
+                    cost: coin(10, "ucore"),
+                }]),
+                ..no_update()
+            },
+            invalid("length_prices"),
+        ),
+        (
+            ConfigUpdate {
+                auction: Some(AuctionConfig {
+                    below_len: 4,
+                    duration: 0,
+                    extension: 10,
+                    min_bid: coin(10, "ucore"),
+                }),
+                ..no_update()
+            },
+            invalid("auction"),
+        ),
+        (
+            ConfigUpdate {
+                profile_cost: Some(coin(10, "1core")),
+                ..no_update()
+            },
+            ContractError::InvalidDenom {
+                denom: "1core".to_string(),
+            },
+        ),
+        (no_update(), ContractError::EmptyConfigUpdate {}),
+    ];
+
+    for (update, expected) in cases {
+        let msg = ExecuteMsg::UpdateConfig(Box::new(update));
+        let err = exec(&mut deps, OWNER, &[], msg).unwrap_err();
+        assert_eq!(err, expected);
+    }
+    assert!(PROPOSALS.is_empty(&deps.storage));
+}
+
+#[test]
+fn applied_config_updates_are_recorded_in_the_history() {
+    let mut msg = instantiate_msg();
+    msg.create_profile_cost = Some(coin(100, "ucore"));
+    let mut deps = setup(msg);
+    let update = ConfigUpdate {
+        message_max_len: Some(50),
+        profile_cost: Some(coin(0, "ucore")),
+        ..no_update()
+    };
+    exec(
+        &mut deps,
+        OWNER,
+        &[],
+        ExecuteMsg::UpdateConfig(Box::new(update)),
+    )
+    .unwrap();
+
+    // Nothing is recorded until the proposal is executed.
+    let history = |deps: &TestDeps| -> ConfigHistoryResponse {
+        let msg = QueryMsg::ConfigHistory {
+            start_after: None,
+            limit: None,
+        };
+        from_json(query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap()
+    };
+    assert!(history(&deps).entries.is_empty());
+    exec(
+        &mut deps,
+        "anyone",
+        &[],
+        ExecuteMsg::ExecuteProposal { id: 0 },
+    )
+    .unwrap();
+
+    let config = CONFIG.load(&deps.storage).unwrap();
+    assert_eq!(config.message_max_len, 50);
+    assert_eq!(config.profile_cost, None);
+    let entries = history(&deps).entries;
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].id, 0);
+    assert_eq!(entries[0].change.sender, Addr::unchecked(OWNER));
+    assert_eq!(entries[0].change.time, mock_env().block.time);
+    let field = |field: &str, value: &str| ConfigFieldChange {
+        field: field.to_string(),
+        value: value.to_string(),
+    };
+    assert_eq!(
+        entries[0].change.changes,
+        vec![
+            field("message_max_len", "50"),
+            field("profile_cost", "none")
+        ]
+    );
+}
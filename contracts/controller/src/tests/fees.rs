@@ -0,0 +1,117 @@
+use cosmwasm_std::testing::{mock_env, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{coin, coins, Addr, BankMsg, CosmosMsg, Uint128};
+
+use super::{exec, instantiate_msg, setup, OWNER};
+use crate::error::ContractError;
+use crate::msg::ExecuteMsg;
+use crate::state::{Auction, FeeShare, AUCTIONS, COLLECTED_FEES, FEE_SHARES, SPONSOR_DEPOSITS};
+
+fn share(recipient: &str, weight: u64) -> FeeShare {
+    FeeShare {
+        recipient: Addr::unchecked(recipient),
+        weight,
+    }
+}
+
+#[test]
+fn distributing_splits_the_collected_fees_by_weight() {
+    let mut deps = setup(instantiate_msg());
+    FEE_SHARES
+        .save(&mut deps.storage, &vec![share("alice", 1), share("bob", 3)])
+        .unwrap();
+    COLLECTED_FEES
+        .save(&mut deps.storage, "ucore".to_string(), &Uint128::new(101))
+        .unwrap();
+
+    let res = exec(&mut deps, OWNER, &[], ExecuteMsg::DistributeFees {}).unwrap();
+    let sends: Vec<CosmosMsg> = res.messages.into_iter().map(|msg| msg.msg).collect();
+    assert_eq!(
+        sends,
+        vec![
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "alice".to_string(),
+                amount: coins(26, "ucore"),
+            }),
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "bob".to_string(),
+                amount: coins(75, "ucore"),
+            }),
+        ]
+    );
+    assert!(!COLLECTED_FEES.has(&deps.storage, "ucore".to_string()));
+}
+
+#[test]
+fn retrieving_pays_the_collected_fees_to_the_owner() {
+    let mut deps = setup(instantiate_msg());
+    deps.querier
+        .update_balance(MOCK_CONTRACT_ADDR, coins(1000, "ucore"));
+    COLLECTED_FEES
+        .save(&mut deps.storage, "ucore".to_string(), &Uint128::new(101))
+        .unwrap();
+    let retrieve = ExecuteMsg::RetrieveFees { receiver: None };
+
+    let err = exec(&mut deps, "alice", &[], retrieve.clone()).unwrap_err();
+    assert!(matches!(err, ContractError::Ownership(_)));
+    let res = exec(&mut deps, OWNER, &[], retrieve.clone()).unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: OWNER.to_string(),
+            amount: coins(101, "ucore"),
+        })
+    );
+    assert!(!COLLECTED_FEES.has(&deps.storage, "ucore".to_string()));
+    let err = exec(&mut deps, OWNER, &[], retrieve).unwrap_err();
+    assert_eq!(err, ContractError::NoFeesToDistribute {});
+}
+
+#[test]
+fn fee_share_weights_must_add_up_within_a_u64() {
+    let mut deps = setup(instantiate_msg());
+    let msg = ExecuteMsg::SetFeeShares {
+        shares: vec![share("alice", u64::MAX), share("bob", 1)],
+    };
+
+    let err = exec(&mut deps, OWNER, &[], msg).unwrap_err();
+    assert_eq!(err, ContractError::InvalidFeeShares {});
+}
+
+#[test]
+fn sweeping_leaves_the_funds_held_for_others() {
+    let mut deps = setup(instantiate_msg());
+    deps.querier.update_balance(
+        MOCK_CONTRACT_ADDR,
+        vec![coin(1000, "ucore"), coin(5, "uother")],
+    );
+    COLLECTED_FEES
+        .save(&mut deps.storage, "ucore".to_string(), &Uint128::new(100))
+        .unwrap();
+    let auction = Auction {
+        highest_bidder: Addr::unchecked("bob"),
+        highest_bid: coin(50, "ucore"),
+        pubkey: None,
+        end: mock_env().block.time,
+    };
+    AUCTIONS
+        .save(&mut deps.storage, "ace".to_string(), &auction)
+        .unwrap();
+    let key = (Addr::unchecked("sponsor"), "ucore".to_string());
+    SPONSOR_DEPOSITS
+        .save(&mut deps.storage, key, &Uint128::new(200))
+        .unwrap();
+    let sweep = ExecuteMsg::SweepFunds {
+        recipient: Addr::unchecked("treasury"),
+    };
+
+    let err = exec(&mut deps, "alice", &[], sweep.clone()).unwrap_err();
+    assert!(matches!(err, ContractError::MissingRole { .. }));
+    let res = exec(&mut deps, OWNER, &[], sweep).unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "treasury".to_string(),
+            amount: vec![coin(650, "ucore"), coin(5, "uother")],
+        })
+    );
+}
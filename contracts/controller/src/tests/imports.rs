@@ -0,0 +1,105 @@
+use cosmwasm_std::testing::mock_env;
+use cosmwasm_std::{
+    coin, from_json, to_json_vec, Addr, Binary, Reply, SubMsgResponse, SubMsgResult,
+};
+use utils::msg::{ImportEntry, ImportFailure, ImportProfilesResponse};
+
+use super::{exec, instantiate_msg, setup, OWNER};
+use crate::contract::reply;
+use crate::error::ContractError;
+use crate::msg::ExecuteMsg;
+use crate::state::{AuctionConfig, Listing, IMPORT_FAILURES, LISTINGS};
+
+fn entry(address: &str, user_id: &str) -> ImportEntry {
+    ImportEntry {
+        address: Addr::unchecked(address),
+        user_id: user_id.to_string(),
+        pubkey: format!("{}-pubkey", address),
+    }
+}
+
+fn failure(address: &str, user_id: &str, error: &str) -> ImportFailure {
+    ImportFailure {
+        address: Addr::unchecked(address),
+        user_id: user_id.to_string(),
+        error: error.to_string(),
+    }
+}
+
+/// The profiles contract response data wrapped in a `MsgExecuteContractResponse`.
+fn execute_response(data: &ImportProfilesResponse) -> Binary {
+    let data = to_json_vec(data).unwrap();
+    let mut encoded = vec![0x0a, data.len() as u8];
+    encoded.extend(data);
+    Binary::from(encoded)
+}
+
+#[test]
+fn auctioned_user_ids_are_reported_without_failing_the_import() {
+    let mut msg = instantiate_msg();
+    msg.user_id_auction = Some(AuctionConfig {
+        below_len: 4,
+        duration: 100,
+        extension: 10,
+        min_bid: coin(10, "ucore"),
+    });
+    let mut deps = setup(msg);
+    let import = |entries| ExecuteMsg::ImportProfiles { entries };
+    let error = ContractError::UserIdRequiresAuction { below_len: 4 }.to_string();
+    let auctioned = failure("alice", "ace", &error);
+
+    let res = exec(&mut deps, OWNER, &[], import(vec![entry("alice", "ace")])).unwrap();
+    assert!(res.messages.is_empty());
+    let data: ImportProfilesResponse = from_json(res.data.unwrap()).unwrap();
+    assert_eq!(data.failures, vec![auctioned.clone()]);
+
+    let entries = vec![entry("alice", "ace"), entry("bob", "bobby")];
+    let res = exec(&mut deps, OWNER, &[], import(entries)).unwrap();
+    assert_eq!(res.messages.len(), 1);
+    assert_eq!(
+        IMPORT_FAILURES.load(&deps.storage).unwrap(),
+        vec![auctioned]
+    );
+}
+
+#[test]
+fn only_imported_user_ids_lose_their_listings() {
+    let mut deps = setup(instantiate_msg());
+    let listing = Listing {
+        seller: Addr::unchecked("seller"),
+        price: coin(100, "ucore"),
+    };
+    for user_id in ["bobby", "carol"] {
+        LISTINGS
+            .save(&mut deps.storage, user_id.to_string(), &listing)
+            .unwrap();
+    }
+    let entries = vec![entry("bob", "bobby"), entry("carol", "carol")];
+    let res = exec(
+        &mut deps,
+        OWNER,
+        &[],
+        ExecuteMsg::ImportProfiles { entries },
+    )
+    .unwrap();
+
+    let taken = failure("bob", "bobby", "User ID already registered");
+    let data = ImportProfilesResponse {
+        imported: vec!["carol".to_string()],
+        failures: vec![taken.clone()],
+    };
+    let imported = Reply {
+        id: res.messages[0].id,
+        result: SubMsgResult::Ok(SubMsgResponse {
+            events: vec![],
+            data: Some(execute_response(&data)),
+        }),
+    };
+    let res = reply(deps.as_mut(), mock_env(), imported).unwrap();
+
+    assert!(LISTINGS.has(&deps.storage, "bobby".to_string()));
+    assert!(!LISTINGS.has(&deps.storage, "carol".to_string()));
+    let data: ImportProfilesResponse = from_json(res.data.unwrap()).unwrap();
+    assert_eq!(data.failures, vec![taken]);
+    assert!(IMPORT_FAILURES.may_load(&deps.storage).unwrap().is_none());
+}
@@ -0,0 +1,25 @@
+use cosmwasm_std::{Binary, CosmosMsg, WasmMsg};
+
+use super::{exec, instantiate_msg, setup, PROFILES};
+use crate::error::ContractError;
+use crate::msg::ExecuteMsg;
+
+#[test]
+fn key_backups_longer_than_the_limit_are_rejected() {
+    let mut deps = setup(instantiate_msg());
+    let backup = |len: usize| ExecuteMsg::SetKeyBackup {
+        data: Binary::from(vec![1u8; len]),
+        expected_version: 0,
+    };
+
+    let err = exec(&mut deps, "alice", &[], backup(1001)).unwrap_err();
+    assert_eq!(err, ContractError::KeyBackupTooLong { max_len: 1000 });
+
+    let res = exec(&mut deps, "alice", &[], backup(1000)).unwrap();
+    match &res.messages[0].msg {
+        CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, .. }) => {
+            assert_eq!(contract_addr, PROFILES)
+        }
+        msg => panic!("unexpected message {:?}", msg),
+    }
+}
@@ -0,0 +1,221 @@
+use cosmwasm_std::{coin, coins, testing::mock_env, to_json_binary, BankMsg, CosmosMsg, Decimal};
+use cosmwasm_std::{Addr, Uint128, WasmMsg};
+use utils::msg::ProfilesExecuteMsg;
+
+use super::{exec, instantiate_msg, mock_profiles, profile, setup, TestDeps, PROFILES};
+use crate::error::ContractError;
+use crate::msg::ExecuteMsg;
+use crate::state::{
+    RecoveryApproval, RecoveryRequest, COLLECTED_FEES, LISTINGS, RECOVERY_REQUESTS,
+};
+
+fn list(deps: &mut TestDeps, seller: &str, user_id: &str) -> Result<(), ContractError> {
+    exec(
+        deps,
+        seller,
+        &[],
+        ExecuteMsg::ListUserId {
+            user_id: user_id.to_string(),
+            price: coin(100, "ucore"),
+        },
+    )
+    .map(|_| ())
+}
+
+#[test]
+fn buying_pays_the_seller_and_keeps_the_trade_fee() {
+    let mut msg = instantiate_msg();
+    msg.user_id_trade_fee = Decimal::percent(10);
+    let mut deps = setup(msg);
+    mock_profiles(&mut deps, vec![profile("alice", "alice", &["ally"])]);
+    list(&mut deps, "alice", "ally").unwrap();
+
+    let res = exec(
+        &mut deps,
+        "bob",
+        &coins(100, "ucore"),
+        ExecuteMsg::BuyUserId {
+            user_id: "ally".to_string(),
+            pubkey: Some("bob-pubkey".to_string()),
+        },
+    )
+    .unwrap();
+
+    let move_msg = ProfilesExecuteMsg::MoveUserId {
+        user_id: "ally".to_string(),
+        from: Addr::unchecked("alice"),
+        to: Addr::unchecked("bob"),
+        pubkey: Some("bob-pubkey".to_string()),
+    };
+    assert_eq!(
+        res.messages
+            .into_iter()
+            .map(|msg| msg.msg)
+            .collect::<Vec<CosmosMsg>>(),
+        vec![
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: PROFILES.to_string(),
+                msg: to_json_binary(&move_msg).unwrap(),
+                funds: vec![],
+            }),
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "alice".to_string(),
+                amount: coins(90, "ucore"),
+            }),
+        ]
+    );
+    assert_eq!(
+        COLLECTED_FEES
+            .load(&deps.storage, "ucore".to_string())
+            .unwrap(),
+        Uint128::new(10)
+    );
+    assert!(!LISTINGS.has(&deps.storage, "ally".to_string()));
+}
+
+#[test]
+fn buying_requires_the_exact_price() {
+    let mut deps = setup(instantiate_msg());
+    mock_profiles(&mut deps, vec![profile("alice", "alice", &["ally"])]);
+    list(&mut deps, "alice", "ally").unwrap();
+
+    let err = exec(
+        &mut deps,
+        "bob",
+        &coins(99, "ucore"),
+        ExecuteMsg::BuyUserId {
+            user_id: "ally".to_string(),
+            pubkey: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidFunds {
+            funds_required: coin(100, "ucore"),
+        }
+    );
+}
+
+#[test]
+fn the_only_user_id_of_a_profile_cant_be_listed() {
+    let mut deps = setup(instantiate_msg());
+    mock_profiles(&mut deps, vec![profile("alice", "alice", &[])]);
+
+    assert_eq!(
+        list(&mut deps, "alice", "alice").unwrap_err(),
+        ContractError::SoleUserId {}
+    );
+}
+
+#[test]
+fn listings_are_removed_when_the_user_id_leaves_the_seller() {
+    let mut deps = setup(instantiate_msg());
+    mock_profiles(
+        &mut deps,
+        vec![profile("alice", "alice", &["ally", "al", "ali"])],
+    );
+    for user_id in ["alice", "ally", "al", "ali"] {
+        list(&mut deps, "alice", user_id).unwrap();
+    }
+
+    exec(
+        &mut deps,
+        "bob",
+        &[],
+        ExecuteMsg::AcceptUserId {
+            user_id: "ally".to_string(),
+            pubkey: None,
+        },
+    )
+    .unwrap();
+    assert!(!LISTINGS.has(&deps.storage, "ally".to_string()));
+
+    exec(
+        &mut deps,
+        "alice",
+        &[],
+        ExecuteMsg::RemoveAlias {
+            alias: "al".to_string(),
+        },
+    )
+    .unwrap();
+    assert!(!LISTINGS.has(&deps.storage, "al".to_string()));
+
+    exec(
+        &mut deps,
+        "alice",
+        &[],
+        ExecuteMsg::DeleteProfile {
+            refund_funds: false,
+        },
+    )
+    .unwrap();
+    assert!(LISTINGS.is_empty(&deps.storage));
+}
+
+#[test]
+fn renaming_removes_the_listing_of_the_replaced_user_id() {
+    let mut deps = setup(instantiate_msg());
+    mock_profiles(&mut deps, vec![profile("alice", "alice", &["ally"])]);
+    list(&mut deps, "alice", "alice").unwrap();
+    list(&mut deps, "alice", "ally").unwrap();
+
+    // Swapping with an alias keeps both user ids on the profile.
+    exec(
+        &mut deps,
+        "alice",
+        &[],
+        ExecuteMsg::ChangeUserId {
+            user_id: "ally".to_string(),
+        },
+    )
+    .unwrap();
+    assert!(LISTINGS.has(&deps.storage, "alice".to_string()));
+
+    exec(
+        &mut deps,
+        "alice",
+        &[],
+        ExecuteMsg::ChangeUserId {
+            user_id: "alicia".to_string(),
+        },
+    )
+    .unwrap();
+    assert!(!LISTINGS.has(&deps.storage, "alice".to_string()));
+    assert!(LISTINGS.has(&deps.storage, "ally".to_string()));
+}
+
+#[test]
+fn recovery_moves_listings_to_the_new_address() {
+    let mut deps = setup(instantiate_msg());
+    mock_profiles(&mut deps, vec![profile("alice", "alice", &["ally"])]);
+    list(&mut deps, "alice", "ally").unwrap();
+    RECOVERY_REQUESTS
+        .save(
+            &mut deps.storage,
+            Addr::unchecked("alice"),
+            &RecoveryRequest {
+                approvals: vec![RecoveryApproval {
+                    guardian: Addr::unchecked("guardian"),
+                    new_address: Addr::unchecked("alice2"),
+                }],
+                new_address: Some(Addr::unchecked("alice2")),
+                executable_at: Some(mock_env().block.time),
+            },
+        )
+        .unwrap();
+
+    exec(
+        &mut deps,
+        "anyone",
+        &[],
+        ExecuteMsg::ExecuteRecovery {
+            address: Addr::unchecked("alice"),
+        },
+    )
+    .unwrap();
+
+    let listing = LISTINGS.load(&deps.storage, "ally".to_string()).unwrap();
+    assert_eq!(listing.seller, Addr::unchecked("alice2"));
+}
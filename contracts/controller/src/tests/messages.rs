@@ -0,0 +1,72 @@
+use cosmwasm_std::{coin, coins, Addr, Binary, CosmosMsg, Uint128, WasmMsg};
+
+use super::{exec, instantiate_msg, setup, TestDeps, MESSAGES};
+use crate::error::ContractError;
+use crate::msg::ExecuteMsg;
+use crate::state::{MessagePrice, COLLECTED_FEES};
+
+/// Ten bytes cost 20ucore or 25uother.
+fn priced_setup() -> TestDeps {
+    let mut msg = instantiate_msg();
+    msg.send_message_prices = vec![
+        MessagePrice {
+            denom: "ucore".to_string(),
+            base: Uint128::new(10),
+            per_byte: Uint128::new(1),
+        },
+        MessagePrice {
+            denom: "uother".to_string(),
+            base: Uint128::new(5),
+            per_byte: Uint128::new(2),
+        },
+    ];
+    setup(msg)
+}
+
+fn send() -> ExecuteMsg {
+    ExecuteMsg::SendMessage {
+        content: Binary::from(b"0123456789".as_slice()),
+        dest_address: Some(Addr::unchecked("bob")),
+        dest_id: None,
+        dest_id_hash: None,
+    }
+}
+
+fn collected(deps: &TestDeps, denom: &str) -> Option<Uint128> {
+    COLLECTED_FEES
+        .may_load(&deps.storage, denom.to_string())
+        .unwrap()
+}
+
+#[test]
+fn the_first_denom_with_enough_funds_pays_and_the_rest_is_forwarded() {
+    let mut deps = priced_setup();
+
+    let funds = vec![coin(15, "ucore"), coin(30, "uother")];
+    let res = exec(&mut deps, "alice", &funds, send()).unwrap();
+    let CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr,
+        funds,
+        ..
+    }) = &res.messages[0].msg
+    else {
+        panic!("expected a wasm execute");
+    };
+    assert_eq!(contract_addr, MESSAGES);
+    assert_eq!(funds, &vec![coin(15, "ucore"), coin(5, "uother")]);
+    assert_eq!(collected(&deps, "uother"), Some(Uint128::new(25)));
+    assert_eq!(collected(&deps, "ucore"), None);
+
+    exec(&mut deps, "alice", &coins(20, "ucore"), send()).unwrap();
+    assert_eq!(collected(&deps, "ucore"), Some(Uint128::new(20)));
+}
+
+#[test]
+fn messages_without_enough_funds_are_rejected() {
+    let mut deps = priced_setup();
+
+    let funds = vec![coin(19, "ucore"), coin(24, "uother")];
+    let err = exec(&mut deps, "alice", &funds, send()).unwrap_err();
+    assert_eq!(err, ContractError::NotEnoughFundsForMessage {});
+    assert_eq!(collected(&deps, "ucore"), None);
+}
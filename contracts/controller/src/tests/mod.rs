@@ -0,0 +1,140 @@
+mod auctions;
+mod children;
+mod config;
+mod fees;
+mod imports;
+mod keys;
+mod marketplace;
+mod messages;
+mod pause;
+mod payments;
+mod proposals;
+mod recovery;
+mod referrals;
+mod roles;
+mod sponsors;
+mod subscriptions;
+
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+};
+use cosmwasm_std::{
+    from_json, to_json_binary, Addr, Coin, ContractResult, Decimal, Empty, OwnedDeps, Response,
+    SystemError, SystemResult, WasmQuery,
+};
+use utils::query::{ProfileInfo, ProfilesQueryMsg};
+
+use crate::contract::{execute, instantiate};
+use crate::error::ContractError;
+use crate::msg::{ExecuteMsg, InstantiateMsg};
+use crate::state::{MESSAGES_ADDRESS, PROFILES_ADDRESS};
+
+pub const OWNER: &str = "owner";
+pub const PROFILES: &str = "profiles";
+pub const MESSAGES: &str = "messages";
+
+pub type TestDeps = OwnedDeps<MockStorage, MockApi, MockQuerier>;
+
+/// Free messages and registrations, no timelock delay.
+pub fn instantiate_msg() -> InstantiateMsg {
+    InstantiateMsg {
+        code_id_profiles: 1,
+        code_id_messages: 2,
+        message_max_len: 1000,
+        message_query_default_limit: 10,
+        message_query_max_limit: 30,
+        user_id_cooldown: 0,
+        max_aliases: 5,
+        create_profile_cost: None,
+        send_message_prices: vec![],
+        user_id_trade_fee: Decimal::zero(),
+        referral_share: Decimal::zero(),
+        user_id_length_prices: vec![],
+        user_id_auction: None,
+        user_id_term: None,
+        user_id_grace_period: 0,
+        user_id_change_cooldown: 0,
+        user_id_change_fee: None,
+        user_id_redirect_period: 0,
+        recovery_delay: 0,
+        key_backup_max_len: 1000,
+        timelock_delay: 0,
+    }
+}
+
+/// Instantiates the controller as if the replies of both child instantiations were handled.
+pub fn setup(msg: InstantiateMsg) -> TestDeps {
+    let mut deps = mock_dependencies();
+    instantiate(deps.as_mut(), mock_env(), mock_info(OWNER, &[]), msg).unwrap();
+    PROFILES_ADDRESS
+        .save(&mut deps.storage, &Addr::unchecked(PROFILES))
+        .unwrap();
+    MESSAGES_ADDRESS
+        .save(&mut deps.storage, &Addr::unchecked(MESSAGES))
+        .unwrap();
+    deps
+}
+
+pub fn exec(
+    deps: &mut TestDeps,
+    sender: &str,
+    funds: &[Coin],
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    execute(deps.as_mut(), mock_env(), mock_info(sender, funds), msg)
+}
+
+pub fn profile(address: &str, user_id: &str, aliases: &[&str]) -> ProfileInfo {
+    ProfileInfo {
+        address: Addr::unchecked(address),
+        user_id: user_id.to_string(),
+        aliases: aliases.iter().map(|alias| alias.to_string()).collect(),
+        pubkey: format!("{}-pubkey", address),
+        active: true,
+        expires: None,
+        attestations: vec![],
+        linked_addresses: vec![],
+    }
+}
+
+/// Answers the `UserInfo` and `AddressInfo` queries sent to the profiles contract from `profiles`.
+/// `CheckRegistration` passes for user ids nobody holds, given a profile or a pubkey.
+pub fn mock_profiles(deps: &mut TestDeps, profiles: Vec<ProfileInfo>) {
+    deps.querier.update_wasm(move |query| {
+        let msg = match query {
+            WasmQuery::Smart { msg, .. } => from_json(msg).unwrap(),
+            _ => {
+                return SystemResult::Err(SystemError::Unknown {});
+            }
+        };
+        if let ProfilesQueryMsg::CheckRegistration {
+            address,
+            user_id,
+            pubkey,
+        } = msg
+        {
+            let taken = profiles
+                .iter()
+                .any(|p| p.user_id == user_id || p.aliases.contains(&user_id));
+            let has_profile = profiles.iter().any(|p| p.address == address);
+            return if taken || (!has_profile && pubkey.is_none()) {
+                SystemResult::Ok(ContractResult::Err("can't register".to_string()))
+            } else {
+                SystemResult::Ok(ContractResult::Ok(to_json_binary(&Empty {}).unwrap()))
+            };
+        }
+        let found = match msg {
+            ProfilesQueryMsg::UserInfo { user_id } => profiles
+                .iter()
+                .find(|p| p.user_id == user_id || p.aliases.contains(&user_id)),
+            ProfilesQueryMsg::AddressInfo { address } => {
+                profiles.iter().find(|p| p.address == address)
+            }
+            _ => None,
+        };
+        match found {
+            Some(profile) => SystemResult::Ok(ContractResult::Ok(to_json_binary(profile).unwrap())),
+            None => SystemResult::Ok(ContractResult::Err("not found".to_string())),
+        }
+    });
+}
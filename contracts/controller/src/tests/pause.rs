@@ -0,0 +1,67 @@
+use cosmwasm_std::{coins, to_json_binary, CosmosMsg, WasmMsg};
+use utils::msg::MessagesExecuteMsg;
+
+use super::{exec, instantiate_msg, setup, MESSAGES, OWNER};
+use crate::error::ContractError;
+use crate::msg::ExecuteMsg;
+use crate::state::Operation;
+
+#[test]
+fn pausing_sending_blocks_subscriptions_and_sponsoring() {
+    let mut deps = setup(instantiate_msg());
+    let pause = |paused| ExecuteMsg::SetPaused {
+        operation: Operation::Sending,
+        paused,
+    };
+    exec(&mut deps, OWNER, &[], pause(true)).unwrap();
+
+    let paused = ContractError::Paused {
+        operation: Operation::Sending.as_str().to_string(),
+    };
+    let blocked = [
+        (
+            ExecuteMsg::Subscribe {
+                plan: "basic".to_string(),
+            },
+            coins(10, "ucore"),
+        ),
+        (ExecuteMsg::DepositSponsorFunds {}, coins(10, "ucore")),
+        (
+            ExecuteMsg::WithdrawSponsorFunds {
+                amount: coins(10, "ucore"),
+            },
+            vec![],
+        ),
+    ];
+    for (msg, funds) in blocked {
+        assert_eq!(exec(&mut deps, "alice", &funds, msg).unwrap_err(), paused);
+    }
+
+    exec(&mut deps, OWNER, &[], pause(false)).unwrap();
+    exec(
+        &mut deps,
+        "alice",
+        &coins(10, "ucore"),
+        ExecuteMsg::DepositSponsorFunds {},
+    )
+    .unwrap();
+}
+
+#[test]
+fn pausing_claims_is_forwarded_to_the_messages_contract() {
+    let mut deps = setup(instantiate_msg());
+    let pause = ExecuteMsg::SetPaused {
+        operation: Operation::Claims,
+        paused: true,
+    };
+
+    let res = exec(&mut deps, OWNER, &[], pause).unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: MESSAGES.to_string(),
+            msg: to_json_binary(&MessagesExecuteMsg::SetClaimsPaused { paused: true }).unwrap(),
+            funds: vec![],
+        })
+    );
+}
@@ -0,0 +1,120 @@
+use cosmwasm_std::{coin, coins, to_json_binary, CosmosMsg, Uint128, WasmMsg};
+use cw_utils::PaymentError;
+use utils::msg::ProfilesExecuteMsg;
+
+use super::{exec, instantiate_msg, mock_profiles, profile, setup, PROFILES};
+use crate::error::ContractError;
+use crate::msg::ExecuteMsg;
+use crate::state::{LengthPrice, COLLECTED_FEES};
+
+#[test]
+fn free_operations_reject_funds() {
+    let mut deps = setup(instantiate_msg());
+    mock_profiles(&mut deps, vec![profile("alice", "alice", &["ally"])]);
+    let change = ExecuteMsg::ChangeUserId {
+        user_id: "ally".to_string(),
+    };
+
+    let err = exec(&mut deps, "alice", &coins(5, "ucore"), change.clone()).unwrap_err();
+    assert_eq!(err, ContractError::Payment(PaymentError::NonPayable {}));
+    exec(&mut deps, "alice", &[], change).unwrap();
+}
+
+#[test]
+fn registrations_pay_the_most_specific_length_price() {
+    let mut msg = instantiate_msg();
+    msg.create_profile_cost = Some(coin(10, "ucore"));
+    msg.user_id_length_prices = vec![
+        LengthPrice {
+            max_len: 5,
+            cost: coin(50, "ucore"),
+        },
+        LengthPrice {
+            max_len: 3,
+            cost: coin(100, "ucore"),
+        },
+    ];
+    let mut deps = setup(msg);
+    let create = |user_id: &str| ExecuteMsg::CreateProfile {
+        user_id: user_id.to_string(),
+        pubkey: "pubkey".to_string(),
+        sponsor_code: None,
+        referrer: None,
+    };
+
+    let err = exec(&mut deps, "alice", &coins(50, "ucore"), create("abc")).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidFunds {
+            funds_required: coin(100, "ucore")
+        }
+    );
+    exec(&mut deps, "alice", &coins(100, "ucore"), create("abc")).unwrap();
+    exec(&mut deps, "bob", &coins(50, "ucore"), create("abcd")).unwrap();
+    exec(&mut deps, "carol", &coins(10, "ucore"), create("abcdef")).unwrap();
+    let collected = COLLECTED_FEES
+        .load(&deps.storage, "ucore".to_string())
+        .unwrap();
+    assert_eq!(collected, Uint128::new(160));
+}
+
+#[test]
+fn renewals_pay_the_registration_price() {
+    let mut msg = instantiate_msg();
+    msg.create_profile_cost = Some(coin(10, "ucore"));
+    let renew = || ExecuteMsg::RenewProfile {
+        user_id: "alice".to_string(),
+    };
+
+    let mut deps = setup(msg.clone());
+    let err = exec(&mut deps, "alice", &coins(10, "ucore"), renew()).unwrap_err();
+    assert_eq!(err, ContractError::NoRegistrationTerm {});
+
+    msg.user_id_term = Some(1000);
+    let mut deps = setup(msg);
+    let err = exec(&mut deps, "alice", &coins(5, "ucore"), renew()).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidFunds { .. }));
+    let res = exec(&mut deps, "alice", &coins(10, "ucore"), renew()).unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: PROFILES.to_string(),
+            msg: to_json_binary(&ProfilesExecuteMsg::RenewUserId {
+                user_id: "alice".to_string(),
+                term: 1000,
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    );
+    let collected = COLLECTED_FEES
+        .load(&deps.storage, "ucore".to_string())
+        .unwrap();
+    assert_eq!(collected, Uint128::new(10));
+}
+
+#[test]
+fn changing_user_id_pays_the_change_fee_and_the_new_id_length_price() {
+    let mut msg = instantiate_msg();
+    msg.user_id_change_fee = Some(coin(5, "ucore"));
+    msg.user_id_length_prices = vec![LengthPrice {
+        max_len: 3,
+        cost: coin(100, "ucore"),
+    }];
+    let mut deps = setup(msg);
+    mock_profiles(&mut deps, vec![profile("alice", "alice", &["ally"])]);
+    let change = |user_id: &str| ExecuteMsg::ChangeUserId {
+        user_id: user_id.to_string(),
+    };
+
+    // Switching to an own alias only pays the change fee.
+    exec(&mut deps, "alice", &coins(5, "ucore"), change("ally")).unwrap();
+
+    let err = exec(&mut deps, "alice", &coins(5, "ucore"), change("al")).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidFunds { .. }));
+    exec(&mut deps, "alice", &coins(105, "ucore"), change("al")).unwrap();
+    let collected = COLLECTED_FEES
+        .load(&deps.storage, "ucore".to_string())
+        .unwrap();
+    assert_eq!(collected, Uint128::new(110));
+}
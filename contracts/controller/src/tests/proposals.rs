@@ -0,0 +1,97 @@
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::Addr;
+
+use super::{exec, instantiate_msg, setup, OWNER};
+use crate::contract::execute;
+use crate::error::ContractError;
+use crate::msg::ExecuteMsg;
+use crate::state::{ConfigUpdate, Role, CONFIG, PROPOSALS};
+
+fn max_len_update(message_max_len: u64) -> ExecuteMsg {
+    ExecuteMsg::UpdateConfig(Box::new(ConfigUpdate {
+        message_max_len: Some(message_max_len),
+        message_prices: None,
+        profile_cost: None,
+        trade_fee: None,
+        referral_share: None,
+        length_prices: None,
+        auction: None,
+        registration_term: None,
+        change_fee: None,
+        recovery_delay: None,
+        key_backup_max_len: None,
+        timelock_delay: None,
+    }))
+}
+
+#[test]
+fn proposals_wait_for_the_timelock() {
+    let mut msg = instantiate_msg();
+    msg.timelock_delay = 100;
+    let mut deps = setup(msg);
+
+    exec(&mut deps, OWNER, &[], max_len_update(50)).unwrap();
+    let err = exec(
+        &mut deps,
+        "anyone",
+        &[],
+        ExecuteMsg::ExecuteProposal { id: 0 },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::ProposalNotReady { .. }));
+
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(100);
+    let info = mock_info("anyone", &[]);
+    execute(
+        deps.as_mut(),
+        env,
+        info,
+        ExecuteMsg::ExecuteProposal { id: 0 },
+    )
+    .unwrap();
+    assert_eq!(CONFIG.load(&deps.storage).unwrap().message_max_len, 50);
+}
+
+#[test]
+fn revoked_proposers_proposals_are_not_applied() {
+    let mut deps = setup(instantiate_msg());
+    let grant = ExecuteMsg::GrantRole {
+        role: Role::ConfigManager,
+        address: Addr::unchecked("manager"),
+    };
+    exec(&mut deps, OWNER, &[], grant).unwrap();
+    exec(&mut deps, "manager", &[], max_len_update(50)).unwrap();
+    exec(&mut deps, "manager", &[], max_len_update(60)).unwrap();
+
+    // Still a config manager: the first proposal applies.
+    exec(
+        &mut deps,
+        "anyone",
+        &[],
+        ExecuteMsg::ExecuteProposal { id: 0 },
+    )
+    .unwrap();
+    assert_eq!(CONFIG.load(&deps.storage).unwrap().message_max_len, 50);
+
+    let revoke = ExecuteMsg::RevokeRole {
+        role: Role::ConfigManager,
+        address: Addr::unchecked("manager"),
+    };
+    exec(&mut deps, OWNER, &[], revoke).unwrap();
+    let err = exec(
+        &mut deps,
+        "anyone",
+        &[],
+        ExecuteMsg::ExecuteProposal { id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::MissingRole {
+            role: "config_manager".to_string()
+        }
+    );
+    assert_eq!(CONFIG.load(&deps.storage).unwrap().message_max_len, 50);
+    assert!(PROPOSALS.has(&deps.storage, 1));
+}
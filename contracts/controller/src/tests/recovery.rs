@@ -0,0 +1,175 @@
+use cosmwasm_std::testing::mock_env;
+use cosmwasm_std::{coin, Addr, Empty, StdError};
+
+use super::{exec, instantiate_msg, mock_profiles, profile, setup, TestDeps};
+use crate::error::ContractError;
+use crate::msg::ExecuteMsg;
+use crate::state::{
+    Auction, RecoveryApproval, RecoveryRequest, Subscription, AUCTIONS, BIDDER_AUCTIONS,
+    RECOVERY_REQUESTS, SUBSCRIPTIONS,
+};
+
+fn approve(deps: &mut TestDeps, guardian: &str, new_address: &str) -> Result<(), ContractError> {
+    exec(
+        deps,
+        guardian,
+        &[],
+        ExecuteMsg::ApproveRecovery {
+            address: Addr::unchecked("alice"),
+            new_address: new_address.to_string(),
+        },
+    )
+    .map(|_| ())
+}
+
+fn request(deps: &TestDeps) -> RecoveryRequest {
+    RECOVERY_REQUESTS
+        .load(&deps.storage, Addr::unchecked("alice"))
+        .unwrap()
+}
+
+#[test]
+fn a_single_guardian_cant_block_the_recovery() {
+    let mut deps = setup(instantiate_msg());
+    exec(
+        &mut deps,
+        "alice",
+        &[],
+        ExecuteMsg::SetRecovery {
+            guardians: ["g1", "g2", "g3"]
+                .into_iter()
+                .map(Addr::unchecked)
+                .collect(),
+            threshold: 2,
+        },
+    )
+    .unwrap();
+
+    approve(&mut deps, "g1", "mallory").unwrap();
+    approve(&mut deps, "g2", "alice2").unwrap();
+    assert_eq!(request(&deps).executable_at, None);
+
+    // g1 changes its mind, which reaches the threshold for alice2.
+    approve(&mut deps, "g1", "alice2").unwrap();
+    let approved = request(&deps);
+    assert_eq!(approved.new_address, Some(Addr::unchecked("alice2")));
+    assert!(approved.executable_at.is_some());
+    assert_eq!(approved.approvals.len(), 2);
+
+    // A single guardian can't replace the approved address either.
+    approve(&mut deps, "g3", "mallory").unwrap();
+    assert_eq!(request(&deps).new_address, Some(Addr::unchecked("alice2")));
+}
+
+#[test]
+fn guardians_can_replace_an_approved_request() {
+    let mut deps = setup(instantiate_msg());
+    exec(
+        &mut deps,
+        "alice",
+        &[],
+        ExecuteMsg::SetRecovery {
+            guardians: ["g1", "g2", "g3"]
+                .into_iter()
+                .map(Addr::unchecked)
+                .collect(),
+            threshold: 2,
+        },
+    )
+    .unwrap();
+
+    let err = approve(&mut deps, "g1", "ALICE2").unwrap_err();
+    assert!(matches!(
+        err,
+        ContractError::Std(StdError::GenericErr { .. })
+    ));
+
+    approve(&mut deps, "g1", "alice2").unwrap();
+    approve(&mut deps, "g2", "alice2").unwrap();
+    assert_eq!(request(&deps).new_address, Some(Addr::unchecked("alice2")));
+
+    // alice2 got a profile of its own, so the guardians move the request to alice3.
+    approve(&mut deps, "g1", "alice3").unwrap();
+    assert_eq!(request(&deps).new_address, Some(Addr::unchecked("alice2")));
+    approve(&mut deps, "g3", "alice3").unwrap();
+    let request = request(&deps);
+    assert_eq!(request.new_address, Some(Addr::unchecked("alice3")));
+    assert!(request.executable_at.is_some());
+}
+
+#[test]
+fn recovery_moves_winning_bids_and_subscriptions() {
+    let mut deps = setup(instantiate_msg());
+    mock_profiles(&mut deps, vec![profile("alice", "alice", &[])]);
+    let now = mock_env().block.time;
+    let auction = |bidder: &str| Auction {
+        highest_bidder: Addr::unchecked(bidder),
+        highest_bid: coin(10, "ucore"),
+        pubkey: None,
+        end: now.plus_seconds(100),
+    };
+    AUCTIONS
+        .save(&mut deps.storage, "ace".to_string(), &auction("alice"))
+        .unwrap();
+    AUCTIONS
+        .save(&mut deps.storage, "bee".to_string(), &auction("bob"))
+        .unwrap();
+    for (bidder, user_id) in [("alice", "ace"), ("bob", "bee")] {
+        BIDDER_AUCTIONS
+            .save(
+                &mut deps.storage,
+                (Addr::unchecked(bidder), user_id.to_string()),
+                &Empty {},
+            )
+            .unwrap();
+    }
+    let subscription = Subscription {
+        plan: "pro".to_string(),
+        expires: now.plus_seconds(1000),
+        remaining: 10,
+    };
+    SUBSCRIPTIONS
+        .save(&mut deps.storage, Addr::unchecked("alice"), &subscription)
+        .unwrap();
+    RECOVERY_REQUESTS
+        .save(
+            &mut deps.storage,
+            Addr::unchecked("alice"),
+            &RecoveryRequest {
+                approvals: vec![RecoveryApproval {
+                    guardian: Addr::unchecked("guardian"),
+                    new_address: Addr::unchecked("alice2"),
+                }],
+                new_address: Some(Addr::unchecked("alice2")),
+                executable_at: Some(now),
+            },
+        )
+        .unwrap();
+
+    exec(
+        &mut deps,
+        "anyone",
+        &[],
+        ExecuteMsg::ExecuteRecovery {
+            address: Addr::unchecked("alice"),
+        },
+    )
+    .unwrap();
+
+    let ace = AUCTIONS.load(&deps.storage, "ace".to_string()).unwrap();
+    assert_eq!(ace.highest_bidder, Addr::unchecked("alice2"));
+    let bee = AUCTIONS.load(&deps.storage, "bee".to_string()).unwrap();
+    assert_eq!(bee.highest_bidder, Addr::unchecked("bob"));
+    assert!(BIDDER_AUCTIONS.has(
+        &deps.storage,
+        (Addr::unchecked("alice2"), "ace".to_string())
+    ));
+    assert!(!BIDDER_AUCTIONS.has(&deps.storage, (Addr::unchecked("alice"), "ace".to_string())));
+    assert!(!SUBSCRIPTIONS.has(&deps.storage, Addr::unchecked("alice")));
+    assert_eq!(
+        SUBSCRIPTIONS
+            .load(&deps.storage, Addr::unchecked("alice2"))
+            .unwrap(),
+        subscription
+    );
+}
@@ -0,0 +1,55 @@
+use cosmwasm_std::{coin, coins, Addr, BankMsg, CosmosMsg, Decimal};
+
+use super::{exec, instantiate_msg, mock_profiles, profile, setup};
+use crate::msg::ExecuteMsg;
+use crate::state::{COLLECTED_FEES, REFERRALS};
+
+fn create_profile(user_id: &str) -> ExecuteMsg {
+    ExecuteMsg::CreateProfile {
+        user_id: user_id.to_string(),
+        pubkey: format!("{}-pubkey", user_id),
+        sponsor_code: None,
+        referrer: Some("alice".to_string()),
+    }
+}
+
+#[test]
+fn free_registrations_dont_count_as_referrals() {
+    let mut deps = setup(instantiate_msg());
+    mock_profiles(&mut deps, vec![profile("alice", "alice", &[])]);
+
+    exec(&mut deps, "bob", &[], create_profile("bob")).unwrap();
+    let alice = Addr::unchecked("alice");
+    assert!(!REFERRALS.has(&deps.storage, alice));
+}
+
+#[test]
+fn paid_registrations_reward_the_referrer() {
+    let mut msg = instantiate_msg();
+    msg.create_profile_cost = Some(coin(100, "ucore"));
+    msg.referral_share = Decimal::percent(20);
+    let mut deps = setup(msg);
+    mock_profiles(&mut deps, vec![profile("alice", "alice", &[])]);
+
+    let res = exec(
+        &mut deps,
+        "bob",
+        &coins(100, "ucore"),
+        create_profile("bob"),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[1].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "alice".to_string(),
+            amount: coins(20, "ucore"),
+        })
+    );
+    let alice = Addr::unchecked("alice");
+    let stats = REFERRALS.load(&deps.storage, alice).unwrap();
+    assert_eq!(stats.count, 1);
+    let collected = COLLECTED_FEES
+        .load(&deps.storage, "ucore".to_string())
+        .unwrap();
+    assert_eq!(collected.u128(), 80);
+}
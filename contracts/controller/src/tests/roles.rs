@@ -0,0 +1,87 @@
+use cosmwasm_std::testing::mock_env;
+use cosmwasm_std::{from_json, Addr};
+use cw_ownable::OwnershipError;
+
+use super::{exec, instantiate_msg, setup, TestDeps, OWNER};
+use crate::contract::query;
+use crate::error::ContractError;
+use crate::msg::{ExecuteMsg, QueryMsg, RoleMembers};
+use crate::state::{Operation, Role, PAUSE_FLAGS};
+
+fn grant(role: Role, address: &str) -> ExecuteMsg {
+    ExecuteMsg::GrantRole {
+        role,
+        address: Addr::unchecked(address),
+    }
+}
+
+fn members(deps: &TestDeps, role: Role, start_after: Option<&str>, limit: u64) -> Vec<Addr> {
+    let msg = QueryMsg::RoleMembers {
+        role,
+        start_after: start_after.map(Addr::unchecked),
+        limit: Some(limit),
+    };
+    let res: RoleMembers = from_json(query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    res.members
+}
+
+#[test]
+fn pausers_pause_but_dont_manage_roles() {
+    let mut deps = setup(instantiate_msg());
+    exec(&mut deps, OWNER, &[], grant(Role::Pauser, "pauser")).unwrap();
+
+    let pause = ExecuteMsg::SetPaused {
+        operation: Operation::Sending,
+        paused: true,
+    };
+    exec(&mut deps, "pauser", &[], pause).unwrap();
+    assert!(PAUSE_FLAGS.load(&deps.storage).unwrap().sending);
+
+    let err = exec(&mut deps, "pauser", &[], grant(Role::Pauser, "other")).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::MissingRole {
+            role: "admin".to_string()
+        }
+    );
+}
+
+#[test]
+fn only_the_owner_manages_admins() {
+    let mut deps = setup(instantiate_msg());
+    exec(&mut deps, OWNER, &[], grant(Role::Admin, "admin")).unwrap();
+
+    exec(&mut deps, "admin", &[], grant(Role::Pauser, "pauser")).unwrap();
+    let err = exec(&mut deps, "admin", &[], grant(Role::Admin, "accomplice")).unwrap_err();
+    assert_eq!(err, ContractError::Ownership(OwnershipError::NotOwner));
+    exec(&mut deps, OWNER, &[], grant(Role::Admin, "admin2")).unwrap();
+    let revoke = ExecuteMsg::RevokeRole {
+        role: Role::Admin,
+        address: Addr::unchecked("admin2"),
+    };
+    let err = exec(&mut deps, "admin", &[], revoke).unwrap_err();
+    assert_eq!(err, ContractError::Ownership(OwnershipError::NotOwner));
+
+    let admins = members(&deps, Role::Admin, None, 10);
+    assert_eq!(
+        admins,
+        vec![Addr::unchecked("admin"), Addr::unchecked("admin2")]
+    );
+}
+
+#[test]
+fn role_members_are_listed_by_page() {
+    let mut deps = setup(instantiate_msg());
+    for pauser in ["pauser1", "pauser2", "pauser3"] {
+        exec(&mut deps, OWNER, &[], grant(Role::Pauser, pauser)).unwrap();
+    }
+
+    let page = members(&deps, Role::Pauser, None, 2);
+    assert_eq!(
+        page,
+        vec![Addr::unchecked("pauser1"), Addr::unchecked("pauser2")]
+    );
+    let page = members(&deps, Role::Pauser, Some("pauser2"), 2);
+    assert_eq!(page, vec![Addr::unchecked("pauser3")]);
+    assert!(members(&deps, Role::Moderator, None, 2).is_empty());
+}
@@ -0,0 +1,145 @@
+use cosmwasm_std::{coin, coins, Addr, BankMsg, Binary, CosmosMsg, Uint128};
+
+use super::{exec, instantiate_msg, setup, TestDeps};
+use crate::error::ContractError;
+use crate::msg::ExecuteMsg;
+use crate::state::{
+    AllowanceLimit, MessagePrice, ALLOWANCES, COLLECTED_FEES, SPONSOR_CODES, SPONSOR_DEPOSITS,
+};
+
+/// Messages cost 10ucore and the sponsor deposited 100ucore.
+fn sponsor_setup() -> TestDeps {
+    let mut msg = instantiate_msg();
+    msg.send_message_prices = vec![MessagePrice {
+        denom: "ucore".to_string(),
+        base: Uint128::new(10),
+        per_byte: Uint128::zero(),
+    }];
+    let mut deps = setup(msg);
+    exec(
+        &mut deps,
+        "sponsor",
+        &coins(100, "ucore"),
+        ExecuteMsg::DepositSponsorFunds {},
+    )
+    .unwrap();
+    deps
+}
+
+fn send() -> ExecuteMsg {
+    ExecuteMsg::SendMessage {
+        content: Binary::from(b"hi".as_slice()),
+        dest_address: Some(Addr::unchecked("bob")),
+        dest_id: None,
+        dest_id_hash: None,
+    }
+}
+
+fn deposit(deps: &TestDeps) -> Option<Uint128> {
+    SPONSOR_DEPOSITS
+        .may_load(
+            &deps.storage,
+            (Addr::unchecked("sponsor"), "ucore".to_string()),
+        )
+        .unwrap()
+}
+
+#[test]
+fn allowances_pay_messages_from_the_sponsor_deposit() {
+    let mut deps = sponsor_setup();
+    let grant = ExecuteMsg::GrantAllowance {
+        grantee: Addr::unchecked("alice"),
+        limit: AllowanceLimit::Messages(1),
+        expires: None,
+    };
+    exec(&mut deps, "sponsor", &[], grant).unwrap();
+
+    exec(&mut deps, "alice", &[], send()).unwrap();
+    assert_eq!(deposit(&deps), Some(Uint128::new(90)));
+    let collected = COLLECTED_FEES
+        .load(&deps.storage, "ucore".to_string())
+        .unwrap();
+    assert_eq!(collected, Uint128::new(10));
+    let key = (Addr::unchecked("alice"), Addr::unchecked("sponsor"));
+    assert!(!ALLOWANCES.has(&deps.storage, key));
+
+    let err = exec(&mut deps, "alice", &[], send()).unwrap_err();
+    assert_eq!(err, ContractError::NotEnoughFundsForMessage {});
+    assert_eq!(deposit(&deps), Some(Uint128::new(90)));
+}
+
+#[test]
+fn sponsor_codes_grant_their_allowance_to_new_profiles() {
+    let mut deps = sponsor_setup();
+    let code = ExecuteMsg::CreateSponsorCode {
+        code: "welcome".to_string(),
+        limit: AllowanceLimit::Coins(coin(15, "ucore")),
+        expires: None,
+        max_uses: None,
+    };
+    exec(&mut deps, "sponsor", &[], code).unwrap();
+    let create = ExecuteMsg::CreateProfile {
+        user_id: "alice".to_string(),
+        pubkey: "alice-pubkey".to_string(),
+        sponsor_code: Some("welcome".to_string()),
+        referrer: None,
+    };
+    exec(&mut deps, "alice", &[], create).unwrap();
+
+    // The allowance covers one message, leaving 5ucore of its cap.
+    exec(&mut deps, "alice", &[], send()).unwrap();
+    assert_eq!(deposit(&deps), Some(Uint128::new(90)));
+    let err = exec(&mut deps, "alice", &[], send()).unwrap_err();
+    assert_eq!(err, ContractError::NotEnoughFundsForMessage {});
+}
+
+#[test]
+fn sponsor_codes_stop_working_after_their_last_use() {
+    let mut deps = sponsor_setup();
+    let code = ExecuteMsg::CreateSponsorCode {
+        code: "welcome".to_string(),
+        limit: AllowanceLimit::Messages(1),
+        expires: None,
+        max_uses: Some(1),
+    };
+    exec(&mut deps, "sponsor", &[], code).unwrap();
+    let create = |user_id: &str| ExecuteMsg::CreateProfile {
+        user_id: user_id.to_string(),
+        pubkey: format!("{}-pubkey", user_id),
+        sponsor_code: Some("welcome".to_string()),
+        referrer: None,
+    };
+
+    exec(&mut deps, "alice", &[], create("alice")).unwrap();
+    let err = exec(&mut deps, "bob", &[], create("bob")).unwrap_err();
+    assert_eq!(err, ContractError::SponsorCodeUsedUp {});
+    let code = SPONSOR_CODES
+        .load(&deps.storage, "welcome".to_string())
+        .unwrap();
+    assert_eq!(code.uses, 1);
+    let key = (Addr::unchecked("bob"), Addr::unchecked("sponsor"));
+    assert!(!ALLOWANCES.has(&deps.storage, key));
+}
+
+#[test]
+fn sponsors_withdraw_at_most_their_deposit() {
+    let mut deps = sponsor_setup();
+
+    let withdraw = |amount| ExecuteMsg::WithdrawSponsorFunds {
+        amount: coins(amount, "ucore"),
+    };
+    let err = exec(&mut deps, "sponsor", &[], withdraw(101)).unwrap_err();
+    assert_eq!(err, ContractError::InsufficientDeposit {});
+    let err = exec(&mut deps, "alice", &[], withdraw(1)).unwrap_err();
+    assert_eq!(err, ContractError::InsufficientDeposit {});
+
+    let res = exec(&mut deps, "sponsor", &[], withdraw(100)).unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "sponsor".to_string(),
+            amount: coins(100, "ucore"),
+        })
+    );
+    assert_eq!(deposit(&deps), None);
+}
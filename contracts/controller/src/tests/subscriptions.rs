@@ -0,0 +1,102 @@
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::{coin, coins, Addr, Binary, Uint128};
+
+use super::{exec, instantiate_msg, setup, TestDeps, OWNER};
+use crate::contract::execute;
+use crate::error::ContractError;
+use crate::msg::ExecuteMsg;
+use crate::state::{MessagePrice, COLLECTED_FEES, SUBSCRIPTIONS};
+
+/// Messages cost 10ucore, the "basic" plan 50ucore for two messages within 100 seconds.
+fn plan_setup() -> TestDeps {
+    let mut msg = instantiate_msg();
+    msg.send_message_prices = vec![MessagePrice {
+        denom: "ucore".to_string(),
+        base: Uint128::new(10),
+        per_byte: Uint128::zero(),
+    }];
+    let mut deps = setup(msg);
+    let plan = ExecuteMsg::SetPlan {
+        name: "basic".to_string(),
+        price: coin(50, "ucore"),
+        period: 100,
+        quota: 2,
+    };
+    exec(&mut deps, OWNER, &[], plan).unwrap();
+    deps
+}
+
+fn subscribe() -> ExecuteMsg {
+    ExecuteMsg::Subscribe {
+        plan: "basic".to_string(),
+    }
+}
+
+fn send() -> ExecuteMsg {
+    ExecuteMsg::SendMessage {
+        content: Binary::from(b"hi".as_slice()),
+        dest_address: Some(Addr::unchecked("bob")),
+        dest_id: None,
+        dest_id_hash: None,
+    }
+}
+
+#[test]
+fn subscriptions_cost_the_plan_price_and_cover_their_quota() {
+    let mut deps = plan_setup();
+
+    let err = exec(&mut deps, "alice", &coins(40, "ucore"), subscribe()).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidFunds { .. }));
+    exec(&mut deps, "alice", &coins(50, "ucore"), subscribe()).unwrap();
+    let collected = COLLECTED_FEES
+        .load(&deps.storage, "ucore".to_string())
+        .unwrap();
+    assert_eq!(collected, Uint128::new(50));
+
+    exec(&mut deps, "alice", &[], send()).unwrap();
+    exec(&mut deps, "alice", &[], send()).unwrap();
+    let err = exec(&mut deps, "alice", &[], send()).unwrap_err();
+    assert_eq!(err, ContractError::NotEnoughFundsForMessage {});
+    exec(&mut deps, "alice", &coins(10, "ucore"), send()).unwrap();
+}
+
+#[test]
+fn renewing_an_active_subscription_extends_it() {
+    let mut deps = plan_setup();
+    exec(&mut deps, "alice", &coins(50, "ucore"), subscribe()).unwrap();
+    exec(&mut deps, "alice", &[], send()).unwrap();
+    exec(&mut deps, "alice", &coins(50, "ucore"), subscribe()).unwrap();
+
+    let subscription = SUBSCRIPTIONS
+        .load(&deps.storage, Addr::unchecked("alice"))
+        .unwrap();
+    assert_eq!(subscription.remaining, 3);
+    assert_eq!(
+        subscription.expires,
+        mock_env().block.time.plus_seconds(200)
+    );
+
+    // Expired subscriptions no longer pay, whatever quota is left.
+    let mut env = mock_env();
+    env.block.time = subscription.expires;
+    let err = execute(deps.as_mut(), env, mock_info("alice", &[]), send()).unwrap_err();
+    assert_eq!(err, ContractError::NotEnoughFundsForMessage {});
+}
+
+#[test]
+fn renewing_an_exhausted_subscription_keeps_its_remaining_period() {
+    let mut deps = plan_setup();
+    exec(&mut deps, "alice", &coins(50, "ucore"), subscribe()).unwrap();
+    exec(&mut deps, "alice", &[], send()).unwrap();
+    exec(&mut deps, "alice", &[], send()).unwrap();
+
+    exec(&mut deps, "alice", &coins(50, "ucore"), subscribe()).unwrap();
+    let subscription = SUBSCRIPTIONS
+        .load(&deps.storage, Addr::unchecked("alice"))
+        .unwrap();
+    assert_eq!(subscription.remaining, 2);
+    assert_eq!(
+        subscription.expires,
+        mock_env().block.time.plus_seconds(200)
+    );
+}
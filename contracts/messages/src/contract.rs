@@ -1,7 +1,8 @@
 use crate::error::ContractError;
-use crate::state::{Config, CONFIG, USER_MESSAGES};
+use crate::state::{Config, CLAIMS_PAUSED, CLOSED_INBOXES, CONFIG, USER_MESSAGES};
 use cosmwasm_std::{
-    entry_point, to_binary, BankMsg, Coin, Deps, DepsMut, Env, MessageInfo, StdResult,
+    entry_point, to_json_binary, BankMsg, Coin, Deps, DepsMut, Empty, Env, MessageInfo, StdResult,
+    Storage,
 };
 use cosmwasm_std::{Addr, Binary, Response};
 use cw2::set_contract_version;
@@ -63,6 +64,11 @@ pub fn execute(
             claim_message_funds(deps, info, message_ids)
         }
         ExecuteMsg::DeleteMessages { message_ids } => delete_messages(deps, info, message_ids),
+        ExecuteMsg::CloseInbox { address } => close_inbox(deps, info, address),
+        ExecuteMsg::OpenInbox { address } => open_inbox(deps, info, address),
+        ExecuteMsg::ClearInbox { address, refund } => clear_inbox(deps, info, address, refund),
+        ExecuteMsg::MoveInbox { from, to } => move_inbox(deps, info, from, to),
+        ExecuteMsg::SetClaimsPaused { paused } => set_claims_paused(deps, info, paused),
         ExecuteMsg::ChangeConfig {
             default_query_limit,
             max_query_limit,
@@ -79,6 +85,11 @@ fn send_message(
     message: Binary,
 ) -> Result<Response, ContractError> {
     assert_owner(deps.storage, &info.sender)?;
+
+    if CLOSED_INBOXES.has(deps.storage, receiver.clone()) {
+        return Err(ContractError::InboxClosed {});
+    }
+
     let message = Message {
         sender: sender.clone(),
         content: message,
@@ -103,6 +114,8 @@ fn claim_message_funds(
     info: MessageInfo,
     message_ids: Vec<u64>,
 ) -> Result<Response, ContractError> {
+    assert_claims_not_paused(deps.storage)?;
+
     let mut messages = USER_MESSAGES.load(deps.storage, info.sender.clone())?;
     let mut funds_to_send = vec![];
     create_funds_array(&mut funds_to_send, &mut messages, message_ids)?;
@@ -125,28 +138,149 @@ fn delete_messages(
     info: MessageInfo,
     message_ids: Vec<u64>,
 ) -> Result<Response, ContractError> {
+    // Deleting pays out the funds of the deleted messages.
+    assert_claims_not_paused(deps.storage)?;
+
     let mut messages = USER_MESSAGES.load(deps.storage, info.sender.clone())?;
     let mut funds_to_send = vec![];
     create_funds_array(&mut funds_to_send, &mut messages, message_ids.clone())?;
 
-    let bank_msg = BankMsg::Send {
-        to_address: info.sender.clone().to_string(),
-        amount: funds_to_send,
-    };
-
     let mut idx = 0;
     messages.retain(|_| {
         let current = idx;
         idx += 1;
         !message_ids.contains(&current)
     });
+    USER_MESSAGES.save(deps.storage, info.sender.clone(), &messages)?;
+
+    let bank_msgs = match funds_to_send.is_empty() {
+        true => None,
+        false => Some(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: funds_to_send,
+        }),
+    };
 
     Ok(Response::new()
-        .add_message(bank_msg)
+        .add_messages(bank_msgs)
         .add_attribute("action", "delete_messages")
         .add_attribute("sender", info.sender))
 }
 
+fn close_inbox(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: Addr,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+    CLOSED_INBOXES.save(deps.storage, address.clone(), &Empty {})?;
+
+    Ok(Response::new()
+        .add_attribute("action", "close_inbox")
+        .add_attribute("address", address))
+}
+
+fn open_inbox(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: Addr,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+    CLOSED_INBOXES.remove(deps.storage, address.clone());
+
+    Ok(Response::new()
+        .add_attribute("action", "open_inbox")
+        .add_attribute("address", address))
+}
+
+fn clear_inbox(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: Addr,
+    refund: bool,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+    // Clearing pays out the unclaimed funds of the inbox.
+    assert_claims_not_paused(deps.storage)?;
+    let messages = USER_MESSAGES
+        .may_load(deps.storage, address.clone())?
+        .unwrap_or_default();
+
+    // Unclaimed funds are grouped by recipient so each one gets a single bank message.
+    let mut payouts: Vec<(Addr, Vec<Coin>)> = vec![];
+    for message in messages {
+        if message.funds.is_empty() {
+            continue;
+        }
+        let recipient = match refund {
+            true => message.sender,
+            false => address.clone(),
+        };
+        match payouts.iter().position(|(r, _)| *r == recipient) {
+            Some(index) => add_funds(&mut payouts[index].1, message.funds),
+            None => payouts.push((recipient, message.funds)),
+        }
+    }
+
+    USER_MESSAGES.remove(deps.storage, address.clone());
+    CLOSED_INBOXES.remove(deps.storage, address.clone());
+
+    let bank_msgs = payouts
+        .into_iter()
+        .map(|(recipient, amount)| BankMsg::Send {
+            to_address: recipient.into_string(),
+            amount,
+        });
+
+    Ok(Response::new()
+        .add_messages(bank_msgs)
+        .add_attribute("action", "clear_inbox")
+        .add_attribute("address", address)
+        .add_attribute("refund", refund.to_string()))
+}
+
+fn move_inbox(
+    deps: DepsMut,
+    info: MessageInfo,
+    from: Addr,
+    to: Addr,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+
+    let moved = USER_MESSAGES
+        .may_load(deps.storage, from.clone())?
+        .unwrap_or_default();
+    let mut messages = USER_MESSAGES
+        .may_load(deps.storage, to.clone())?
+        .unwrap_or_default();
+    messages.extend(moved);
+
+    USER_MESSAGES.remove(deps.storage, from.clone());
+    USER_MESSAGES.save(deps.storage, to.clone(), &messages)?;
+    if CLOSED_INBOXES.has(deps.storage, from.clone()) {
+        CLOSED_INBOXES.remove(deps.storage, from.clone());
+        CLOSED_INBOXES.save(deps.storage, to.clone(), &Empty {})?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "move_inbox")
+        .add_attribute("from", from)
+        .add_attribute("to", to))
+}
+
+fn add_funds(funds: &mut Vec<Coin>, coins: Vec<Coin>) {
+    for c in coins {
+        match funds.iter().position(|f| f.denom == c.denom) {
+            Some(index) => {
+                funds[index].amount += c.amount;
+            }
+            None => {
+                funds.push(c);
+            }
+        }
+    }
+}
+
 fn create_funds_array(
     funds: &mut Vec<Coin>,
     messages: &mut [Message],
@@ -157,16 +291,7 @@ fn create_funds_array(
         match message {
             Some(m) => {
                 if !m.funds.is_empty() {
-                    for c in m.funds.clone() {
-                        match funds.iter().position(|f| f.denom == c.denom) {
-                            Some(index) => {
-                                funds[index].amount += c.amount;
-                            }
-                            None => {
-                                funds.push(c);
-                            }
-                        }
-                    }
+                    add_funds(funds, m.funds.clone());
 
                     messages[index as usize].funds = vec![];
                 }
@@ -177,6 +302,30 @@ fn create_funds_array(
     Ok(())
 }
 
+fn assert_claims_not_paused(storage: &dyn Storage) -> Result<(), ContractError> {
+    if CLAIMS_PAUSED.may_load(storage)?.unwrap_or(false) {
+        return Err(ContractError::Paused {
+            operation: "claims".to_string(),
+        });
+    }
+
+    Ok(())
+}
+
+fn set_claims_paused(
+    deps: DepsMut,
+    info: MessageInfo,
+    paused: bool,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+    CLAIMS_PAUSED.save(deps.storage, &paused)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_claims_paused")
+        .add_attribute("sender", info.sender)
+        .add_attribute("paused", paused.to_string()))
+}
+
 fn change_config(
     deps: DepsMut,
     info: MessageInfo,
@@ -211,8 +360,13 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
             address,
             from,
             limit,
-        } => to_binary(&query_messages(deps, address, from, limit)?),
-        QueryMsg::TotalMessages { address } => to_binary(&query_total_messages(deps, address)?),
+        } => to_json_binary(&query_messages(deps, address, from, limit)?),
+        QueryMsg::TotalMessages { address } => {
+            to_json_binary(&query_total_messages(deps, address)?)
+        }
+        QueryMsg::ClaimsPaused {} => {
+            to_json_binary(&CLAIMS_PAUSED.may_load(deps.storage)?.unwrap_or(false))
+        }
     }
 }
 
@@ -230,7 +384,8 @@ fn query_messages(
         .min(config.max_query_limit);
     let mut return_messages = vec![];
 
-    for index in ((from.min(messages.len().try_into().unwrap()) - query_limit).max(0)..from).rev() {
+    let end = from.min(messages.len().try_into().unwrap());
+    for index in (end.saturating_sub(query_limit)..end).rev() {
         return_messages.push(MessageResponse {
             id: index,
             message: messages.get(index as usize).unwrap().clone(),
@@ -12,4 +12,10 @@ pub enum ContractError {
 
     #[error("Message does not exist")]
     NoMessage {},
+
+    #[error("Inbox of this address is closed")]
+    InboxClosed {},
+
+    #[error("{operation} is paused")]
+    Paused { operation: String },
 }
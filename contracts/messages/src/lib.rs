@@ -1,3 +1,5 @@
 pub mod contract;
 pub mod error;
-pub mod state;
\ No newline at end of file
+pub mod state;
+#[cfg(test)]
+mod tests;
@@ -1,10 +1,15 @@
-use cosmwasm_std::Addr;
+use cosmwasm_std::{Addr, Empty};
 use cw_storage_plus::{Map, Item};
 use utils::elements::Message;
 use cosmwasm_schema::cw_serde;
 
 pub const CONFIG: Item<Config> = Item::new("config");
 pub const USER_MESSAGES: Map<Addr, Vec<Message>> = Map::new("user_messages");
+/// Set by the owner to stop every payout of message funds in an emergency: claims, deletions and
+/// cleared inboxes.
+pub const CLAIMS_PAUSED: Item<bool> = Item::new("claims_paused");
+/// Inboxes of deactivated profiles. New messages to these addresses are rejected.
+pub const CLOSED_INBOXES: Map<Addr, Empty> = Map::new("closed_inboxes");
 
 #[cw_serde]
 pub struct Config {
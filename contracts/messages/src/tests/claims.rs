@@ -0,0 +1,86 @@
+use cosmwasm_std::{coins, Addr, BankMsg, CosmosMsg};
+use utils::msg::MessagesExecuteMsg as ExecuteMsg;
+
+use super::{exec, send, setup, CONTROLLER};
+use crate::error::ContractError;
+
+#[test]
+fn paused_claims_are_rejected_until_resumed() {
+    let mut deps = setup();
+    send(&mut deps, "alice", "carol", &coins(5, "ucore"));
+    let pause = |paused| ExecuteMsg::SetClaimsPaused { paused };
+    let claim = || ExecuteMsg::ClaimMessageFunds {
+        message_ids: vec![0],
+    };
+
+    let err = exec(&mut deps, "carol", &[], pause(true)).unwrap_err();
+    assert!(matches!(err, ContractError::Ownership(_)));
+    exec(&mut deps, CONTROLLER, &[], pause(true)).unwrap();
+    let err = exec(&mut deps, "carol", &[], claim()).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Paused {
+            operation: "claims".to_string()
+        }
+    );
+
+    exec(&mut deps, CONTROLLER, &[], pause(false)).unwrap();
+    let res = exec(&mut deps, "carol", &[], claim()).unwrap();
+    assert_eq!(res.messages.len(), 1);
+}
+
+#[test]
+fn paused_claims_stop_deleting_and_clearing_too() {
+    let mut deps = setup();
+    send(&mut deps, "alice", "carol", &coins(5, "ucore"));
+    exec(
+        &mut deps,
+        CONTROLLER,
+        &[],
+        ExecuteMsg::SetClaimsPaused { paused: true },
+    )
+    .unwrap();
+    let paused = ContractError::Paused {
+        operation: "claims".to_string(),
+    };
+
+    let delete = ExecuteMsg::DeleteMessages {
+        message_ids: vec![0],
+    };
+    assert_eq!(exec(&mut deps, "carol", &[], delete).unwrap_err(), paused);
+    let clear = ExecuteMsg::ClearInbox {
+        address: Addr::unchecked("carol"),
+        refund: false,
+    };
+    assert_eq!(exec(&mut deps, CONTROLLER, &[], clear).unwrap_err(), paused);
+}
+
+#[test]
+fn deleted_messages_pay_out_only_once() {
+    let mut deps = setup();
+    send(&mut deps, "alice", "carol", &coins(5, "ucore"));
+    send(&mut deps, "alice", "carol", &coins(7, "ucore"));
+    let delete = || ExecuteMsg::DeleteMessages {
+        message_ids: vec![0],
+    };
+
+    let res = exec(&mut deps, "carol", &[], delete()).unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "carol".to_string(),
+            amount: coins(5, "ucore"),
+        })
+    );
+    // The remaining message is now the first one.
+    let res = exec(&mut deps, "carol", &[], delete()).unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "carol".to_string(),
+            amount: coins(7, "ucore"),
+        })
+    );
+    let err = exec(&mut deps, "carol", &[], delete()).unwrap_err();
+    assert_eq!(err, ContractError::NoMessage {});
+}
@@ -0,0 +1,89 @@
+use cosmwasm_std::{coin, coins, Addr, BankMsg, CosmosMsg, Response};
+use utils::msg::MessagesExecuteMsg as ExecuteMsg;
+
+use super::{exec, send, setup, CONTROLLER};
+use crate::state::USER_MESSAGES;
+
+fn bank_sends(res: Response) -> Vec<CosmosMsg> {
+    res.messages.into_iter().map(|msg| msg.msg).collect()
+}
+
+#[test]
+fn clearing_an_inbox_refunds_each_sender_once() {
+    let mut deps = setup();
+    send(&mut deps, "alice", "carol", &coins(5, "ucore"));
+    send(&mut deps, "bob", "carol", &coins(7, "ucore"));
+    send(
+        &mut deps,
+        "alice",
+        "carol",
+        &[coin(3, "ucore"), coin(1, "uother")],
+    );
+    send(&mut deps, "bob", "carol", &[]);
+
+    let clear = ExecuteMsg::ClearInbox {
+        address: Addr::unchecked("carol"),
+        refund: true,
+    };
+    let res = exec(&mut deps, CONTROLLER, &[], clear).unwrap();
+    assert_eq!(
+        bank_sends(res),
+        vec![
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "alice".to_string(),
+                amount: vec![coin(8, "ucore"), coin(1, "uother")],
+            }),
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "bob".to_string(),
+                amount: coins(7, "ucore"),
+            }),
+        ]
+    );
+    assert!(!USER_MESSAGES.has(&deps.storage, Addr::unchecked("carol")));
+}
+
+#[test]
+fn clearing_an_inbox_without_refund_pays_its_owner() {
+    let mut deps = setup();
+    send(&mut deps, "alice", "carol", &coins(5, "ucore"));
+    send(&mut deps, "bob", "carol", &coins(7, "ucore"));
+
+    let clear = ExecuteMsg::ClearInbox {
+        address: Addr::unchecked("carol"),
+        refund: false,
+    };
+    let res = exec(&mut deps, CONTROLLER, &[], clear).unwrap();
+    assert_eq!(
+        bank_sends(res),
+        vec![CosmosMsg::Bank(BankMsg::Send {
+            to_address: "carol".to_string(),
+            amount: coins(12, "ucore"),
+        })]
+    );
+}
+
+#[test]
+fn moving_an_inbox_keeps_its_funds_claimable_by_the_new_address() {
+    let mut deps = setup();
+    send(&mut deps, "alice", "carol", &coins(5, "ucore"));
+    send(&mut deps, "alice", "dave", &coins(2, "ucore"));
+
+    let move_inbox = ExecuteMsg::MoveInbox {
+        from: Addr::unchecked("carol"),
+        to: Addr::unchecked("dave"),
+    };
+    exec(&mut deps, CONTROLLER, &[], move_inbox).unwrap();
+    assert!(!USER_MESSAGES.has(&deps.storage, Addr::unchecked("carol")));
+
+    let claim = ExecuteMsg::ClaimMessageFunds {
+        message_ids: vec![0, 1],
+    };
+    let res = exec(&mut deps, "dave", &[], claim).unwrap();
+    assert_eq!(
+        bank_sends(res),
+        vec![CosmosMsg::Bank(BankMsg::Send {
+            to_address: "dave".to_string(),
+            amount: coins(7, "ucore"),
+        })]
+    );
+}
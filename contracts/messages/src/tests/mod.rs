@@ -0,0 +1,51 @@
+mod claims;
+mod inbox;
+
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+};
+use cosmwasm_std::{Addr, Binary, Coin, OwnedDeps, Response};
+use utils::msg::MessagesExecuteMsg as ExecuteMsg;
+use utils::msg::MessagesInstantiateMsg as InstantiateMsg;
+
+use crate::contract::{execute, instantiate};
+use crate::error::ContractError;
+
+/// The controller owns the messages contract and forwards every message.
+pub const CONTROLLER: &str = "controller";
+
+pub type TestDeps = OwnedDeps<MockStorage, MockApi, MockQuerier>;
+
+pub fn setup() -> TestDeps {
+    let mut deps = mock_dependencies();
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(CONTROLLER, &[]),
+        InstantiateMsg {
+            default_query_limit: 10,
+            max_query_limit: 30,
+        },
+    )
+    .unwrap();
+    deps
+}
+
+pub fn exec(
+    deps: &mut TestDeps,
+    sender: &str,
+    funds: &[Coin],
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    execute(deps.as_mut(), mock_env(), mock_info(sender, funds), msg)
+}
+
+/// Stores a message from `sender` to `receiver` carrying `funds`.
+pub fn send(deps: &mut TestDeps, sender: &str, receiver: &str, funds: &[Coin]) {
+    let msg = ExecuteMsg::SendMessage {
+        sender: Addr::unchecked(sender),
+        receiver: Addr::unchecked(receiver),
+        message: Binary::from(b"hi".as_slice()),
+    };
+    exec(deps, CONTROLLER, funds, msg).unwrap();
+}
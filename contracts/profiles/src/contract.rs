@@ -1,24 +1,40 @@
-use cosmwasm_std::{entry_point, to_binary, DepsMut, Env, MessageInfo, Response};
-use cosmwasm_std::{Addr, Binary, Deps, StdResult};
+use cosmwasm_std::{entry_point, to_json_binary, DepsMut, Env, MessageInfo, Response};
+use cosmwasm_std::{Addr, Binary, Deps, Empty, Order, StdError, StdResult};
 use cw2::set_contract_version;
-use cw_ownable::{assert_owner, initialize_owner};
-use utils::elements::Profile;
-use utils::msg::ProfilesInstantiateMsg as InstantiateMsg;
+use cw_ownable::initialize_owner;
+use cw_storage_plus::Bound;
+use utils::elements::{Attestation, Subname};
 use utils::msg::ProfilesExecuteMsg as ExecuteMsg;
-use utils::query::{ProfileInfo, ProfilesQueryMsg as QueryMsg};
+use utils::msg::ProfilesInstantiateMsg as InstantiateMsg;
+use utils::query::{
+    AttestationChallengeResponse, DirectoryEntry, DirectoryResponse, ExpirationResponse,
+    ExportEntry, ExportProfilesResponse, KeyRecoveryResponse, KeySharesResponse,
+    LinkChallengeResponse, PendingTransferResponse, ProfileInfo, ProfilesQueryMsg as QueryMsg,
+    VerifiersResponse,
+};
 
 use crate::error::ContractError;
-use crate::state::{ADDRESS_TO_PROFILE, USERID_TO_ADDRESS};
+use crate::execute::{attestations, config, keys, links, profiles, subnames, transfers, user_ids};
+use crate::state::{
+    Config, Reservation, ADDRESS_TO_PROFILE, ATTESTATIONS, CONFIG, IMPORTED_PROFILES, KEY_BACKUPS,
+    KEY_RECOVERIES, KEY_SHARES, LINKED_ADDRESSES, PENDING_TRANSFERS, PRIVATE_USER_IDS,
+    PUBKEY_TO_ADDRESS, RESERVED_USER_IDS, SUBNAMES, USERID_EXPIRATIONS, USERID_TO_ADDRESS,
+    VERIFIERS,
+};
 
 const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+const DEFAULT_QUERY_LIMIT: u64 = 10;
+const MAX_QUERY_LIMIT: u64 = 30;
+// Entries a listing query reads at most, including those it leaves out.
+const MAX_QUERY_SCAN: usize = 300;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
     _env: Env,
     info: MessageInfo,
-    _msg: InstantiateMsg,
+    msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
@@ -28,6 +44,17 @@ pub fn instantiate(
         Some(&info.sender.clone().into_string()),
     )?;
 
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            user_id_cooldown: msg.user_id_cooldown,
+            max_aliases: msg.max_aliases,
+            grace_period: msg.grace_period,
+            change_cooldown: msg.change_cooldown,
+            redirect_period: msg.redirect_period,
+        },
+    )?;
+
     Ok(Response::new()
         .add_attribute("action", "instantiate")
         .add_attribute("contract_name", CONTRACT_NAME)
@@ -47,112 +74,503 @@ pub fn execute(
             address,
             user_id,
             pubkey,
-        } => create_profile(deps, info, address, user_id, pubkey),
+            term,
+        } => profiles::create_profile(deps, env, info, address, user_id, pubkey, term),
+        ExecuteMsg::ImportProfiles { entries, term } => {
+            profiles::import_profiles(deps, env, info, entries, term)
+        }
+        ExecuteMsg::CreatePrivateProfile {
+            address,
+            user_id_hash,
+            pubkey,
+        } => profiles::create_private_profile(deps, info, address, user_id_hash, pubkey),
         ExecuteMsg::ChangeUserId { address, user_id } => {
-            change_user_id(deps, info, address, user_id)
+            user_ids::change_user_id(deps, env, info, address, user_id)
+        }
+        ExecuteMsg::ChangePubkey { address, pubkey } => {
+            profiles::change_pubkey(deps, info, address, pubkey)
         }
-        ExecuteMsg::ChangePubkey { address, pubkey } => change_pubkey(deps, info, address, pubkey),
-        ExecuteMsg::UpdateOwnership(action) => update_ownership(deps, env, info, action),
+        ExecuteMsg::AddAlias {
+            address,
+            alias,
+            term,
+        } => user_ids::add_alias(deps, env, info, address, alias, term),
+        ExecuteMsg::RemoveAlias { address, alias } => {
+            user_ids::remove_alias(deps, env, info, address, alias)
+        }
+        ExecuteMsg::RenewUserId { user_id, term } => {
+            user_ids::renew_user_id(deps, env, info, user_id, term)
+        }
+        ExecuteMsg::TransferUserId {
+            address,
+            user_id,
+            to,
+        } => transfers::transfer_user_id(deps, info, address, user_id, to),
+        ExecuteMsg::CancelUserIdTransfer { address, user_id } => {
+            transfers::cancel_user_id_transfer(deps, info, address, user_id)
+        }
+        ExecuteMsg::AcceptUserId {
+            address,
+            user_id,
+            pubkey,
+        } => transfers::accept_user_id(deps, info, address, user_id, pubkey),
+        ExecuteMsg::MoveUserId {
+            user_id,
+            from,
+            to,
+            pubkey,
+        } => transfers::move_user_id(deps, info, user_id, from, to, pubkey),
+        ExecuteMsg::CreateSubname {
+            address,
+            parent,
+            label,
+            holder,
+            pubkey,
+        } => {
+            let subname = Subname { holder, pubkey };
+            subnames::create_subname(deps, env, info, address, parent, label, subname)
+        }
+        ExecuteMsg::TransferSubname {
+            address,
+            parent,
+            label,
+            holder,
+            pubkey,
+        } => subnames::transfer_subname(deps, info, address, parent, label, holder, pubkey),
+        ExecuteMsg::RevokeSubname {
+            address,
+            parent,
+            label,
+        } => subnames::revoke_subname(deps, info, address, parent, label),
+        ExecuteMsg::ChangeSubnamePubkey {
+            address,
+            parent,
+            label,
+            pubkey,
+        } => subnames::change_subname_pubkey(deps, info, address, parent, label, pubkey),
+        ExecuteMsg::SetSubnamePolicy {
+            address,
+            parent,
+            holders_can_change_pubkey,
+        } => subnames::set_subname_policy(deps, info, address, parent, holders_can_change_pubkey),
+        ExecuteMsg::SetDirectoryListing { address, listed } => {
+            profiles::set_directory_listing(deps, info, address, listed)
+        }
+        ExecuteMsg::DeactivateProfile { address } => {
+            profiles::set_active(deps, info, address, false)
+        }
+        ExecuteMsg::ReactivateProfile { address } => {
+            profiles::set_active(deps, info, address, true)
+        }
+        ExecuteMsg::DeleteProfile { address } => profiles::delete_profile(deps, env, info, address),
+        ExecuteMsg::LinkAddress {
+            address,
+            external_address,
+            pubkey,
+            signature,
+        } => links::link_address(
+            deps,
+            env,
+            info,
+            address,
+            external_address,
+            pubkey,
+            signature,
+        ),
+        ExecuteMsg::UnlinkAddress {
+            address,
+            external_address,
+        } => links::unlink_address(deps, info, address, external_address),
+        ExecuteMsg::AddVerifier { verifier, pubkey } => {
+            attestations::add_verifier(deps, info, verifier, pubkey)
+        }
+        ExecuteMsg::RemoveVerifier { verifier } => {
+            attestations::remove_verifier(deps, info, verifier)
+        }
+        ExecuteMsg::Attest {
+            address,
+            verifier,
+            kind,
+            expires,
+            signature,
+        } => {
+            let attestation = Attestation {
+                verifier,
+                kind,
+                expires,
+            };
+            attestations::attest(deps, env, info, address, attestation, signature)
+        }
+        ExecuteMsg::RevokeAttestation {
+            address,
+            verifier,
+            kind,
+        } => attestations::revoke_attestation(deps, info, address, verifier, kind),
+        ExecuteMsg::SetKeyBackup {
+            address,
+            data,
+            expected_version,
+        } => keys::set_key_backup(deps, info, address, data, expected_version),
+        ExecuteMsg::SetKeyShares {
+            address,
+            shares,
+            threshold,
+        } => keys::set_key_shares(deps, info, address, shares, threshold),
+        ExecuteMsg::RequestKeyRecovery {
+            address,
+            new_pubkey,
+        } => keys::request_key_recovery(deps, info, address, new_pubkey),
+        ExecuteMsg::CancelKeyRecovery { address } => keys::cancel_key_recovery(deps, info, address),
+        ExecuteMsg::SubmitKeyShare {
+            address,
+            guardian,
+            share,
+        } => keys::submit_key_share(deps, info, address, guardian, share),
+        ExecuteMsg::MoveProfile { from, to } => profiles::move_profile(deps, info, from, to),
+        ExecuteMsg::ChangeConfig {
+            user_id_cooldown,
+            max_aliases,
+            grace_period,
+            change_cooldown,
+            redirect_period,
+        } => config::change_config(
+            deps,
+            info,
+            user_id_cooldown,
+            max_aliases,
+            grace_period,
+            change_cooldown,
+            redirect_period,
+        ),
+        ExecuteMsg::UpdateOwnership(action) => config::update_ownership(deps, env, info, action),
     }
 }
 
-fn update_ownership(
-    deps: DepsMut,
-    env: Env,
-    info: MessageInfo,
-    action: cw_ownable::Action,
-) -> Result<Response, ContractError> {
-    let ownership = cw_ownable::update_ownership(deps, &env.block, &info.sender, action)?;
-    Ok(Response::new().add_attributes(ownership.into_attributes()))
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::UserInfo { user_id } => to_json_binary(&query_user_info(deps, env, user_id)?),
+        QueryMsg::AddressInfo { address } => {
+            to_json_binary(&query_address_info(deps, &env, address)?)
+        }
+        QueryMsg::UserInfoByHash { user_id_hash } => {
+            let address = PRIVATE_USER_IDS.load(deps.storage, user_id_hash)?;
+            to_json_binary(&query_address_info(deps, &env, address)?)
+        }
+        QueryMsg::ProfileByPubkey { pubkey } => {
+            let address = PUBKEY_TO_ADDRESS.load(deps.storage, pubkey)?;
+            to_json_binary(&query_address_info(deps, &env, address)?)
+        }
+        QueryMsg::ProfileByLinkedAddress { external_address } => {
+            let external_address = links::normalize_external_address(&external_address)
+                .map_err(|e| StdError::generic_err(e.to_string()))?;
+            let address = LINKED_ADDRESSES.load(deps.storage, external_address)?;
+            to_json_binary(&query_address_info(deps, &env, address)?)
+        }
+        QueryMsg::LinkChallenge {
+            address,
+            external_address,
+        } => {
+            let external_address = links::normalize_external_address(&external_address)
+                .map_err(|e| StdError::generic_err(e.to_string()))?;
+            to_json_binary(&LinkChallengeResponse {
+                challenge: links::link_challenge(deps, &env, &address, &external_address)?,
+            })
+        }
+        QueryMsg::AttestationChallenge {
+            address,
+            verifier,
+            kind,
+            expires,
+        } => to_json_binary(&AttestationChallengeResponse {
+            challenge: attestations::attestation_challenge(
+                deps, &env, &address, &verifier, &kind, expires,
+            )?,
+        }),
+        QueryMsg::ListProfiles { start_after, limit } => {
+            let start = start_after.map(Bound::exclusive);
+            to_json_binary(&query_directory(deps, env, start, "", limit)?)
+        }
+        QueryMsg::SearchByPrefix {
+            prefix,
+            start_after,
+            limit,
+        } => {
+            let start = match start_after {
+                Some(start_after) => Bound::exclusive(start_after),
+                None => Bound::inclusive(prefix.clone()),
+            };
+            let start = Some(start);
+            to_json_binary(&query_directory(deps, env, start, &prefix, limit)?)
+        }
+        QueryMsg::ExportProfiles { start_after, limit } => {
+            to_json_binary(&query_export_profiles(deps, start_after, limit)?)
+        }
+        QueryMsg::PendingTransfer { user_id } => {
+            to_json_binary(&query_pending_transfer(deps, user_id)?)
+        }
+        QueryMsg::Expiration { user_id } => to_json_binary(&query_expiration(deps, user_id)?),
+        QueryMsg::CheckRegistration {
+            address,
+            user_id,
+            pubkey,
+        } => {
+            check_registration(deps, &env, &address, &user_id, pubkey)
+                .map_err(|e| StdError::generic_err(e.to_string()))?;
+            to_json_binary(&Empty {})
+        }
+        QueryMsg::Verifiers {} => to_json_binary(&query_verifiers(deps)?),
+        QueryMsg::KeyBackup { address } => {
+            to_json_binary(&KEY_BACKUPS.load(deps.storage, address)?)
+        }
+        QueryMsg::KeyShares { address } => to_json_binary(&query_key_shares(deps, address)?),
+        QueryMsg::KeyRecovery { address } => to_json_binary(&query_key_recovery(deps, address)?),
+    }
 }
 
-fn create_profile(
-    deps: DepsMut,
-    info: MessageInfo,
-    address: Addr,
-    user_id: String,
-    pubkey: String,
-) -> Result<Response, ContractError> {
-    assert_owner(deps.storage, &info.sender)?;
+fn query_user_info(deps: Deps, env: Env, user_id: String) -> StdResult<ProfileInfo> {
+    if let Some((label, parent)) = user_id.split_once('.') {
+        return query_subname_info(deps, env, parent, label);
+    }
+
+    user_ids::assert_not_expired(deps.storage, &env, &user_id)?;
+    let address = match USERID_TO_ADDRESS.may_load(deps.storage, user_id.clone())? {
+        Some(address) => address,
+        None => redirect_target(deps, &env, user_id)?,
+    };
 
-    if USERID_TO_ADDRESS.has(deps.storage, user_id.clone()) {
-        return Err(ContractError::UserIdAlreadyExists {});
+    query_address_info(deps, &env, address)
+}
+
+// A user id replaced by a change still resolves to its former holder during the redirect period.
+fn redirect_target(deps: Deps, env: &Env, user_id: String) -> StdResult<Addr> {
+    match RESERVED_USER_IDS.may_load(deps.storage, user_id)? {
+        Some(Reservation {
+            until,
+            redirect: Some(address),
+        }) if env.block.time < until => Ok(address),
+        _ => Err(StdError::not_found("user id")),
     }
+}
+
+fn query_subname_info(deps: Deps, env: Env, parent: &str, label: &str) -> StdResult<ProfileInfo> {
+    let subname = SUBNAMES.load(deps.storage, (parent.to_string(), label.to_string()))?;
+    user_ids::assert_not_expired(deps.storage, &env, parent)?;
+    let parent_address = USERID_TO_ADDRESS
+        .may_load(deps.storage, parent.to_string())?
+        .ok_or_else(|| StdError::not_found("parent user id"))?;
+    let parent_profile = ADDRESS_TO_PROFILE.load(deps.storage, parent_address)?;
+
+    Ok(ProfileInfo {
+        address: subname.holder,
+        user_id: format!("{}.{}", label, parent),
+        aliases: vec![],
+        pubkey: subname.pubkey,
+        active: parent_profile.active,
+        expires: USERID_EXPIRATIONS.may_load(deps.storage, parent.to_string())?,
+        attestations: vec![],
+        linked_addresses: vec![],
+    })
+}
+
+fn query_address_info(deps: Deps, env: &Env, address: Addr) -> StdResult<ProfileInfo> {
+    let profile = ADDRESS_TO_PROFILE.load(deps.storage, address.clone())?;
+    let expires = match profile.private {
+        true => None,
+        false => USERID_EXPIRATIONS.may_load(deps.storage, profile.user_id.clone())?,
+    };
+    let attestations = query_attestations(deps, env, &address)?;
+
+    Ok(ProfileInfo {
+        address,
+        user_id: profile.user_id,
+        aliases: profile.aliases,
+        pubkey: profile.pubkey,
+        active: profile.active,
+        expires,
+        attestations,
+        linked_addresses: profile.linked_addresses,
+    })
+}
 
-    if ADDRESS_TO_PROFILE.has(deps.storage, address.clone()) {
-        return Err(ContractError::AddressHasProfile {});
+fn query_attestations(deps: Deps, env: &Env, address: &Addr) -> StdResult<Vec<Attestation>> {
+    let mut attestations = vec![];
+    for item in
+        ATTESTATIONS
+            .sub_prefix(address.clone())
+            .range(deps.storage, None, None, Order::Ascending)
+    {
+        let ((verifier, kind), expires) = item?;
+        if expires > env.block.time && VERIFIERS.has(deps.storage, verifier.clone()) {
+            attestations.push(Attestation {
+                verifier,
+                kind,
+                expires,
+            });
+        }
     }
 
-    USERID_TO_ADDRESS.save(deps.storage, user_id.clone(), &address)?;
-    ADDRESS_TO_PROFILE.save(deps.storage, address.clone(), &Profile { user_id, pubkey })?;
+    Ok(attestations)
+}
+
+fn query_verifiers(deps: Deps) -> StdResult<VerifiersResponse> {
+    let verifiers = VERIFIERS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<Addr>>>()?;
 
-    Ok(Response::new()
-        .add_attribute("action", "create_profile")
-        .add_attribute("address", address))
+    Ok(VerifiersResponse { verifiers })
 }
 
-fn change_pubkey(
-    deps: DepsMut,
-    info: MessageInfo,
-    address: Addr,
-    pubkey: String,
-) -> Result<Response, ContractError> {
-    assert_owner(deps.storage, &info.sender)?;
+fn query_key_shares(deps: Deps, address: Addr) -> StdResult<KeySharesResponse> {
+    let key_shares = KEY_SHARES.load(deps.storage, address)?;
+
+    Ok(KeySharesResponse {
+        shares: key_shares.shares,
+        threshold: key_shares.threshold,
+    })
+}
 
-    let mut profile = ADDRESS_TO_PROFILE.load(deps.storage, address.clone())?;
-    profile.pubkey = pubkey;
-    ADDRESS_TO_PROFILE.save(deps.storage, address.clone(), &profile)?;
+fn query_key_recovery(deps: Deps, address: Addr) -> StdResult<KeyRecoveryResponse> {
+    let key_shares = KEY_SHARES.load(deps.storage, address.clone())?;
+    let recovery = KEY_RECOVERIES.load(deps.storage, address)?;
 
-    Ok(Response::new()
-        .add_attribute("action", "update_pubkey")
-        .add_attribute("address", address))
+    let approvals = recovery.shares.iter().map(|s| s.guardian.clone()).collect();
+    let released = recovery.shares.len() as u64 >= key_shares.threshold;
+
+    Ok(KeyRecoveryResponse {
+        new_pubkey: recovery.new_pubkey,
+        approvals,
+        threshold: key_shares.threshold,
+        shares: released.then_some(recovery.shares),
+    })
 }
 
-fn change_user_id(
-    deps: DepsMut,
-    info: MessageInfo,
-    address: Addr,
-    user_id: String,
-) -> Result<Response, ContractError> {
-    assert_owner(deps.storage, &info.sender)?;
+// Walks user ids from `start` while they begin with `prefix`, skipping hidden profiles and expired ids.
+fn query_directory(
+    deps: Deps,
+    env: Env,
+    start: Option<Bound<String>>,
+    prefix: &str,
+    limit: Option<u64>,
+) -> StdResult<DirectoryResponse> {
+    let limit = limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT) as usize;
 
-    let mut profile = ADDRESS_TO_PROFILE.load(deps.storage, address.clone())?;
-    if USERID_TO_ADDRESS.has(deps.storage, user_id.clone()) {
-        return Err(ContractError::UserIdAlreadyExists {});
+    let mut entries = vec![];
+    let mut last_read = None;
+    let mut next_start_after = None;
+    let range = USERID_TO_ADDRESS.range(deps.storage, start, None, Order::Ascending);
+    for (read, item) in range.enumerate() {
+        let (user_id, address) = item?;
+        if !user_id.starts_with(prefix) {
+            break;
+        }
+        if entries.len() == limit || read == MAX_QUERY_SCAN {
+            next_start_after = last_read;
+            break;
+        }
+        last_read = Some(user_id.clone());
+        if ADDRESS_TO_PROFILE
+            .load(deps.storage, address.clone())?
+            .hidden
+            || user_ids::assert_not_expired(deps.storage, &env, &user_id).is_err()
+        {
+            continue;
+        }
+        entries.push(DirectoryEntry {
+            user_id,
+            profile: query_address_info(deps, &env, address)?,
+        });
     }
-    profile.user_id = user_id;
-    ADDRESS_TO_PROFILE.save(deps.storage, address.clone(), &profile)?;
 
-    Ok(Response::new()
-        .add_attribute("action", "change_user_id")
-        .add_attribute("address", address))
+    Ok(DirectoryResponse {
+        entries,
+        next_start_after,
+    })
 }
 
-#[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
-    match msg {
-        QueryMsg::UserInfo { user_id } => to_binary(&query_user_info(deps, user_id)?),
-        QueryMsg::AddressInfo { address } => to_binary(&query_address_info(deps, address)?),
+// Hidden and private profiles are left out, like in the directory.
+fn query_export_profiles(
+    deps: Deps,
+    start_after: Option<Addr>,
+    limit: Option<u64>,
+) -> StdResult<ExportProfilesResponse> {
+    let limit = limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let mut profiles = vec![];
+    let mut last_read = None;
+    let mut next_start_after = None;
+    let range = ADDRESS_TO_PROFILE.range(deps.storage, start, None, Order::Ascending);
+    for (read, item) in range.enumerate() {
+        let (address, profile) = item?;
+        if profiles.len() == limit || read == MAX_QUERY_SCAN {
+            next_start_after = last_read;
+            break;
+        }
+        last_read = Some(address.clone());
+        if profile.hidden {
+            continue;
+        }
+        profiles.push(ExportEntry {
+            imported: IMPORTED_PROFILES.has(deps.storage, address.clone()),
+            address,
+            user_id: profile.user_id,
+            aliases: profile.aliases,
+            pubkey: profile.pubkey,
+        });
     }
+
+    Ok(ExportProfilesResponse {
+        profiles,
+        next_start_after,
+    })
 }
 
-fn query_user_info(deps: Deps, user_id: String) -> StdResult<ProfileInfo> {
+fn query_expiration(deps: Deps, user_id: String) -> StdResult<ExpirationResponse> {
+    let config = CONFIG.load(deps.storage)?;
     let address = USERID_TO_ADDRESS.load(deps.storage, user_id.clone())?;
-    let profile = ADDRESS_TO_PROFILE.load(deps.storage, address.clone())?;
 
-    Ok(ProfileInfo {
+    Ok(ExpirationResponse {
         address,
-        user_id,
-        pubkey: profile.pubkey,
+        expires: USERID_EXPIRATIONS.may_load(deps.storage, user_id.clone())?,
+        grace_end: user_ids::grace_end(deps.storage, &config, &user_id)?,
     })
 }
 
-fn query_address_info(deps: Deps, address: Addr) -> StdResult<ProfileInfo> {
-    let profile = ADDRESS_TO_PROFILE.load(deps.storage, address.clone())?;
+// The checks `AddAlias` would run now if `address` has a profile, otherwise those of
+// `CreateProfile`.
+fn check_registration(
+    deps: Deps,
+    env: &Env,
+    address: &Addr,
+    user_id: &str,
+    pubkey: Option<String>,
+) -> Result<(), ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    user_ids::validate_user_id(user_id)?;
+    match ADDRESS_TO_PROFILE.may_load(deps.storage, address.clone())? {
+        Some(profile) => {
+            if profile.private {
+                return Err(ContractError::PrivateProfile {});
+            }
+            if profile.aliases.len() as u64 >= config.max_aliases {
+                return Err(ContractError::TooManyAliases {
+                    max: config.max_aliases,
+                });
+            }
+        }
+        None => {
+            let pubkey = pubkey.ok_or(ContractError::PubkeyRequired {})?;
+            profiles::claim_pubkey(deps.storage, &pubkey, address)?;
+        }
+    }
+    user_ids::check_user_id_available(deps.storage, env, user_id, address)?;
 
-    Ok(ProfileInfo {
-        address,
-        user_id: profile.user_id,
-        pubkey: profile.pubkey,
-    })
+    Ok(())
+}
+
+fn query_pending_transfer(deps: Deps, user_id: String) -> StdResult<PendingTransferResponse> {
+    let to = PENDING_TRANSFERS.may_load(deps.storage, user_id)?;
+
+    Ok(PendingTransferResponse { to })
 }
@@ -1,4 +1,4 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{StdError, Timestamp};
 use cw_ownable::OwnershipError;
 use thiserror::Error;
 
@@ -15,4 +15,88 @@ pub enum ContractError {
     
     #[error("User ID already registered")]
     UserIdAlreadyExists {},
+
+    #[error("Profile can't have more than {max} aliases")]
+    TooManyAliases { max: u64 },
+
+    #[error("User ID is not an alias of this profile")]
+    NotAnAlias {},
+
+    #[error("User ID can't be empty or contain '.'")]
+    InvalidUserId {},
+
+    #[error("Address does not own this user ID")]
+    NotUserIdOwner {},
+
+    #[error("User ID can't be transferred to its current owner")]
+    SelfTransfer {},
+
+    #[error("User ID is the only one of its profile and can't be sold")]
+    SoleUserId {},
+
+    #[error("There is no pending transfer of this user ID to this address")]
+    NoPendingTransfer {},
+
+    #[error("A pubkey is required to create a profile for the receiving address")]
+    PubkeyRequired {},
+
+    #[error("Subname already exists")]
+    SubnameAlreadyExists {},
+
+    #[error("Subname holders are not allowed to change their pubkey")]
+    SubnamePubkeyLocked {},
+
+    #[error("User ID does not expire")]
+    NotRenewable {},
+
+    #[error("User ID expired and its grace period is over")]
+    UserIdExpired {},
+
+    #[error("User ID was changed recently and can be changed again at {until}")]
+    ChangeCooldown { until: Timestamp },
+
+    #[error("User ID was released recently and is reserved until {until}")]
+    UserIdReserved { until: Timestamp },
+
+    #[error("User ID hash must be a hex encoded sha256 digest")]
+    InvalidUserIdHash {},
+
+    #[error("Private profiles hold a single user ID")]
+    PrivateProfile {},
+
+    #[error("Not a valid Cosmos or EVM address")]
+    InvalidExternalAddress {},
+
+    #[error("Signature does not prove ownership of the address")]
+    InvalidLinkSignature {},
+
+    #[error("Address is already linked to a profile")]
+    AddressAlreadyLinked {},
+
+    #[error("Address is not linked to this profile")]
+    NotLinked {},
+
+    #[error("Not a verifier")]
+    NotVerifier {},
+
+    #[error("Attestation is not signed by the verifier")]
+    InvalidAttestationSignature {},
+
+    #[error("Attestation must expire in the future")]
+    AttestationExpired {},
+
+    #[error("Pubkey is already used by another profile")]
+    PubkeyAlreadyUsed {},
+
+    #[error("Key share threshold must be between 1 and the number of guardians")]
+    InvalidShareThreshold {},
+
+    #[error("Each guardian must have a profile and hold a single share")]
+    InvalidGuardian {},
+
+    #[error("Not a guardian of this profile")]
+    NotGuardian {},
+
+    #[error("Key backup is at version {current}")]
+    BackupVersionMismatch { current: u64 },
 }
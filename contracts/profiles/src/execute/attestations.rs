@@ -0,0 +1,130 @@
+use cosmwasm_std::{Addr, Binary, Deps, StdResult, Timestamp};
+use cosmwasm_std::{DepsMut, Env, MessageInfo, Response};
+use cw_ownable::assert_owner;
+use sha2::{Digest, Sha256};
+use utils::elements::Attestation;
+
+use crate::error::ContractError;
+use crate::state::{ADDRESS_TO_PROFILE, ATTESTATIONS, ATTESTATION_NONCES, VERIFIERS};
+
+// Like `link_challenge`. The nonce keeps a revoked attestation from being submitted again.
+pub(crate) fn attestation_challenge(
+    deps: Deps,
+    env: &Env,
+    address: &Addr,
+    verifier: &Addr,
+    kind: &str,
+    expires: Timestamp,
+) -> StdResult<String> {
+    let nonce = ATTESTATION_NONCES
+        .may_load(deps.storage, (address.clone(), verifier.clone()))?
+        .unwrap_or_default();
+
+    Ok(format!(
+        "Attest {} about profile {} until {} on {} {} #{}",
+        kind,
+        address,
+        expires.seconds(),
+        env.block.chain_id,
+        env.contract.address,
+        nonce
+    ))
+}
+
+pub(crate) fn add_verifier(
+    deps: DepsMut,
+    info: MessageInfo,
+    verifier: Addr,
+    pubkey: Binary,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+    VERIFIERS.save(deps.storage, verifier.clone(), &pubkey)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "add_verifier")
+        .add_attribute("verifier", verifier))
+}
+
+pub(crate) fn remove_verifier(
+    deps: DepsMut,
+    info: MessageInfo,
+    verifier: Addr,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+    VERIFIERS.remove(deps.storage, verifier.clone());
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_verifier")
+        .add_attribute("verifier", verifier))
+}
+
+pub(crate) fn attest(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    address: Addr,
+    attestation: Attestation,
+    signature: Binary,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+    let Attestation {
+        verifier,
+        kind,
+        expires,
+    } = attestation;
+
+    let pubkey = VERIFIERS
+        .may_load(deps.storage, verifier.clone())?
+        .ok_or(ContractError::NotVerifier {})?;
+    if expires <= env.block.time {
+        return Err(ContractError::AttestationExpired {});
+    }
+    ADDRESS_TO_PROFILE.load(deps.storage, address.clone())?;
+    let challenge =
+        attestation_challenge(deps.as_ref(), &env, &address, &verifier, &kind, expires)?;
+    let hash = Sha256::digest(challenge.as_bytes());
+    if !deps
+        .api
+        .secp256k1_verify(&hash, &signature, &pubkey)
+        .unwrap_or(false)
+    {
+        return Err(ContractError::InvalidAttestationSignature {});
+    }
+    ATTESTATIONS.save(
+        deps.storage,
+        (address.clone(), verifier.clone(), kind.clone()),
+        &expires,
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "attest")
+        .add_attribute("address", address)
+        .add_attribute("verifier", verifier)
+        .add_attribute("kind", kind)
+        .add_attribute("expires", expires.to_string()))
+}
+
+pub(crate) fn revoke_attestation(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: Addr,
+    verifier: Addr,
+    kind: String,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+
+    let key = (address.clone(), verifier.clone(), kind.clone());
+    ATTESTATIONS.load(deps.storage, key.clone())?;
+    ATTESTATIONS.remove(deps.storage, key);
+    let nonce_key = (address.clone(), verifier.clone());
+    let nonce = ATTESTATION_NONCES
+        .may_load(deps.storage, nonce_key.clone())?
+        .unwrap_or_default();
+    ATTESTATION_NONCES.save(deps.storage, nonce_key, &(nonce + 1))?;
+
+    Ok(Response::new()
+        .add_attribute("action", "revoke_attestation")
+        .add_attribute("address", address)
+        .add_attribute("verifier", verifier)
+        .add_attribute("kind", kind))
+}
@@ -0,0 +1,38 @@
+use cosmwasm_std::{DepsMut, Env, MessageInfo, Response};
+use cw_ownable::assert_owner;
+
+use crate::error::ContractError;
+use crate::state::CONFIG;
+
+pub(crate) fn update_ownership(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    action: cw_ownable::Action,
+) -> Result<Response, ContractError> {
+    let ownership = cw_ownable::update_ownership(deps, &env.block, &info.sender, action)?;
+    Ok(Response::new().add_attributes(ownership.into_attributes()))
+}
+
+pub(crate) fn change_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    user_id_cooldown: u64,
+    max_aliases: u64,
+    grace_period: u64,
+    change_cooldown: u64,
+    redirect_period: u64,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+    let mut config = CONFIG.load(deps.storage)?;
+    config.user_id_cooldown = user_id_cooldown;
+    config.max_aliases = max_aliases;
+    config.grace_period = grace_period;
+    config.change_cooldown = change_cooldown;
+    config.redirect_period = redirect_period;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "change_config")
+        .add_attribute("sender", info.sender))
+}
@@ -0,0 +1,141 @@
+use cosmwasm_std::{Addr, Binary};
+use cosmwasm_std::{DepsMut, MessageInfo, Response};
+use cw_ownable::assert_owner;
+use utils::elements::{KeyBackup, KeyShare};
+
+use crate::error::ContractError;
+use crate::state::{
+    KeyRecovery, KeyShares, ADDRESS_TO_PROFILE, KEY_BACKUPS, KEY_RECOVERIES, KEY_SHARES,
+};
+
+pub(crate) fn set_key_backup(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: Addr,
+    data: Binary,
+    expected_version: u64,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+
+    ADDRESS_TO_PROFILE.load(deps.storage, address.clone())?;
+    let current = KEY_BACKUPS
+        .may_load(deps.storage, address.clone())?
+        .map_or(0, |backup| backup.version);
+    if current != expected_version {
+        return Err(ContractError::BackupVersionMismatch { current });
+    }
+
+    let version = current + 1;
+    KEY_BACKUPS.save(deps.storage, address.clone(), &KeyBackup { data, version })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_key_backup")
+        .add_attribute("address", address)
+        .add_attribute("version", version.to_string()))
+}
+
+pub(crate) fn set_key_shares(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: Addr,
+    shares: Vec<KeyShare>,
+    threshold: u64,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+
+    ADDRESS_TO_PROFILE.load(deps.storage, address.clone())?;
+    if threshold == 0 || threshold > shares.len() as u64 {
+        return Err(ContractError::InvalidShareThreshold {});
+    }
+    // Shares are encrypted to the guardians' profile pubkeys, so every guardian needs a profile.
+    for (index, key_share) in shares.iter().enumerate() {
+        if key_share.guardian == address
+            || shares[..index]
+                .iter()
+                .any(|s| s.guardian == key_share.guardian)
+            || !ADDRESS_TO_PROFILE.has(deps.storage, key_share.guardian.clone())
+        {
+            return Err(ContractError::InvalidGuardian {});
+        }
+    }
+
+    KEY_SHARES.save(
+        deps.storage,
+        address.clone(),
+        &KeyShares { shares, threshold },
+    )?;
+    KEY_RECOVERIES.remove(deps.storage, address.clone());
+
+    Ok(Response::new()
+        .add_attribute("action", "set_key_shares")
+        .add_attribute("address", address)
+        .add_attribute("threshold", threshold.to_string()))
+}
+
+pub(crate) fn request_key_recovery(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: Addr,
+    new_pubkey: String,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+
+    KEY_SHARES.load(deps.storage, address.clone())?;
+    KEY_RECOVERIES.save(
+        deps.storage,
+        address.clone(),
+        &KeyRecovery {
+            new_pubkey,
+            shares: vec![],
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "request_key_recovery")
+        .add_attribute("address", address))
+}
+
+pub(crate) fn cancel_key_recovery(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: Addr,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+
+    KEY_RECOVERIES.load(deps.storage, address.clone())?;
+    KEY_RECOVERIES.remove(deps.storage, address.clone());
+
+    Ok(Response::new()
+        .add_attribute("action", "cancel_key_recovery")
+        .add_attribute("address", address))
+}
+
+pub(crate) fn submit_key_share(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: Addr,
+    guardian: Addr,
+    share: Binary,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+
+    let key_shares = KEY_SHARES.load(deps.storage, address.clone())?;
+    if !key_shares.shares.iter().any(|s| s.guardian == guardian) {
+        return Err(ContractError::NotGuardian {});
+    }
+
+    let mut recovery = KEY_RECOVERIES.load(deps.storage, address.clone())?;
+    // A guardian submitting again replaces its previous share.
+    recovery.shares.retain(|s| s.guardian != guardian);
+    recovery.shares.push(KeyShare {
+        guardian: guardian.clone(),
+        share,
+    });
+    KEY_RECOVERIES.save(deps.storage, address.clone(), &recovery)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "submit_key_share")
+        .add_attribute("address", address)
+        .add_attribute("guardian", guardian)
+        .add_attribute("approvals", recovery.shares.len().to_string()))
+}
@@ -0,0 +1,147 @@
+use bech32::{ToBase32, Variant};
+use cosmwasm_std::{Addr, Binary, Deps, StdResult};
+use cosmwasm_std::{DepsMut, Env, MessageInfo, Response};
+use cw_ownable::assert_owner;
+use ripemd::Ripemd160;
+use sha2::{Digest, Sha256};
+use sha3::Keccak256;
+
+use crate::error::ContractError;
+use crate::state::{ADDRESS_TO_PROFILE, LINKED_ADDRESSES, LINK_NONCES};
+
+pub(crate) fn link_address(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    address: Addr,
+    external_address: String,
+    pubkey: Option<Binary>,
+    signature: Binary,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+
+    let mut profile = ADDRESS_TO_PROFILE.load(deps.storage, address.clone())?;
+    let external_address = normalize_external_address(&external_address)?;
+    if LINKED_ADDRESSES.has(deps.storage, external_address.clone()) {
+        return Err(ContractError::AddressAlreadyLinked {});
+    }
+
+    let challenge = link_challenge(deps.as_ref(), &env, &address, &external_address)?;
+    let proven = match pubkey {
+        Some(pubkey) => {
+            let hash = Sha256::digest(challenge.as_bytes());
+            deps.api
+                .secp256k1_verify(&hash, &signature, &pubkey)
+                .unwrap_or(false)
+                && cosmos_address(&external_address, &pubkey)? == external_address
+        }
+        None => evm_signer(deps.as_ref(), &challenge, &signature)? == external_address,
+    };
+    if !proven {
+        return Err(ContractError::InvalidLinkSignature {});
+    }
+
+    let nonce = LINK_NONCES
+        .may_load(deps.storage, address.clone())?
+        .unwrap_or_default();
+    LINK_NONCES.save(deps.storage, address.clone(), &(nonce + 1))?;
+    LINKED_ADDRESSES.save(deps.storage, external_address.clone(), &address)?;
+    profile.linked_addresses.push(external_address.clone());
+    ADDRESS_TO_PROFILE.save(deps.storage, address.clone(), &profile)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "link_address")
+        .add_attribute("address", address)
+        .add_attribute("external_address", external_address))
+}
+
+pub(crate) fn unlink_address(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: Addr,
+    external_address: String,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+
+    let mut profile = ADDRESS_TO_PROFILE.load(deps.storage, address.clone())?;
+    let external_address = normalize_external_address(&external_address)?;
+    let index = profile
+        .linked_addresses
+        .iter()
+        .position(|a| *a == external_address)
+        .ok_or(ContractError::NotLinked {})?;
+    profile.linked_addresses.remove(index);
+    ADDRESS_TO_PROFILE.save(deps.storage, address.clone(), &profile)?;
+    LINKED_ADDRESSES.remove(deps.storage, external_address.clone());
+
+    Ok(Response::new()
+        .add_attribute("action", "unlink_address")
+        .add_attribute("address", address)
+        .add_attribute("external_address", external_address))
+}
+
+// EVM addresses are 0x-prefixed hex and anything else must be bech32. Both are stored lowercase,
+// bech32 ones re-encoded from what they decode to.
+pub(crate) fn normalize_external_address(external_address: &str) -> Result<String, ContractError> {
+    match external_address.strip_prefix("0x") {
+        Some(hex) if hex.len() == 40 && hex.chars().all(|c| c.is_ascii_hexdigit()) => {
+            Ok(external_address.to_lowercase())
+        }
+        Some(_) => Err(ContractError::InvalidExternalAddress {}),
+        None => {
+            let (hrp, data, variant) = bech32::decode(external_address)
+                .map_err(|_| ContractError::InvalidExternalAddress {})?;
+            bech32::encode(&hrp, data, variant)
+                .map_err(|_| ContractError::InvalidExternalAddress {})
+        }
+    }
+}
+
+// The chain id, contract and nonce keep a signature from being replayed elsewhere or after an unlink.
+pub(crate) fn link_challenge(
+    deps: Deps,
+    env: &Env,
+    address: &Addr,
+    external_address: &str,
+) -> StdResult<String> {
+    let nonce = LINK_NONCES
+        .may_load(deps.storage, address.clone())?
+        .unwrap_or_default();
+
+    Ok(format!(
+        "Link {} to profile {} on {} {} #{}",
+        external_address, address, env.block.chain_id, env.contract.address, nonce
+    ))
+}
+
+// Bech32 address of a compressed secp256k1 pubkey, with the prefix of `external_address`.
+fn cosmos_address(external_address: &str, pubkey: &[u8]) -> Result<String, ContractError> {
+    let (hrp, _, _) =
+        bech32::decode(external_address).map_err(|_| ContractError::InvalidExternalAddress {})?;
+    let hash = Ripemd160::digest(Sha256::digest(pubkey));
+
+    bech32::encode(&hrp, hash.to_base32(), Variant::Bech32)
+        .map_err(|_| ContractError::InvalidExternalAddress {})
+}
+
+// Recovers the address that signed the challenge with `personal_sign`. The signature is r, s and v.
+fn evm_signer(deps: Deps, challenge: &str, signature: &[u8]) -> Result<String, ContractError> {
+    if signature.len() != 65 {
+        return Err(ContractError::InvalidLinkSignature {});
+    }
+    let mut hasher = Keccak256::new();
+    hasher.update(format!("\x19Ethereum Signed Message:\n{}", challenge.len()));
+    hasher.update(challenge);
+    let hash = hasher.finalize();
+
+    let recovery_param = signature[64].checked_sub(27).unwrap_or(signature[64]);
+    let pubkey = deps
+        .api
+        .secp256k1_recover_pubkey(&hash, &signature[..64], recovery_param)
+        .map_err(|_| ContractError::InvalidLinkSignature {})?;
+    // The address is the last 20 bytes of the keccak256 of the uncompressed pubkey without its prefix.
+    let hash = Keccak256::digest(&pubkey[1..]);
+    let hex: String = hash[12..].iter().map(|b| format!("{:02x}", b)).collect();
+
+    Ok(format!("0x{}", hex))
+}
@@ -0,0 +1,8 @@
+pub mod attestations;
+pub mod config;
+pub mod keys;
+pub mod links;
+pub mod profiles;
+pub mod subnames;
+pub mod transfers;
+pub mod user_ids;
@@ -0,0 +1,387 @@
+use cosmwasm_std::{to_json_binary, DepsMut, Env, MessageInfo, Response};
+use cosmwasm_std::{Addr, Order, StdResult, Storage};
+use cw_ownable::assert_owner;
+use utils::elements::Profile;
+use utils::msg::{ImportEntry, ImportFailure, ImportProfilesResponse};
+
+use crate::error::ContractError;
+use crate::state::{
+    ADDRESS_TO_PROFILE, ATTESTATIONS, CONFIG, HOLDER_SUBNAMES, IMPORTED_PROFILES, KEY_BACKUPS,
+    KEY_RECOVERIES, KEY_SHARES, LAST_USER_ID_CHANGES, LINKED_ADDRESSES, PRIVATE_USER_IDS,
+    PUBKEY_TO_ADDRESS, PUBLIC_USER_ID_HASHES, RECEIVER_TRANSFERS, REDIRECTS, RESERVED_USER_IDS,
+    SUBNAMES, USERID_TO_ADDRESS,
+};
+
+use super::subnames::save_subname;
+use super::transfers::{detach_user_id, save_pending_transfer};
+use super::user_ids::{
+    check_user_id_available, clear_user_id, lapsed_user_ids, reclaim_user_id, release_user_id,
+    save_public_user_id, save_reservation, set_expiration, validate_user_id,
+};
+
+pub(crate) fn create_profile(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    address: Addr,
+    user_id: String,
+    pubkey: String,
+    term: Option<u64>,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+
+    register_profile(deps.storage, &env, &address, user_id, pubkey, term)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "create_profile")
+        .add_attribute("address", address))
+}
+
+// All checks run before anything is written, so a failed registration leaves no trace.
+fn register_profile(
+    storage: &mut dyn Storage,
+    env: &Env,
+    address: &Addr,
+    user_id: String,
+    pubkey: String,
+    term: Option<u64>,
+) -> Result<(), ContractError> {
+    validate_user_id(&user_id)?;
+    let lapsed = match ADDRESS_TO_PROFILE.may_load(storage, address.clone())? {
+        Some(profile) => {
+            lapsed_user_ids(storage, env, &profile)?.ok_or(ContractError::AddressHasProfile {})?
+        }
+        None => vec![],
+    };
+    claim_pubkey(storage, &pubkey, address)?;
+    let expired_holder = check_user_id_available(storage, env, &user_id, address)?;
+
+    // A profile whose user ids all went past their grace period is replaced by the new one.
+    for lapsed_id in lapsed {
+        detach_user_id(storage, &lapsed_id, address)?;
+        clear_user_id(storage, env, lapsed_id)?;
+    }
+    // The user id may have been one of them.
+    let expired_holder = expired_holder.filter(|holder| holder != address);
+    reclaim_user_id(storage, env, &user_id, expired_holder)?;
+
+    save_public_user_id(storage, env, &user_id, address)?;
+    PUBKEY_TO_ADDRESS.save(storage, pubkey.clone(), address)?;
+    set_expiration(storage, env, &user_id, term)?;
+    ADDRESS_TO_PROFILE.save(
+        storage,
+        address.clone(),
+        &Profile {
+            user_id,
+            pubkey,
+            aliases: vec![],
+            active: true,
+            hidden: false,
+            private: false,
+            linked_addresses: vec![],
+        },
+    )?;
+
+    Ok(())
+}
+
+pub(crate) fn import_profiles(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    entries: Vec<ImportEntry>,
+    term: Option<u64>,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+
+    let mut imported = vec![];
+    let mut failures = vec![];
+    for entry in entries {
+        let result = deps
+            .api
+            .addr_validate(entry.address.as_str())
+            .map_err(ContractError::from)
+            .and_then(|address| {
+                register_profile(
+                    deps.storage,
+                    &env,
+                    &address,
+                    entry.user_id.clone(),
+                    entry.pubkey,
+                    term,
+                )?;
+                IMPORTED_PROFILES.save(deps.storage, address, &env.block.time)?;
+                Ok(())
+            });
+        match result {
+            Ok(()) => imported.push(entry.user_id),
+            Err(error) => failures.push(ImportFailure {
+                address: entry.address,
+                user_id: entry.user_id,
+                error: error.to_string(),
+            }),
+        }
+    }
+
+    let counts = (imported.len(), failures.len());
+
+    Ok(Response::new()
+        .set_data(to_json_binary(&ImportProfilesResponse {
+            imported,
+            failures,
+        })?)
+        .add_attribute("action", "import_profiles")
+        .add_attribute("imported", counts.0.to_string())
+        .add_attribute("failed", counts.1.to_string()))
+}
+
+pub(crate) fn create_private_profile(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: Addr,
+    user_id_hash: String,
+    pubkey: String,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+
+    if user_id_hash.len() != 64
+        || !user_id_hash
+            .chars()
+            .all(|c| c.is_ascii_digit() || ('a'..='f').contains(&c))
+    {
+        return Err(ContractError::InvalidUserIdHash {});
+    }
+    if ADDRESS_TO_PROFILE.has(deps.storage, address.clone()) {
+        return Err(ContractError::AddressHasProfile {});
+    }
+    if PRIVATE_USER_IDS.has(deps.storage, user_id_hash.clone())
+        || PUBLIC_USER_ID_HASHES.has(deps.storage, user_id_hash.clone())
+    {
+        return Err(ContractError::UserIdAlreadyExists {});
+    }
+    claim_pubkey(deps.storage, &pubkey, &address)?;
+
+    PRIVATE_USER_IDS.save(deps.storage, user_id_hash.clone(), &address)?;
+    PUBKEY_TO_ADDRESS.save(deps.storage, pubkey.clone(), &address)?;
+    ADDRESS_TO_PROFILE.save(
+        deps.storage,
+        address.clone(),
+        &Profile {
+            user_id: user_id_hash,
+            pubkey,
+            aliases: vec![],
+            active: true,
+            hidden: true,
+            private: true,
+            linked_addresses: vec![],
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "create_private_profile")
+        .add_attribute("address", address))
+}
+
+pub(crate) fn change_pubkey(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: Addr,
+    pubkey: String,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+
+    let mut profile = ADDRESS_TO_PROFILE.load(deps.storage, address.clone())?;
+    claim_pubkey(deps.storage, &pubkey, &address)?;
+    PUBKEY_TO_ADDRESS.remove(deps.storage, profile.pubkey);
+    PUBKEY_TO_ADDRESS.save(deps.storage, pubkey.clone(), &address)?;
+    profile.pubkey = pubkey;
+    ADDRESS_TO_PROFILE.save(deps.storage, address.clone(), &profile)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_pubkey")
+        .add_attribute("address", address))
+}
+
+pub(crate) fn set_directory_listing(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: Addr,
+    listed: bool,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+
+    let mut profile = ADDRESS_TO_PROFILE.load(deps.storage, address.clone())?;
+    profile.hidden = !listed;
+    ADDRESS_TO_PROFILE.save(deps.storage, address.clone(), &profile)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_directory_listing")
+        .add_attribute("address", address)
+        .add_attribute("listed", listed.to_string()))
+}
+
+pub(crate) fn set_active(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: Addr,
+    active: bool,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+
+    let mut profile = ADDRESS_TO_PROFILE.load(deps.storage, address.clone())?;
+    profile.active = active;
+    ADDRESS_TO_PROFILE.save(deps.storage, address.clone(), &profile)?;
+
+    let action = match active {
+        true => "reactivate_profile",
+        false => "deactivate_profile",
+    };
+
+    Ok(Response::new()
+        .add_attribute("action", action)
+        .add_attribute("address", address))
+}
+
+pub(crate) fn delete_profile(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    address: Addr,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+
+    let config = CONFIG.load(deps.storage)?;
+    let profile = ADDRESS_TO_PROFILE.load(deps.storage, address.clone())?;
+
+    match profile.private {
+        true => PRIVATE_USER_IDS.remove(deps.storage, profile.user_id),
+        false => release_user_id(deps.storage, &env, &config, profile.user_id)?,
+    }
+    for alias in profile.aliases {
+        release_user_id(deps.storage, &env, &config, alias)?;
+    }
+    ADDRESS_TO_PROFILE.remove(deps.storage, address.clone());
+    PUBKEY_TO_ADDRESS.remove(deps.storage, profile.pubkey);
+    for linked in profile.linked_addresses {
+        LINKED_ADDRESSES.remove(deps.storage, linked);
+    }
+    remove_profile_data(deps.storage, &address)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "delete_profile")
+        .add_attribute("address", address))
+}
+
+// Removes the key material, attestations and change history of a profile that no longer exists.
+pub(crate) fn remove_profile_data(storage: &mut dyn Storage, address: &Addr) -> StdResult<()> {
+    KEY_BACKUPS.remove(storage, address.clone());
+    KEY_SHARES.remove(storage, address.clone());
+    KEY_RECOVERIES.remove(storage, address.clone());
+    LAST_USER_ID_CHANGES.remove(storage, address.clone());
+    IMPORTED_PROFILES.remove(storage, address.clone());
+    for key in attestation_keys(storage, address)? {
+        ATTESTATIONS.remove(storage, (address.clone(), key.0, key.1));
+    }
+
+    Ok(())
+}
+
+fn attestation_keys(storage: &dyn Storage, address: &Addr) -> StdResult<Vec<(Addr, String)>> {
+    ATTESTATIONS
+        .sub_prefix(address.clone())
+        .keys(storage, None, None, Order::Ascending)
+        .collect()
+}
+
+pub(crate) fn move_profile(
+    deps: DepsMut,
+    info: MessageInfo,
+    from: Addr,
+    to: Addr,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+
+    if ADDRESS_TO_PROFILE.has(deps.storage, to.clone()) {
+        return Err(ContractError::AddressHasProfile {});
+    }
+    let profile = ADDRESS_TO_PROFILE.load(deps.storage, from.clone())?;
+
+    match profile.private {
+        true => PRIVATE_USER_IDS.save(deps.storage, profile.user_id.clone(), &to)?,
+        false => USERID_TO_ADDRESS.save(deps.storage, profile.user_id.clone(), &to)?,
+    }
+    for alias in &profile.aliases {
+        USERID_TO_ADDRESS.save(deps.storage, alias.clone(), &to)?;
+    }
+    for linked in &profile.linked_addresses {
+        LINKED_ADDRESSES.save(deps.storage, linked.clone(), &to)?;
+    }
+    ADDRESS_TO_PROFILE.remove(deps.storage, from.clone());
+    PUBKEY_TO_ADDRESS.save(deps.storage, profile.pubkey.clone(), &to)?;
+    ADDRESS_TO_PROFILE.save(deps.storage, to.clone(), &profile)?;
+    if let Some(backup) = KEY_BACKUPS.may_load(deps.storage, from.clone())? {
+        KEY_BACKUPS.save(deps.storage, to.clone(), &backup)?;
+    }
+    if let Some(key_shares) = KEY_SHARES.may_load(deps.storage, from.clone())? {
+        KEY_SHARES.save(deps.storage, to.clone(), &key_shares)?;
+    }
+    if let Some(recovery) = KEY_RECOVERIES.may_load(deps.storage, from.clone())? {
+        KEY_RECOVERIES.save(deps.storage, to.clone(), &recovery)?;
+    }
+    if let Some(last_change) = LAST_USER_ID_CHANGES.may_load(deps.storage, from.clone())? {
+        LAST_USER_ID_CHANGES.save(deps.storage, to.clone(), &last_change)?;
+    }
+    if let Some(imported) = IMPORTED_PROFILES.may_load(deps.storage, from.clone())? {
+        IMPORTED_PROFILES.save(deps.storage, to.clone(), &imported)?;
+    }
+    for (verifier, kind) in attestation_keys(deps.storage, &from)? {
+        let key = (from.clone(), verifier.clone(), kind.clone());
+        let expires = ATTESTATIONS.load(deps.storage, key)?;
+        ATTESTATIONS.save(deps.storage, (to.clone(), verifier, kind), &expires)?;
+    }
+    // User ids offered to `from` can be accepted by `to` instead.
+    let offered = RECEIVER_TRANSFERS
+        .prefix(from.clone())
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<String>>>()?;
+    for user_id in offered {
+        save_pending_transfer(deps.storage, &user_id, &to)?;
+    }
+    // User ids replaced by a change keep redirecting to the profile.
+    let redirected = REDIRECTS
+        .prefix(from.clone())
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<String>>>()?;
+    for user_id in redirected {
+        let mut reservation = RESERVED_USER_IDS.load(deps.storage, user_id.clone())?;
+        reservation.redirect = Some(to.clone());
+        save_reservation(deps.storage, &user_id, &reservation)?;
+    }
+    // Subnames held by `from` are held by `to`.
+    let held = HOLDER_SUBNAMES
+        .sub_prefix(from.clone())
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<(String, String)>>>()?;
+    for key in held {
+        let mut subname = SUBNAMES.load(deps.storage, key.clone())?;
+        subname.holder = to.clone();
+        save_subname(deps.storage, key, &subname)?;
+    }
+    remove_profile_data(deps.storage, &from)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "move_profile")
+        .add_attribute("from", from)
+        .add_attribute("to", to))
+}
+
+pub(crate) fn claim_pubkey(
+    storage: &dyn Storage,
+    pubkey: &str,
+    address: &Addr,
+) -> Result<(), ContractError> {
+    match PUBKEY_TO_ADDRESS.may_load(storage, pubkey.to_string())? {
+        Some(owner) if owner != *address => Err(ContractError::PubkeyAlreadyUsed {}),
+        _ => Ok(()),
+    }
+}
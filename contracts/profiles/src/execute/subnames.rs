@@ -0,0 +1,170 @@
+use cosmwasm_std::{Addr, Empty, Order, StdResult, Storage};
+use cosmwasm_std::{DepsMut, Env, MessageInfo, Response};
+use cw_ownable::assert_owner;
+use utils::elements::Subname;
+
+use crate::error::ContractError;
+use crate::state::{SubnamePolicy, HOLDER_SUBNAMES, SUBNAMES, SUBNAME_POLICIES};
+
+use super::user_ids::{assert_not_expired, assert_user_id_owner, validate_user_id};
+
+pub(crate) fn create_subname(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    address: Addr,
+    parent: String,
+    label: String,
+    subname: Subname,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+    assert_user_id_owner(deps.storage, &address, &parent)?;
+    // A parent in its grace period is still held but doesn't resolve, so neither would the subname.
+    assert_not_expired(deps.storage, &env, &parent)?;
+    validate_user_id(&label)?;
+
+    let key = (parent.clone(), label.clone());
+    if SUBNAMES.has(deps.storage, key.clone()) {
+        return Err(ContractError::SubnameAlreadyExists {});
+    }
+    save_subname(deps.storage, key, &subname)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "create_subname")
+        .add_attribute("subname", format!("{}.{}", label, parent))
+        .add_attribute("holder", subname.holder))
+}
+
+pub(crate) fn transfer_subname(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: Addr,
+    parent: String,
+    label: String,
+    holder: Addr,
+    pubkey: String,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+    assert_user_id_owner(deps.storage, &address, &parent)?;
+
+    let key = (parent.clone(), label.clone());
+    let mut subname = SUBNAMES.load(deps.storage, key.clone())?;
+    subname.holder = holder.clone();
+    subname.pubkey = pubkey;
+    save_subname(deps.storage, key, &subname)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "transfer_subname")
+        .add_attribute("subname", format!("{}.{}", label, parent))
+        .add_attribute("holder", holder))
+}
+
+pub(crate) fn revoke_subname(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: Addr,
+    parent: String,
+    label: String,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+    assert_user_id_owner(deps.storage, &address, &parent)?;
+
+    let key = (parent.clone(), label.clone());
+    SUBNAMES.load(deps.storage, key.clone())?;
+    remove_subname(deps.storage, key)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "revoke_subname")
+        .add_attribute("subname", format!("{}.{}", label, parent)))
+}
+
+pub(crate) fn change_subname_pubkey(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: Addr,
+    parent: String,
+    label: String,
+    pubkey: String,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+
+    let key = (parent.clone(), label.clone());
+    let mut subname = SUBNAMES.load(deps.storage, key.clone())?;
+    if assert_user_id_owner(deps.storage, &address, &parent).is_err() {
+        if subname.holder != address {
+            return Err(ContractError::NotUserIdOwner {});
+        }
+        let policy = SUBNAME_POLICIES
+            .may_load(deps.storage, parent.clone())?
+            .unwrap_or_default();
+        if !policy.holders_can_change_pubkey {
+            return Err(ContractError::SubnamePubkeyLocked {});
+        }
+    }
+    subname.pubkey = pubkey;
+    SUBNAMES.save(deps.storage, key, &subname)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "change_subname_pubkey")
+        .add_attribute("subname", format!("{}.{}", label, parent))
+        .add_attribute("address", address))
+}
+
+pub(crate) fn set_subname_policy(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: Addr,
+    parent: String,
+    holders_can_change_pubkey: bool,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+    assert_user_id_owner(deps.storage, &address, &parent)?;
+
+    SUBNAME_POLICIES.save(
+        deps.storage,
+        parent.clone(),
+        &SubnamePolicy {
+            holders_can_change_pubkey,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_subname_policy")
+        .add_attribute("parent", parent)
+        .add_attribute(
+            "holders_can_change_pubkey",
+            holders_can_change_pubkey.to_string(),
+        ))
+}
+
+pub(crate) fn save_subname(
+    storage: &mut dyn Storage,
+    key: (String, String),
+    subname: &Subname,
+) -> StdResult<()> {
+    remove_subname(storage, key.clone())?;
+    SUBNAMES.save(storage, key.clone(), subname)?;
+    HOLDER_SUBNAMES.save(storage, (subname.holder.clone(), key.0, key.1), &Empty {})
+}
+
+fn remove_subname(storage: &mut dyn Storage, key: (String, String)) -> StdResult<()> {
+    if let Some(subname) = SUBNAMES.may_load(storage, key.clone())? {
+        HOLDER_SUBNAMES.remove(storage, (subname.holder, key.0.clone(), key.1.clone()));
+        SUBNAMES.remove(storage, key);
+    }
+
+    Ok(())
+}
+
+pub(crate) fn clear_subnames(storage: &mut dyn Storage, user_id: &str) -> StdResult<()> {
+    let labels = SUBNAMES
+        .prefix(user_id.to_string())
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<String>>>()?;
+    for label in labels {
+        remove_subname(storage, (user_id.to_string(), label))?;
+    }
+    SUBNAME_POLICIES.remove(storage, user_id.to_string());
+
+    Ok(())
+}
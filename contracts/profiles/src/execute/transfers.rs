@@ -0,0 +1,205 @@
+use cosmwasm_std::{Addr, Empty, StdResult, Storage};
+use cosmwasm_std::{DepsMut, MessageInfo, Response};
+use cw_ownable::assert_owner;
+use utils::elements::Profile;
+
+use crate::error::ContractError;
+use crate::state::{
+    ADDRESS_TO_PROFILE, CONFIG, LINKED_ADDRESSES, PENDING_TRANSFERS, PUBKEY_TO_ADDRESS,
+    RECEIVER_TRANSFERS, USERID_TO_ADDRESS,
+};
+
+use super::profiles::{claim_pubkey, remove_profile_data};
+use super::subnames::clear_subnames;
+use super::user_ids::assert_user_id_owner;
+
+pub(crate) fn transfer_user_id(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: Addr,
+    user_id: String,
+    to: Addr,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+    assert_user_id_owner(deps.storage, &address, &user_id)?;
+    if to == address {
+        return Err(ContractError::SelfTransfer {});
+    }
+
+    save_pending_transfer(deps.storage, &user_id, &to)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "transfer_user_id")
+        .add_attribute("user_id", user_id)
+        .add_attribute("from", address)
+        .add_attribute("to", to))
+}
+
+pub(crate) fn cancel_user_id_transfer(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: Addr,
+    user_id: String,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+    assert_user_id_owner(deps.storage, &address, &user_id)?;
+
+    if !PENDING_TRANSFERS.has(deps.storage, user_id.clone()) {
+        return Err(ContractError::NoPendingTransfer {});
+    }
+    remove_pending_transfer(deps.storage, &user_id)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "cancel_user_id_transfer")
+        .add_attribute("user_id", user_id))
+}
+
+pub(crate) fn accept_user_id(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: Addr,
+    user_id: String,
+    pubkey: Option<String>,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+
+    match PENDING_TRANSFERS.may_load(deps.storage, user_id.clone())? {
+        Some(to) if to == address => {}
+        _ => return Err(ContractError::NoPendingTransfer {}),
+    }
+    let from = USERID_TO_ADDRESS.load(deps.storage, user_id.clone())?;
+    reassign_user_id(deps.storage, &user_id, &from, &address, pubkey)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "accept_user_id")
+        .add_attribute("user_id", user_id)
+        .add_attribute("from", from)
+        .add_attribute("to", address))
+}
+
+pub(crate) fn move_user_id(
+    deps: DepsMut,
+    info: MessageInfo,
+    user_id: String,
+    from: Addr,
+    to: Addr,
+    pubkey: Option<String>,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+    assert_user_id_owner(deps.storage, &from, &user_id)?;
+    if to == from {
+        return Err(ContractError::SelfTransfer {});
+    }
+    // A sale must not take the seller's profile down with its last user id.
+    let seller = ADDRESS_TO_PROFILE.load(deps.storage, from.clone())?;
+    if seller.user_id == user_id && seller.aliases.is_empty() {
+        return Err(ContractError::SoleUserId {});
+    }
+
+    reassign_user_id(deps.storage, &user_id, &from, &to, pubkey)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "move_user_id")
+        .add_attribute("user_id", user_id)
+        .add_attribute("from", from)
+        .add_attribute("to", to))
+}
+
+// Moves `user_id` from `from` to `to`. The receiver gets it as an alias if it already has a profile,
+// otherwise a new profile is created with the receiver's own pubkey. Subnames handed out by the
+// previous owner are revoked.
+fn reassign_user_id(
+    storage: &mut dyn Storage,
+    user_id: &str,
+    from: &Addr,
+    to: &Addr,
+    pubkey: Option<String>,
+) -> Result<(), ContractError> {
+    let config = CONFIG.load(storage)?;
+    let receiver = match ADDRESS_TO_PROFILE.may_load(storage, to.clone())? {
+        Some(mut profile) => {
+            if profile.private {
+                return Err(ContractError::PrivateProfile {});
+            }
+            if profile.aliases.len() as u64 >= config.max_aliases {
+                return Err(ContractError::TooManyAliases {
+                    max: config.max_aliases,
+                });
+            }
+            profile.aliases.push(user_id.to_string());
+            profile
+        }
+        None => {
+            let pubkey = pubkey.ok_or(ContractError::PubkeyRequired {})?;
+            claim_pubkey(storage, &pubkey, to)?;
+            PUBKEY_TO_ADDRESS.save(storage, pubkey.clone(), to)?;
+            Profile {
+                user_id: user_id.to_string(),
+                pubkey,
+                aliases: vec![],
+                active: true,
+                hidden: false,
+                private: false,
+                linked_addresses: vec![],
+            }
+        }
+    };
+
+    detach_user_id(storage, user_id, from)?;
+    clear_subnames(storage, user_id)?;
+    ADDRESS_TO_PROFILE.save(storage, to.clone(), &receiver)?;
+    USERID_TO_ADDRESS.save(storage, user_id.to_string(), to)?;
+    remove_pending_transfer(storage, user_id)?;
+
+    Ok(())
+}
+
+// Takes `user_id` off the profile of `owner`. If it was the primary id, the first alias is promoted,
+// or the profile is removed when it has no aliases left.
+pub(crate) fn detach_user_id(
+    storage: &mut dyn Storage,
+    user_id: &str,
+    owner: &Addr,
+) -> StdResult<()> {
+    let mut profile = ADDRESS_TO_PROFILE.load(storage, owner.clone())?;
+    match profile.aliases.iter().position(|a| a == user_id) {
+        Some(index) => {
+            profile.aliases.remove(index);
+            ADDRESS_TO_PROFILE.save(storage, owner.clone(), &profile)?;
+        }
+        None if !profile.aliases.is_empty() => {
+            profile.user_id = profile.aliases.remove(0);
+            ADDRESS_TO_PROFILE.save(storage, owner.clone(), &profile)?;
+        }
+        None => {
+            ADDRESS_TO_PROFILE.remove(storage, owner.clone());
+            PUBKEY_TO_ADDRESS.remove(storage, profile.pubkey);
+            for linked in profile.linked_addresses {
+                LINKED_ADDRESSES.remove(storage, linked);
+            }
+            remove_profile_data(storage, owner)?;
+        }
+    }
+
+    Ok(())
+}
+
+// Offers `user_id` to `to`, replacing any previous offer.
+pub(crate) fn save_pending_transfer(
+    storage: &mut dyn Storage,
+    user_id: &str,
+    to: &Addr,
+) -> StdResult<()> {
+    remove_pending_transfer(storage, user_id)?;
+    PENDING_TRANSFERS.save(storage, user_id.to_string(), to)?;
+    RECEIVER_TRANSFERS.save(storage, (to.clone(), user_id.to_string()), &Empty {})
+}
+
+pub(crate) fn remove_pending_transfer(storage: &mut dyn Storage, user_id: &str) -> StdResult<()> {
+    if let Some(to) = PENDING_TRANSFERS.may_load(storage, user_id.to_string())? {
+        RECEIVER_TRANSFERS.remove(storage, (to, user_id.to_string()));
+        PENDING_TRANSFERS.remove(storage, user_id.to_string());
+    }
+
+    Ok(())
+}
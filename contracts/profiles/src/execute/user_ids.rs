@@ -0,0 +1,390 @@
+use cosmwasm_std::{Addr, Empty, StdError, StdResult, Storage, Timestamp};
+use cosmwasm_std::{DepsMut, Env, MessageInfo, Response};
+use cw_ownable::assert_owner;
+use sha2::{Digest, Sha256};
+use utils::elements::Profile;
+
+use crate::error::ContractError;
+use crate::state::{
+    Config, Reservation, ADDRESS_TO_PROFILE, CONFIG, LAST_USER_ID_CHANGES, PRIVATE_USER_IDS,
+    PUBLIC_USER_ID_HASHES, REDIRECTS, RESERVED_USER_IDS, USERID_EXPIRATIONS, USERID_TO_ADDRESS,
+};
+
+use super::subnames::clear_subnames;
+use super::transfers::{detach_user_id, remove_pending_transfer};
+
+pub(crate) fn change_user_id(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    address: Addr,
+    user_id: String,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+
+    let config = CONFIG.load(deps.storage)?;
+    let mut profile = ADDRESS_TO_PROFILE.load(deps.storage, address.clone())?;
+    if profile.private {
+        return Err(ContractError::PrivateProfile {});
+    }
+    if let Some(last_change) = LAST_USER_ID_CHANGES.may_load(deps.storage, address.clone())? {
+        let until = last_change.plus_seconds(config.change_cooldown);
+        if env.block.time < until {
+            return Err(ContractError::ChangeCooldown { until });
+        }
+    }
+
+    // Switching to one of the profile's own aliases swaps it with the primary user id.
+    match profile.aliases.iter().position(|a| *a == user_id) {
+        Some(index) => profile.aliases[index] = profile.user_id.clone(),
+        None => {
+            validate_user_id(&user_id)?;
+            assert_user_id_available(deps.storage, &env, &user_id, &address)?;
+            // The new id keeps the registration term of the one it replaces.
+            let expires = USERID_EXPIRATIONS.may_load(deps.storage, profile.user_id.clone())?;
+            clear_user_id(deps.storage, &env, profile.user_id.clone())?;
+            // Messages sent to the old id keep reaching the profile for a while.
+            if config.redirect_period > 0 {
+                save_reservation(
+                    deps.storage,
+                    &profile.user_id,
+                    &Reservation {
+                        until: env.block.time.plus_seconds(config.redirect_period),
+                        redirect: Some(address.clone()),
+                    },
+                )?;
+            }
+            save_public_user_id(deps.storage, &env, &user_id, &address)?;
+            if let Some(expires) = expires {
+                USERID_EXPIRATIONS.save(deps.storage, user_id.clone(), &expires)?;
+            }
+        }
+    }
+    profile.user_id = user_id;
+    ADDRESS_TO_PROFILE.save(deps.storage, address.clone(), &profile)?;
+    LAST_USER_ID_CHANGES.save(deps.storage, address.clone(), &env.block.time)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "change_user_id")
+        .add_attribute("address", address))
+}
+
+pub(crate) fn add_alias(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    address: Addr,
+    alias: String,
+    term: Option<u64>,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+
+    let config = CONFIG.load(deps.storage)?;
+    validate_user_id(&alias)?;
+    // Reclaiming an expired alias can change the profile, so it is loaded afterwards.
+    assert_user_id_available(deps.storage, &env, &alias, &address)?;
+    let mut profile = ADDRESS_TO_PROFILE.load(deps.storage, address.clone())?;
+    if profile.private {
+        return Err(ContractError::PrivateProfile {});
+    }
+    if profile.aliases.len() as u64 >= config.max_aliases {
+        return Err(ContractError::TooManyAliases {
+            max: config.max_aliases,
+        });
+    }
+
+    save_public_user_id(deps.storage, &env, &alias, &address)?;
+    set_expiration(deps.storage, &env, &alias, term)?;
+    profile.aliases.push(alias.clone());
+    ADDRESS_TO_PROFILE.save(deps.storage, address.clone(), &profile)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "add_alias")
+        .add_attribute("address", address)
+        .add_attribute("alias", alias))
+}
+
+pub(crate) fn remove_alias(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    address: Addr,
+    alias: String,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+
+    let config = CONFIG.load(deps.storage)?;
+    let mut profile = ADDRESS_TO_PROFILE.load(deps.storage, address.clone())?;
+    let index = profile
+        .aliases
+        .iter()
+        .position(|a| *a == alias)
+        .ok_or(ContractError::NotAnAlias {})?;
+
+    profile.aliases.remove(index);
+    release_user_id(deps.storage, &env, &config, alias.clone())?;
+    ADDRESS_TO_PROFILE.save(deps.storage, address.clone(), &profile)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_alias")
+        .add_attribute("address", address)
+        .add_attribute("alias", alias))
+}
+
+pub(crate) fn renew_user_id(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    user_id: String,
+    term: u64,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+
+    let config = CONFIG.load(deps.storage)?;
+    USERID_TO_ADDRESS.load(deps.storage, user_id.clone())?;
+    let expires = USERID_EXPIRATIONS
+        .may_load(deps.storage, user_id.clone())?
+        .ok_or(ContractError::NotRenewable {})?;
+    if env.block.time >= expires.plus_seconds(config.grace_period) {
+        return Err(ContractError::UserIdExpired {});
+    }
+
+    let expires = expires.max(env.block.time).plus_seconds(term);
+    USERID_EXPIRATIONS.save(deps.storage, user_id.clone(), &expires)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "renew_user_id")
+        .add_attribute("user_id", user_id)
+        .add_attribute("expires", expires.to_string()))
+}
+
+// Top-level user ids and subname labels can't contain '.', which separates a label from its parent.
+pub(crate) fn validate_user_id(user_id: &str) -> Result<(), ContractError> {
+    if user_id.is_empty() || user_id.contains('.') {
+        return Err(ContractError::InvalidUserId {});
+    }
+
+    Ok(())
+}
+
+pub(crate) fn assert_user_id_owner(
+    storage: &dyn Storage,
+    address: &Addr,
+    user_id: &str,
+) -> Result<(), ContractError> {
+    match USERID_TO_ADDRESS.may_load(storage, user_id.to_string())? {
+        Some(owner) if owner == *address => Ok(()),
+        _ => Err(ContractError::NotUserIdOwner {}),
+    }
+}
+
+// Checks that nobody holds the user id and that it is not reserved after a recent deletion, unless
+// it redirects to `address`. Returns the previous holder of a user id whose term and grace period
+// are over, which must be reclaimed.
+pub(crate) fn check_user_id_available(
+    storage: &dyn Storage,
+    env: &Env,
+    user_id: &str,
+    address: &Addr,
+) -> Result<Option<Addr>, ContractError> {
+    if PRIVATE_USER_IDS.has(storage, private_user_id_hash(env, user_id)) {
+        return Err(ContractError::UserIdAlreadyExists {});
+    }
+
+    let mut expired_holder = None;
+    if let Some(owner) = USERID_TO_ADDRESS.may_load(storage, user_id.to_string())? {
+        let config = CONFIG.load(storage)?;
+        match grace_end(storage, &config, user_id)? {
+            Some(grace_end) if env.block.time >= grace_end => expired_holder = Some(owner),
+            _ => return Err(ContractError::UserIdAlreadyExists {}),
+        }
+    }
+
+    if let Some(reservation) = RESERVED_USER_IDS.may_load(storage, user_id.to_string())? {
+        if env.block.time < reservation.until && reservation.redirect.as_ref() != Some(address) {
+            return Err(ContractError::UserIdReserved {
+                until: reservation.until,
+            });
+        }
+    }
+
+    Ok(expired_holder)
+}
+
+// The key of the user id in `PRIVATE_USER_IDS`: the hex encoded sha256 of this contract's address
+// followed by the user id.
+fn private_user_id_hash(env: &Env, user_id: &str) -> String {
+    let hash = Sha256::new()
+        .chain_update(env.contract.address.as_bytes())
+        .chain_update(user_id.as_bytes())
+        .finalize();
+
+    hash.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+// Registers a new public user id, indexing its hash so it can't be registered privately as well.
+pub(crate) fn save_public_user_id(
+    storage: &mut dyn Storage,
+    env: &Env,
+    user_id: &str,
+    address: &Addr,
+) -> StdResult<()> {
+    USERID_TO_ADDRESS.save(storage, user_id.to_string(), address)?;
+    PUBLIC_USER_ID_HASHES.save(storage, private_user_id_hash(env, user_id), &Empty {})
+}
+
+// Like `check_user_id_available`, and reclaims the user id for `address`.
+fn assert_user_id_available(
+    storage: &mut dyn Storage,
+    env: &Env,
+    user_id: &str,
+    address: &Addr,
+) -> Result<(), ContractError> {
+    let expired_holder = check_user_id_available(storage, env, user_id, address)?;
+    reclaim_user_id(storage, env, user_id, expired_holder)?;
+
+    Ok(())
+}
+
+// Takes a user id that passed `check_user_id_available` off its expired previous holder. A
+// reservation left is over or redirects to the new holder, which takes the user id back.
+pub(crate) fn reclaim_user_id(
+    storage: &mut dyn Storage,
+    env: &Env,
+    user_id: &str,
+    expired_holder: Option<Addr>,
+) -> StdResult<()> {
+    if let Some(holder) = expired_holder {
+        detach_user_id(storage, user_id, &holder)?;
+        clear_user_id(storage, env, user_id.to_string())?;
+    }
+    remove_reservation(storage, user_id)?;
+
+    Ok(())
+}
+
+pub(crate) fn set_expiration(
+    storage: &mut dyn Storage,
+    env: &Env,
+    user_id: &str,
+    term: Option<u64>,
+) -> StdResult<()> {
+    match term {
+        Some(term) => USERID_EXPIRATIONS.save(
+            storage,
+            user_id.to_string(),
+            &env.block.time.plus_seconds(term),
+        ),
+        None => {
+            USERID_EXPIRATIONS.remove(storage, user_id.to_string());
+            Ok(())
+        }
+    }
+}
+
+pub(crate) fn grace_end(
+    storage: &dyn Storage,
+    config: &Config,
+    user_id: &str,
+) -> StdResult<Option<Timestamp>> {
+    let expires = USERID_EXPIRATIONS.may_load(storage, user_id.to_string())?;
+
+    Ok(expires.map(|expires| expires.plus_seconds(config.grace_period)))
+}
+
+// All user ids of the profile if each of them is past its grace period, None otherwise.
+pub(crate) fn lapsed_user_ids(
+    storage: &dyn Storage,
+    env: &Env,
+    profile: &Profile,
+) -> StdResult<Option<Vec<String>>> {
+    let config = CONFIG.load(storage)?;
+    let user_ids: Vec<String> = std::iter::once(&profile.user_id)
+        .chain(&profile.aliases)
+        .cloned()
+        .collect();
+    for user_id in &user_ids {
+        match grace_end(storage, &config, user_id)? {
+            Some(grace_end) if env.block.time >= grace_end => {}
+            _ => return Ok(None),
+        }
+    }
+
+    Ok(Some(user_ids))
+}
+
+// Expired user ids don't resolve, even during their grace period.
+pub(crate) fn assert_not_expired(storage: &dyn Storage, env: &Env, user_id: &str) -> StdResult<()> {
+    if let Some(expires) = USERID_EXPIRATIONS.may_load(storage, user_id.to_string())? {
+        if env.block.time >= expires {
+            return Err(StdError::generic_err(format!(
+                "User ID {} has expired",
+                user_id
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+// Frees a user id, keeping it reserved for the configured cooldown.
+pub(crate) fn release_user_id(
+    storage: &mut dyn Storage,
+    env: &Env,
+    config: &Config,
+    user_id: String,
+) -> StdResult<()> {
+    clear_user_id(storage, env, user_id.clone())?;
+    if config.user_id_cooldown > 0 {
+        save_reservation(
+            storage,
+            &user_id,
+            &Reservation {
+                until: env.block.time.plus_seconds(config.user_id_cooldown),
+                redirect: None,
+            },
+        )?;
+    }
+
+    Ok(())
+}
+
+// Removes a user id together with its subnames and any pending transfer.
+pub(crate) fn clear_user_id(
+    storage: &mut dyn Storage,
+    env: &Env,
+    user_id: String,
+) -> StdResult<()> {
+    clear_subnames(storage, &user_id)?;
+    PUBLIC_USER_ID_HASHES.remove(storage, private_user_id_hash(env, &user_id));
+    remove_pending_transfer(storage, &user_id)?;
+    USERID_EXPIRATIONS.remove(storage, user_id.clone());
+    USERID_TO_ADDRESS.remove(storage, user_id);
+
+    Ok(())
+}
+
+pub(crate) fn save_reservation(
+    storage: &mut dyn Storage,
+    user_id: &str,
+    reservation: &Reservation,
+) -> StdResult<()> {
+    remove_reservation(storage, user_id)?;
+    RESERVED_USER_IDS.save(storage, user_id.to_string(), reservation)?;
+    if let Some(redirect) = &reservation.redirect {
+        REDIRECTS.save(storage, (redirect.clone(), user_id.to_string()), &Empty {})?;
+    }
+
+    Ok(())
+}
+
+fn remove_reservation(storage: &mut dyn Storage, user_id: &str) -> StdResult<()> {
+    if let Some(reservation) = RESERVED_USER_IDS.may_load(storage, user_id.to_string())? {
+        if let Some(redirect) = reservation.redirect {
+            REDIRECTS.remove(storage, (redirect, user_id.to_string()));
+        }
+        RESERVED_USER_IDS.remove(storage, user_id.to_string());
+    }
+
+    Ok(())
+}
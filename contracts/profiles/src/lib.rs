@@ -1,3 +1,7 @@
 pub mod contract;
 pub mod error;
-pub mod state;
\ No newline at end of file
+mod execute;
+pub mod state;
+
+#[cfg(test)]
+mod tests;
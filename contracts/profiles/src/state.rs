@@ -1,6 +1,86 @@
-use cosmwasm_std::Addr;
-use cw_storage_plus::Map;
-use utils::elements::Profile;
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Binary, Empty, Timestamp};
+use cw_storage_plus::{Item, Map};
+use utils::elements::{KeyBackup, KeyShare, Profile, Subname};
 
+pub const CONFIG: Item<Config> = Item::new("config");
 pub const USERID_TO_ADDRESS: Map<String, Addr> = Map::new("address_mapping");
-pub const ADDRESS_TO_PROFILE: Map<Addr, Profile> = Map::new("profile_mapping");
\ No newline at end of file
+pub const ADDRESS_TO_PROFILE: Map<Addr, Profile> = Map::new("profile_mapping");
+/// Blinded user ids of private profiles, keyed by their hash.
+pub const PRIVATE_USER_IDS: Map<String, Addr> = Map::new("private_user_ids");
+/// Hashes of the registered public user ids, computed like the keys of `PRIVATE_USER_IDS`, so a
+/// user id can't be held both publicly and privately.
+pub const PUBLIC_USER_ID_HASHES: Map<String, Empty> = Map::new("public_user_id_hashes");
+/// Reverse index of profile pubkeys. A pubkey belongs to a single profile.
+pub const PUBKEY_TO_ADDRESS: Map<String, Addr> = Map::new("pubkey_mapping");
+/// Expiry of user ids registered for a limited term. Ids without an entry never expire.
+pub const USERID_EXPIRATIONS: Map<String, Timestamp> = Map::new("userid_expirations");
+/// User ids released by a deleted profile or replaced by a user id change.
+pub const RESERVED_USER_IDS: Map<String, Reservation> = Map::new("reserved_user_ids");
+/// Reserved user ids keyed by the profile they redirect to.
+pub const REDIRECTS: Map<(Addr, String), Empty> = Map::new("redirects");
+/// Profiles imported from the legacy directory, with the time of the import.
+pub const IMPORTED_PROFILES: Map<Addr, Timestamp> = Map::new("imported_profiles");
+/// Time of the last primary user id change of each profile.
+pub const LAST_USER_ID_CHANGES: Map<Addr, Timestamp> = Map::new("last_user_id_changes");
+/// External addresses linked to a profile, mapped to the profile address.
+pub const LINKED_ADDRESSES: Map<String, Addr> = Map::new("linked_addresses");
+/// Number of links made by each profile, part of the link challenge so signatures can't be replayed.
+pub const LINK_NONCES: Map<Addr, u64> = Map::new("link_nonces");
+/// Addresses allowed to attest claims about profiles, with the pubkey their attestations are signed
+/// with.
+pub const VERIFIERS: Map<Addr, Binary> = Map::new("verifiers");
+/// Attestation expiries keyed by (profile address, verifier, kind).
+pub const ATTESTATIONS: Map<(Addr, Addr, String), Timestamp> = Map::new("attestations");
+/// Number of revocations keyed by (profile address, verifier), part of the attestation challenge so
+/// a revoked attestation can't be submitted again.
+pub const ATTESTATION_NONCES: Map<(Addr, Addr), u64> = Map::new("attestation_nonces");
+pub const KEY_BACKUPS: Map<Addr, KeyBackup> = Map::new("key_backups");
+pub const KEY_SHARES: Map<Addr, KeyShares> = Map::new("key_shares");
+/// Pending key recoveries keyed by the profile address.
+pub const KEY_RECOVERIES: Map<Addr, KeyRecovery> = Map::new("key_recoveries");
+/// Subnames keyed by (parent user id, label).
+pub const SUBNAMES: Map<(String, String), Subname> = Map::new("subnames");
+/// Subnames keyed by (holder, parent user id, label).
+pub const HOLDER_SUBNAMES: Map<(Addr, String, String), Empty> = Map::new("holder_subnames");
+/// User ids offered by their owner to another address, waiting for that address to accept.
+pub const PENDING_TRANSFERS: Map<String, Addr> = Map::new("pending_transfers");
+/// Pending transfers keyed by (receiver, user id).
+pub const RECEIVER_TRANSFERS: Map<(Addr, String), Empty> = Map::new("receiver_transfers");
+pub const SUBNAME_POLICIES: Map<String, SubnamePolicy> = Map::new("subname_policies");
+
+#[cw_serde]
+#[derive(Default)]
+pub struct SubnamePolicy {
+    pub holders_can_change_pubkey: bool,
+}
+
+#[cw_serde]
+pub struct Reservation {
+    /// The user id becomes available again at this time.
+    pub until: Timestamp,
+    /// Profile the user id keeps resolving to until then, set when it was replaced by a change.
+    pub redirect: Option<Addr>,
+}
+
+#[cw_serde]
+pub struct KeyShares {
+    pub shares: Vec<KeyShare>,
+    pub threshold: u64,
+}
+
+#[cw_serde]
+pub struct KeyRecovery {
+    pub new_pubkey: String,
+    /// Shares re-encrypted to `new_pubkey`, one per approving guardian.
+    pub shares: Vec<KeyShare>,
+}
+
+#[cw_serde]
+pub struct Config {
+    pub user_id_cooldown: u64,
+    pub max_aliases: u64,
+    pub grace_period: u64,
+    pub change_cooldown: u64,
+    pub redirect_period: u64,
+}
\ No newline at end of file
@@ -0,0 +1,42 @@
+use cosmwasm_std::testing::mock_env;
+use cosmwasm_std::{from_json, Addr};
+use utils::msg::ProfilesExecuteMsg as ExecuteMsg;
+use utils::query::{ProfileInfo, ProfilesQueryMsg as QueryMsg};
+
+use super::{add_alias, create_profile, exec, setup};
+use crate::contract::query;
+use crate::error::ContractError;
+use crate::state::USERID_TO_ADDRESS;
+
+#[test]
+fn profiles_have_at_most_max_aliases() {
+    let mut deps = setup();
+    create_profile(&mut deps, "alice", "alice");
+    for alias in ["ally1", "ally2", "ally3", "ally4", "ally5"] {
+        add_alias(&mut deps, "alice", alias);
+    }
+
+    let msg = ExecuteMsg::AddAlias {
+        address: Addr::unchecked("alice"),
+        alias: "ally6".to_string(),
+        term: None,
+    };
+    let err = exec(&mut deps, msg).unwrap_err();
+    assert_eq!(err, ContractError::TooManyAliases { max: 5 });
+    assert!(!USERID_TO_ADDRESS.has(&deps.storage, "ally6".to_string()));
+}
+
+#[test]
+fn aliases_resolve_to_the_primary_user_id() {
+    let mut deps = setup();
+    create_profile(&mut deps, "alice", "alice");
+    add_alias(&mut deps, "alice", "ally");
+
+    let msg = QueryMsg::UserInfo {
+        user_id: "ally".to_string(),
+    };
+    let info: ProfileInfo = from_json(query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(info.address, Addr::unchecked("alice"));
+    assert_eq!(info.user_id, "alice");
+    assert_eq!(info.aliases, vec!["ally".to_string()]);
+}
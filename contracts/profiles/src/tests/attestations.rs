@@ -0,0 +1,130 @@
+use cosmwasm_std::testing::mock_env;
+use cosmwasm_std::{from_json, Addr, Binary, Timestamp};
+use k256::ecdsa::{signature::Signer, Signature, SigningKey};
+use utils::msg::ProfilesExecuteMsg as ExecuteMsg;
+use utils::query::{AttestationChallengeResponse, ProfilesQueryMsg as QueryMsg};
+
+use super::{create_profile, exec, setup, TestDeps};
+use crate::contract::query;
+use crate::error::ContractError;
+use crate::state::ATTESTATIONS;
+
+fn verifier_key() -> SigningKey {
+    SigningKey::from_bytes(&[7u8; 32].into()).unwrap()
+}
+
+fn sign(key: &SigningKey, kind: &str, address: &str, expires: Timestamp) -> Binary {
+    sign_nonce(key, kind, address, expires, 0)
+}
+
+fn sign_nonce(
+    key: &SigningKey,
+    kind: &str,
+    address: &str,
+    expires: Timestamp,
+    nonce: u64,
+) -> Binary {
+    let env = mock_env();
+    let challenge = format!(
+        "Attest {} about profile {} until {} on {} {} #{}",
+        kind,
+        address,
+        expires.seconds(),
+        env.block.chain_id,
+        env.contract.address,
+        nonce
+    );
+    let signature: Signature = key.sign(challenge.as_bytes());
+    Binary::from(&signature.to_bytes()[..])
+}
+
+fn setup_verifier() -> TestDeps {
+    let mut deps = setup();
+    create_profile(&mut deps, "alice", "alice");
+    let pubkey = verifier_key().verifying_key().to_encoded_point(true);
+    exec(
+        &mut deps,
+        ExecuteMsg::AddVerifier {
+            verifier: Addr::unchecked("verifier"),
+            pubkey: Binary::from(pubkey.as_bytes()),
+        },
+    )
+    .unwrap();
+    deps
+}
+
+fn attest(expires: Timestamp, signature: Binary) -> ExecuteMsg {
+    ExecuteMsg::Attest {
+        address: Addr::unchecked("alice"),
+        verifier: Addr::unchecked("verifier"),
+        kind: "kyc".to_string(),
+        expires,
+        signature,
+    }
+}
+
+#[test]
+fn attestations_must_be_signed_by_the_verifier() {
+    let mut deps = setup_verifier();
+    let expires = mock_env().block.time.plus_seconds(100);
+
+    let other_key = SigningKey::from_bytes(&[8u8; 32].into()).unwrap();
+    let forged = sign(&other_key, "kyc", "alice", expires);
+    let err = exec(&mut deps, attest(expires, forged)).unwrap_err();
+    assert_eq!(err, ContractError::InvalidAttestationSignature {});
+    // Signed for another profile.
+    let misused = sign(&verifier_key(), "kyc", "bob", expires);
+    let err = exec(&mut deps, attest(expires, misused)).unwrap_err();
+    assert_eq!(err, ContractError::InvalidAttestationSignature {});
+
+    let signature = sign(&verifier_key(), "kyc", "alice", expires);
+    exec(&mut deps, attest(expires, signature)).unwrap();
+    let key = (
+        Addr::unchecked("alice"),
+        Addr::unchecked("verifier"),
+        "kyc".to_string(),
+    );
+    assert_eq!(ATTESTATIONS.load(&deps.storage, key).unwrap(), expires);
+}
+
+#[test]
+fn attestations_must_expire_in_the_future() {
+    let mut deps = setup_verifier();
+    let expires = mock_env().block.time;
+
+    let signature = sign(&verifier_key(), "kyc", "alice", expires);
+    let err = exec(&mut deps, attest(expires, signature)).unwrap_err();
+    assert_eq!(err, ContractError::AttestationExpired {});
+}
+
+#[test]
+fn a_revoked_attestation_cant_be_submitted_again() {
+    let mut deps = setup_verifier();
+    let expires = mock_env().block.time.plus_seconds(100);
+    let signature = sign(&verifier_key(), "kyc", "alice", expires);
+    exec(&mut deps, attest(expires, signature.clone())).unwrap();
+
+    exec(
+        &mut deps,
+        ExecuteMsg::RevokeAttestation {
+            address: Addr::unchecked("alice"),
+            verifier: Addr::unchecked("verifier"),
+            kind: "kyc".to_string(),
+        },
+    )
+    .unwrap();
+
+    let err = exec(&mut deps, attest(expires, signature)).unwrap_err();
+    assert_eq!(err, ContractError::InvalidAttestationSignature {});
+    let msg = QueryMsg::AttestationChallenge {
+        address: Addr::unchecked("alice"),
+        verifier: Addr::unchecked("verifier"),
+        kind: "kyc".to_string(),
+        expires,
+    };
+    let res: AttestationChallengeResponse =
+        from_json(query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert!(res.challenge.ends_with(" #1"));
+    let signature = sign_nonce(&verifier_key(), "kyc", "alice", expires, 1);
+    exec(&mut deps, attest(expires, signature)).unwrap();
+}
@@ -0,0 +1,41 @@
+use cosmwasm_std::testing::mock_env;
+use cosmwasm_std::{from_json, Addr};
+use utils::msg::ProfilesExecuteMsg as ExecuteMsg;
+use utils::query::{DirectoryResponse, ProfilesQueryMsg as QueryMsg};
+
+use super::{create_profile, exec, setup};
+use crate::contract::query;
+
+#[test]
+fn listing_stops_after_reading_a_bounded_number_of_user_ids() {
+    let mut deps = setup();
+    for i in 0..301 {
+        let address = format!("hidden{:03}", i);
+        create_profile(&mut deps, &address, &address);
+        exec(
+            &mut deps,
+            ExecuteMsg::SetDirectoryListing {
+                address: Addr::unchecked(address),
+                listed: false,
+            },
+        )
+        .unwrap();
+    }
+    create_profile(&mut deps, "visible", "visible");
+
+    let list = |start_after: Option<String>| -> DirectoryResponse {
+        let msg = QueryMsg::ListProfiles {
+            start_after,
+            limit: Some(10),
+        };
+        from_json(query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap()
+    };
+    let first = list(None);
+    assert!(first.entries.is_empty());
+    assert_eq!(first.next_start_after, Some("hidden299".to_string()));
+
+    let second = list(first.next_start_after);
+    assert_eq!(second.entries.len(), 1);
+    assert_eq!(second.entries[0].user_id, "visible");
+    assert_eq!(second.next_start_after, None);
+}
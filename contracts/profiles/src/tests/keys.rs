@@ -0,0 +1,135 @@
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::{Addr, Binary};
+use cw_ownable::OwnershipError;
+use utils::elements::KeyShare;
+use utils::msg::ProfilesExecuteMsg as ExecuteMsg;
+
+use super::{create_profile, exec, setup, TestDeps};
+use crate::contract::execute;
+use crate::error::ContractError;
+use crate::state::{KEY_BACKUPS, KEY_RECOVERIES};
+
+fn backup(data: &[u8], expected_version: u64) -> ExecuteMsg {
+    ExecuteMsg::SetKeyBackup {
+        address: Addr::unchecked("alice"),
+        data: Binary::from(data),
+        expected_version,
+    }
+}
+
+#[test]
+fn key_backups_are_replaced_only_from_the_current_version() {
+    let mut deps = setup();
+    create_profile(&mut deps, "alice", "alice");
+
+    let err = exec(&mut deps, backup(b"first", 1)).unwrap_err();
+    assert_eq!(err, ContractError::BackupVersionMismatch { current: 0 });
+    exec(&mut deps, backup(b"first", 0)).unwrap();
+    exec(&mut deps, backup(b"second", 1)).unwrap();
+
+    // A write based on the first version would overwrite the second.
+    let err = exec(&mut deps, backup(b"stale", 1)).unwrap_err();
+    assert_eq!(err, ContractError::BackupVersionMismatch { current: 2 });
+    let stored = KEY_BACKUPS
+        .load(&deps.storage, Addr::unchecked("alice"))
+        .unwrap();
+    assert_eq!(stored.data, Binary::from(b"second".as_slice()));
+    assert_eq!(stored.version, 2);
+}
+
+#[test]
+fn key_backups_need_a_profile() {
+    let mut deps = setup();
+
+    exec(&mut deps, backup(b"first", 0)).unwrap_err();
+    assert!(!KEY_BACKUPS.has(&deps.storage, Addr::unchecked("alice")));
+}
+
+fn share(guardian: &str) -> KeyShare {
+    KeyShare {
+        guardian: Addr::unchecked(guardian),
+        share: Binary::from(guardian.as_bytes()),
+    }
+}
+
+fn set_shares(guardians: &[&str], threshold: u64) -> ExecuteMsg {
+    ExecuteMsg::SetKeyShares {
+        address: Addr::unchecked("alice"),
+        shares: guardians.iter().map(|guardian| share(guardian)).collect(),
+        threshold,
+    }
+}
+
+fn submit(guardian: &str) -> ExecuteMsg {
+    ExecuteMsg::SubmitKeyShare {
+        address: Addr::unchecked("alice"),
+        guardian: Addr::unchecked(guardian),
+        share: Binary::from(b"re-encrypted".as_slice()),
+    }
+}
+
+/// alice, with the profiles of her guardians g1 and g2.
+fn guardians_setup() -> TestDeps {
+    let mut deps = setup();
+    for address in ["alice", "g1", "g2"] {
+        create_profile(&mut deps, address, address);
+    }
+    deps
+}
+
+#[test]
+fn key_share_thresholds_must_be_reachable() {
+    let mut deps = guardians_setup();
+
+    let err = exec(&mut deps, set_shares(&["g1", "g2"], 0)).unwrap_err();
+    assert_eq!(err, ContractError::InvalidShareThreshold {});
+    let err = exec(&mut deps, set_shares(&["g1", "g2"], 3)).unwrap_err();
+    assert_eq!(err, ContractError::InvalidShareThreshold {});
+    exec(&mut deps, set_shares(&["g1", "g2"], 2)).unwrap();
+}
+
+#[test]
+fn key_shares_go_to_distinct_guardians_with_profiles() {
+    let mut deps = guardians_setup();
+
+    for guardians in [&["g1", "alice"][..], &["g1", "g1"], &["g1", "nobody"]] {
+        let err = exec(&mut deps, set_shares(guardians, 1)).unwrap_err();
+        assert_eq!(err, ContractError::InvalidGuardian {});
+    }
+}
+
+#[test]
+fn only_guardians_submit_key_shares() {
+    let mut deps = guardians_setup();
+    exec(&mut deps, set_shares(&["g1", "g2"], 2)).unwrap();
+    let request = ExecuteMsg::RequestKeyRecovery {
+        address: Addr::unchecked("alice"),
+        new_pubkey: "alice-new-pubkey".to_string(),
+    };
+    exec(&mut deps, request).unwrap();
+
+    let err = exec(&mut deps, submit("mallory")).unwrap_err();
+    assert_eq!(err, ContractError::NotGuardian {});
+    // Only the controller executes on behalf of guardians.
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("g1", &[]),
+        submit("g1"),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Ownership(OwnershipError::NotOwner));
+
+    exec(&mut deps, submit("g1")).unwrap();
+    exec(&mut deps, submit("g1")).unwrap();
+    let recovery = KEY_RECOVERIES
+        .load(&deps.storage, Addr::unchecked("alice"))
+        .unwrap();
+    assert_eq!(recovery.shares.len(), 1);
+
+    // New shares cancel the pending recovery.
+    exec(&mut deps, set_shares(&["g1"], 1)).unwrap();
+    assert!(!KEY_RECOVERIES.has(&deps.storage, Addr::unchecked("alice")));
+    let err = exec(&mut deps, submit("g2")).unwrap_err();
+    assert_eq!(err, ContractError::NotGuardian {});
+}
@@ -0,0 +1,140 @@
+use bech32::{ToBase32, Variant};
+use cosmwasm_std::testing::mock_env;
+use cosmwasm_std::{from_json, Addr, Binary};
+use k256::ecdsa::{signature::Signer, Signature, SigningKey};
+use ripemd::Ripemd160;
+use sha2::{Digest, Sha256};
+use sha3::Keccak256;
+use utils::msg::ProfilesExecuteMsg as ExecuteMsg;
+use utils::query::{LinkChallengeResponse, ProfilesQueryMsg as QueryMsg};
+
+use super::{create_profile, exec, setup, TestDeps};
+use crate::contract::query;
+use crate::error::ContractError;
+use crate::state::{ADDRESS_TO_PROFILE, LINKED_ADDRESSES};
+
+fn key(seed: u8) -> SigningKey {
+    SigningKey::from_bytes(&[seed; 32].into()).unwrap()
+}
+
+fn pubkey(key: &SigningKey) -> Binary {
+    Binary::from(key.verifying_key().to_encoded_point(true).as_bytes())
+}
+
+fn cosmos_address(key: &SigningKey) -> String {
+    let hash = Ripemd160::digest(Sha256::digest(pubkey(key).as_slice()));
+    bech32::encode("cosmos", hash.to_base32(), Variant::Bech32).unwrap()
+}
+
+fn evm_address(key: &SigningKey) -> String {
+    let pubkey = key.verifying_key().to_encoded_point(false);
+    let hash = Keccak256::digest(&pubkey.as_bytes()[1..]);
+    let hex: String = hash[12..].iter().map(|b| format!("{:02x}", b)).collect();
+    format!("0x{}", hex)
+}
+
+fn challenge(deps: &TestDeps, external_address: &str) -> String {
+    let msg = QueryMsg::LinkChallenge {
+        address: Addr::unchecked("alice"),
+        external_address: external_address.to_string(),
+    };
+    let res: LinkChallengeResponse =
+        from_json(query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    res.challenge
+}
+
+fn cosmos_sign(key: &SigningKey, challenge: &str) -> Binary {
+    let signature: Signature = key.sign(challenge.as_bytes());
+    Binary::from(&signature.to_bytes()[..])
+}
+
+// Signs like `personal_sign`: r, s and v over the keccak256 of the prefixed challenge.
+fn evm_sign(key: &SigningKey, challenge: &str) -> Binary {
+    let mut hasher = Keccak256::new();
+    hasher.update(format!("\x19Ethereum Signed Message:\n{}", challenge.len()));
+    hasher.update(challenge);
+    let (signature, recovery_id) = key.sign_prehash_recoverable(&hasher.finalize()).unwrap();
+    let mut bytes = signature.to_bytes().to_vec();
+    bytes.push(recovery_id.to_byte() + 27);
+    Binary::from(bytes)
+}
+
+fn link(external_address: &str, pubkey: Option<Binary>, signature: Binary) -> ExecuteMsg {
+    ExecuteMsg::LinkAddress {
+        address: Addr::unchecked("alice"),
+        external_address: external_address.to_string(),
+        pubkey,
+        signature,
+    }
+}
+
+fn unlink(external_address: &str) -> ExecuteMsg {
+    ExecuteMsg::UnlinkAddress {
+        address: Addr::unchecked("alice"),
+        external_address: external_address.to_string(),
+    }
+}
+
+fn linked(deps: &TestDeps) -> Vec<String> {
+    ADDRESS_TO_PROFILE
+        .load(&deps.storage, Addr::unchecked("alice"))
+        .unwrap()
+        .linked_addresses
+}
+
+#[test]
+fn cosmos_addresses_are_linked_lowercase_with_a_matching_signature() {
+    let mut deps = setup();
+    create_profile(&mut deps, "alice", "alice");
+    let address = cosmos_address(&key(1));
+    let uppercase = address.to_uppercase();
+    let challenge = challenge(&deps, &uppercase);
+    assert!(challenge.starts_with(&format!("Link {} ", address)));
+
+    // Signed by another key, alone or together with its own pubkey.
+    let forged = cosmos_sign(&key(2), &challenge);
+    let err = exec(
+        &mut deps,
+        link(&address, Some(pubkey(&key(1))), forged.clone()),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidLinkSignature {});
+    let err = exec(&mut deps, link(&address, Some(pubkey(&key(2))), forged)).unwrap_err();
+    assert_eq!(err, ContractError::InvalidLinkSignature {});
+
+    let signature = cosmos_sign(&key(1), &challenge);
+    exec(
+        &mut deps,
+        link(&uppercase, Some(pubkey(&key(1))), signature.clone()),
+    )
+    .unwrap();
+    assert_eq!(linked(&deps), vec![address.clone()]);
+    assert!(LINKED_ADDRESSES.has(&deps.storage, address.clone()));
+
+    // The signature can't be replayed once the address is unlinked.
+    exec(&mut deps, unlink(&uppercase)).unwrap();
+    assert!(!LINKED_ADDRESSES.has(&deps.storage, address.clone()));
+    let err = exec(&mut deps, link(&address, Some(pubkey(&key(1))), signature)).unwrap_err();
+    assert_eq!(err, ContractError::InvalidLinkSignature {});
+}
+
+#[test]
+fn evm_addresses_are_linked_lowercase_with_a_matching_signature() {
+    let mut deps = setup();
+    create_profile(&mut deps, "alice", "alice");
+    let address = evm_address(&key(1));
+    let mixed_case = format!("0x{}", address[2..].to_uppercase());
+    let challenge = challenge(&deps, &mixed_case);
+
+    let forged = evm_sign(&key(2), &challenge);
+    let err = exec(&mut deps, link(&address, None, forged)).unwrap_err();
+    assert_eq!(err, ContractError::InvalidLinkSignature {});
+
+    let signature = evm_sign(&key(1), &challenge);
+    exec(&mut deps, link(&mixed_case, None, signature.clone())).unwrap();
+    assert_eq!(linked(&deps), vec![address.clone()]);
+
+    exec(&mut deps, unlink(&address)).unwrap();
+    let err = exec(&mut deps, link(&address, None, signature)).unwrap_err();
+    assert_eq!(err, ContractError::InvalidLinkSignature {});
+}
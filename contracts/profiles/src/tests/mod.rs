@@ -0,0 +1,81 @@
+mod aliases;
+mod attestations;
+mod directory;
+mod keys;
+mod links;
+mod registration;
+mod subnames;
+mod transfers;
+
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+};
+use cosmwasm_std::{Addr, Env, OwnedDeps, Response};
+use utils::msg::ProfilesExecuteMsg as ExecuteMsg;
+use utils::msg::ProfilesInstantiateMsg as InstantiateMsg;
+
+use crate::contract::{execute, instantiate};
+use crate::error::ContractError;
+
+/// The controller owns the profiles contract and sends every execute.
+pub const CONTROLLER: &str = "controller";
+
+pub type TestDeps = OwnedDeps<MockStorage, MockApi, MockQuerier>;
+
+pub fn setup() -> TestDeps {
+    let mut deps = mock_dependencies();
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(CONTROLLER, &[]),
+        InstantiateMsg {
+            user_id_cooldown: 0,
+            max_aliases: 5,
+            grace_period: 0,
+            change_cooldown: 0,
+            redirect_period: 0,
+        },
+    )
+    .unwrap();
+    deps
+}
+
+pub fn exec(deps: &mut TestDeps, msg: ExecuteMsg) -> Result<Response, ContractError> {
+    exec_at(deps, mock_env(), msg)
+}
+
+pub fn exec_at(deps: &mut TestDeps, env: Env, msg: ExecuteMsg) -> Result<Response, ContractError> {
+    execute(deps.as_mut(), env, mock_info(CONTROLLER, &[]), msg)
+}
+
+/// The mock block time moved `seconds` forward.
+pub fn later(seconds: u64) -> Env {
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(seconds);
+    env
+}
+
+pub fn create_profile(deps: &mut TestDeps, address: &str, user_id: &str) {
+    exec(
+        deps,
+        ExecuteMsg::CreateProfile {
+            address: Addr::unchecked(address),
+            user_id: user_id.to_string(),
+            pubkey: format!("{}-pubkey", address),
+            term: None,
+        },
+    )
+    .unwrap();
+}
+
+pub fn add_alias(deps: &mut TestDeps, address: &str, alias: &str) {
+    exec(
+        deps,
+        ExecuteMsg::AddAlias {
+            address: Addr::unchecked(address),
+            alias: alias.to_string(),
+            term: None,
+        },
+    )
+    .unwrap();
+}
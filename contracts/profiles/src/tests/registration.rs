@@ -0,0 +1,242 @@
+use cosmwasm_std::testing::{mock_env, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{from_json, Addr, StdError, StdResult};
+use sha2::{Digest, Sha256};
+use utils::msg::{ImportEntry, ImportProfilesResponse, ProfilesExecuteMsg as ExecuteMsg};
+use utils::query::ProfilesQueryMsg as QueryMsg;
+
+use crate::contract::query;
+use crate::error::ContractError;
+
+use super::{add_alias, create_profile, exec, exec_at, later, setup, TestDeps};
+use crate::state::{ADDRESS_TO_PROFILE, CONFIG, RESERVED_USER_IDS, USERID_TO_ADDRESS};
+
+fn check_registration(
+    deps: &TestDeps,
+    address: &str,
+    user_id: &str,
+    pubkey: Option<&str>,
+) -> Result<(), String> {
+    let msg = QueryMsg::CheckRegistration {
+        address: Addr::unchecked(address),
+        user_id: user_id.to_string(),
+        pubkey: pubkey.map(str::to_string),
+    };
+    match query(deps.as_ref(), mock_env(), msg) {
+        Ok(_) => Ok(()),
+        Err(StdError::GenericErr { msg, .. }) => Err(msg),
+        Err(err) => panic!("unexpected error: {}", err),
+    }
+}
+
+#[test]
+fn registration_checks_pass_for_a_free_user_id() {
+    let mut deps = setup();
+    create_profile(&mut deps, "alice", "alice");
+
+    check_registration(&deps, "alice", "wonderland", None).unwrap();
+    check_registration(&deps, "bob", "wonderland", Some("bob-pubkey")).unwrap();
+}
+
+#[test]
+fn registration_checks_reject_what_registering_would() {
+    let mut deps = setup();
+    create_profile(&mut deps, "alice", "alice");
+    for alias in ["a1", "a2", "a3", "a4", "a5"] {
+        add_alias(&mut deps, "alice", alias);
+    }
+    exec(
+        &mut deps,
+        ExecuteMsg::CreatePrivateProfile {
+            address: Addr::unchecked("carol"),
+            user_id_hash: "00".repeat(32),
+            pubkey: "carol-pubkey".to_string(),
+        },
+    )
+    .unwrap();
+
+    let taken = check_registration(&deps, "bob", "alice", Some("bob-pubkey"));
+    assert_eq!(
+        taken,
+        Err(ContractError::UserIdAlreadyExists {}.to_string())
+    );
+    let full = check_registration(&deps, "alice", "wonderland", None);
+    assert_eq!(
+        full,
+        Err(ContractError::TooManyAliases { max: 5 }.to_string())
+    );
+    let private = check_registration(&deps, "carol", "wonderland", None);
+    assert_eq!(private, Err(ContractError::PrivateProfile {}.to_string()));
+    let no_pubkey = check_registration(&deps, "bob", "wonderland", None);
+    assert_eq!(no_pubkey, Err(ContractError::PubkeyRequired {}.to_string()));
+    let used_pubkey = check_registration(&deps, "bob", "wonderland", Some("alice-pubkey"));
+    assert_eq!(
+        used_pubkey,
+        Err(ContractError::PubkeyAlreadyUsed {}.to_string())
+    );
+}
+
+fn create_profile_msg(address: &str, user_id: &str, term: Option<u64>) -> ExecuteMsg {
+    ExecuteMsg::CreateProfile {
+        address: Addr::unchecked(address),
+        user_id: user_id.to_string(),
+        pubkey: format!("{}-pubkey", address),
+        term,
+    }
+}
+
+#[test]
+fn an_expired_alias_can_be_registered_again_by_its_holder() {
+    let mut deps = setup();
+    create_profile(&mut deps, "alice", "alice");
+    let add_alias = |term| ExecuteMsg::AddAlias {
+        address: Addr::unchecked("alice"),
+        alias: "wonderland".to_string(),
+        term: Some(term),
+    };
+    exec(&mut deps, add_alias(10)).unwrap();
+
+    exec_at(&mut deps, later(10), add_alias(20)).unwrap();
+    let profile = ADDRESS_TO_PROFILE
+        .load(&deps.storage, Addr::unchecked("alice"))
+        .unwrap();
+    assert_eq!(profile.aliases, vec!["wonderland".to_string()]);
+}
+
+#[test]
+fn a_holder_whose_user_ids_all_lapsed_can_create_a_profile_again() {
+    let mut deps = setup();
+    exec(&mut deps, create_profile_msg("alice", "alice", Some(10))).unwrap();
+
+    let err = exec_at(
+        &mut deps,
+        later(5),
+        create_profile_msg("alice", "ally", None),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::AddressHasProfile {});
+
+    exec_at(
+        &mut deps,
+        later(10),
+        create_profile_msg("alice", "ally", None),
+    )
+    .unwrap();
+    let profile = ADDRESS_TO_PROFILE
+        .load(&deps.storage, Addr::unchecked("alice"))
+        .unwrap();
+    assert_eq!(profile.user_id, "ally");
+    assert!(profile.aliases.is_empty());
+    assert!(!USERID_TO_ADDRESS.has(&deps.storage, "alice".to_string()));
+}
+
+fn create_private_profile_msg(address: &str, user_id: &str) -> ExecuteMsg {
+    let hash = Sha256::new()
+        .chain_update(MOCK_CONTRACT_ADDR.as_bytes())
+        .chain_update(user_id.as_bytes())
+        .finalize();
+    ExecuteMsg::CreatePrivateProfile {
+        address: Addr::unchecked(address),
+        user_id_hash: hash.iter().map(|b| format!("{:02x}", b)).collect(),
+        pubkey: format!("{}-pubkey", address),
+    }
+}
+
+#[test]
+fn a_privately_registered_user_id_cant_be_registered_publicly() {
+    let mut deps = setup();
+    exec(&mut deps, create_private_profile_msg("carol", "secret")).unwrap();
+    create_profile(&mut deps, "alice", "alice");
+
+    let err = exec(&mut deps, create_profile_msg("bob", "secret", None)).unwrap_err();
+    assert_eq!(err, ContractError::UserIdAlreadyExists {});
+    let alias = ExecuteMsg::AddAlias {
+        address: Addr::unchecked("alice"),
+        alias: "secret".to_string(),
+        term: None,
+    };
+    assert_eq!(
+        exec(&mut deps, alias).unwrap_err(),
+        ContractError::UserIdAlreadyExists {}
+    );
+    let change = ExecuteMsg::ChangeUserId {
+        address: Addr::unchecked("alice"),
+        user_id: "secret".to_string(),
+    };
+    assert_eq!(
+        exec(&mut deps, change).unwrap_err(),
+        ContractError::UserIdAlreadyExists {}
+    );
+}
+
+#[test]
+fn a_publicly_registered_user_id_cant_be_registered_privately() {
+    let mut deps = setup();
+    create_profile(&mut deps, "alice", "alice");
+    add_alias(&mut deps, "alice", "ally");
+
+    for user_id in ["alice", "ally"] {
+        let err = exec(&mut deps, create_private_profile_msg("carol", user_id)).unwrap_err();
+        assert_eq!(err, ContractError::UserIdAlreadyExists {});
+    }
+
+    // Once released, the user id is free to be registered privately.
+    let remove = ExecuteMsg::RemoveAlias {
+        address: Addr::unchecked("alice"),
+        alias: "ally".to_string(),
+    };
+    exec(&mut deps, remove).unwrap();
+    exec(&mut deps, create_private_profile_msg("carol", "ally")).unwrap();
+}
+
+#[test]
+fn a_replaced_user_id_can_be_taken_back_during_its_redirect_period() {
+    let mut deps = setup();
+    CONFIG
+        .update(&mut deps.storage, |mut config| -> StdResult<_> {
+            config.redirect_period = 100;
+            Ok(config)
+        })
+        .unwrap();
+    create_profile(&mut deps, "alice", "alice");
+    let change = |user_id: &str| ExecuteMsg::ChangeUserId {
+        address: Addr::unchecked("alice"),
+        user_id: user_id.to_string(),
+    };
+    exec(&mut deps, change("wonderland")).unwrap();
+
+    let err = exec(&mut deps, create_profile_msg("bob", "alice", None)).unwrap_err();
+    assert!(matches!(err, ContractError::UserIdReserved { .. }));
+    exec(&mut deps, change("alice")).unwrap();
+    let holder = USERID_TO_ADDRESS
+        .load(&deps.storage, "alice".to_string())
+        .unwrap();
+    assert_eq!(holder, Addr::unchecked("alice"));
+    assert!(!RESERVED_USER_IDS.has(&deps.storage, "alice".to_string()));
+}
+
+#[test]
+fn a_failed_import_entry_leaves_no_trace() {
+    let mut deps = setup();
+    exec(&mut deps, create_profile_msg("alice", "alice", Some(10))).unwrap();
+    create_profile(&mut deps, "bob", "bob");
+    let entry = |user_id: &str| ImportEntry {
+        address: Addr::unchecked("alice"),
+        user_id: user_id.to_string(),
+        pubkey: "alice-pubkey".to_string(),
+    };
+    let import = ExecuteMsg::ImportProfiles {
+        entries: vec![entry("bob"), entry("")],
+        term: None,
+    };
+
+    let res = exec_at(&mut deps, later(10), import).unwrap();
+    let res: ImportProfilesResponse = from_json(res.data.unwrap()).unwrap();
+    assert!(res.imported.is_empty());
+    assert_eq!(res.failures.len(), 2);
+    // The lapsed profile would have been replaced by a successful entry, not by failed ones.
+    let profile = ADDRESS_TO_PROFILE
+        .load(&deps.storage, Addr::unchecked("alice"))
+        .unwrap();
+    assert_eq!(profile.user_id, "alice");
+    assert!(USERID_TO_ADDRESS.has(&deps.storage, "alice".to_string()));
+}
@@ -0,0 +1,148 @@
+use cosmwasm_std::testing::mock_env;
+use cosmwasm_std::{from_json, Addr, StdError, StdResult};
+use utils::msg::ProfilesExecuteMsg as ExecuteMsg;
+use utils::query::{ProfileInfo, ProfilesQueryMsg as QueryMsg};
+
+use super::{create_profile, exec, exec_at, later, setup, TestDeps};
+use crate::contract::query;
+use crate::error::ContractError;
+use crate::state::{CONFIG, HOLDER_SUBNAMES, SUBNAMES};
+
+fn create_subname(parent_owner: &str, holder: &str) -> ExecuteMsg {
+    ExecuteMsg::CreateSubname {
+        address: Addr::unchecked(parent_owner),
+        parent: "bob".to_string(),
+        label: "pay".to_string(),
+        holder: Addr::unchecked(holder),
+        pubkey: format!("{}-pubkey", holder),
+    }
+}
+
+fn change_pubkey(address: &str) -> ExecuteMsg {
+    ExecuteMsg::ChangeSubnamePubkey {
+        address: Addr::unchecked(address),
+        parent: "bob".to_string(),
+        label: "pay".to_string(),
+        pubkey: "new-pubkey".to_string(),
+    }
+}
+
+fn subname_info(deps: &TestDeps) -> StdResult<ProfileInfo> {
+    let msg = QueryMsg::UserInfo {
+        user_id: "pay.bob".to_string(),
+    };
+    query(deps.as_ref(), mock_env(), msg).and_then(from_json)
+}
+
+/// bob owns the parent user id, alice holds its "pay" subname.
+fn subname_setup() -> TestDeps {
+    let mut deps = setup();
+    create_profile(&mut deps, "alice", "alice");
+    create_profile(&mut deps, "bob", "bob");
+    exec(&mut deps, create_subname("bob", "alice")).unwrap();
+    deps
+}
+
+#[test]
+fn subnames_are_created_under_unexpired_parents_only() {
+    let mut deps = setup();
+    CONFIG
+        .update(&mut deps.storage, |mut config| -> StdResult<_> {
+            config.grace_period = 100;
+            Ok(config)
+        })
+        .unwrap();
+    let create_bob = ExecuteMsg::CreateProfile {
+        address: Addr::unchecked("bob"),
+        user_id: "bob".to_string(),
+        pubkey: "bob-pubkey".to_string(),
+        term: Some(10),
+    };
+    exec(&mut deps, create_bob).unwrap();
+
+    let err = exec(&mut deps, create_subname("alice", "alice")).unwrap_err();
+    assert_eq!(err, ContractError::NotUserIdOwner {});
+    // In its grace period, bob still holds the parent but it doesn't resolve.
+    let err = exec_at(&mut deps, later(10), create_subname("bob", "alice")).unwrap_err();
+    assert!(matches!(
+        err,
+        ContractError::Std(StdError::GenericErr { .. })
+    ));
+    assert!(!SUBNAMES.has(&deps.storage, ("bob".to_string(), "pay".to_string())));
+
+    exec(&mut deps, create_subname("bob", "alice")).unwrap();
+    let err = exec(&mut deps, create_subname("bob", "carol")).unwrap_err();
+    assert_eq!(err, ContractError::SubnameAlreadyExists {});
+}
+
+#[test]
+fn subnames_resolve_to_their_holder() {
+    let deps = subname_setup();
+
+    let info = subname_info(&deps).unwrap();
+    assert_eq!(info.address, Addr::unchecked("alice"));
+    assert_eq!(info.user_id, "pay.bob");
+    assert_eq!(info.pubkey, "alice-pubkey");
+}
+
+#[test]
+fn holders_change_their_pubkey_only_if_the_policy_allows_it() {
+    let mut deps = subname_setup();
+
+    let err = exec(&mut deps, change_pubkey("alice")).unwrap_err();
+    assert_eq!(err, ContractError::SubnamePubkeyLocked {});
+    let err = exec(&mut deps, change_pubkey("carol")).unwrap_err();
+    assert_eq!(err, ContractError::NotUserIdOwner {});
+    exec(&mut deps, change_pubkey("bob")).unwrap();
+
+    let policy = ExecuteMsg::SetSubnamePolicy {
+        address: Addr::unchecked("bob"),
+        parent: "bob".to_string(),
+        holders_can_change_pubkey: true,
+    };
+    exec(&mut deps, policy).unwrap();
+    exec(&mut deps, change_pubkey("alice")).unwrap();
+    let err = exec(&mut deps, change_pubkey("carol")).unwrap_err();
+    assert_eq!(err, ContractError::NotUserIdOwner {});
+}
+
+#[test]
+fn only_the_parent_owner_transfers_and_revokes_subnames() {
+    let mut deps = subname_setup();
+    let transfer = |address: &str| ExecuteMsg::TransferSubname {
+        address: Addr::unchecked(address),
+        parent: "bob".to_string(),
+        label: "pay".to_string(),
+        holder: Addr::unchecked("carol"),
+        pubkey: "carol-pubkey".to_string(),
+    };
+    let revoke = |address: &str| ExecuteMsg::RevokeSubname {
+        address: Addr::unchecked(address),
+        parent: "bob".to_string(),
+        label: "pay".to_string(),
+    };
+    let held_by = |deps: &TestDeps, holder: &str| {
+        let key = (
+            Addr::unchecked(holder),
+            "bob".to_string(),
+            "pay".to_string(),
+        );
+        HOLDER_SUBNAMES.has(&deps.storage, key)
+    };
+
+    let err = exec(&mut deps, transfer("alice")).unwrap_err();
+    assert_eq!(err, ContractError::NotUserIdOwner {});
+    exec(&mut deps, transfer("bob")).unwrap();
+    assert_eq!(
+        subname_info(&deps).unwrap().address,
+        Addr::unchecked("carol")
+    );
+    assert!(!held_by(&deps, "alice"));
+    assert!(held_by(&deps, "carol"));
+
+    let err = exec(&mut deps, revoke("carol")).unwrap_err();
+    assert_eq!(err, ContractError::NotUserIdOwner {});
+    exec(&mut deps, revoke("bob")).unwrap();
+    subname_info(&deps).unwrap_err();
+    assert!(!held_by(&deps, "carol"));
+}
@@ -0,0 +1,177 @@
+use cosmwasm_std::{Addr, StdResult};
+use utils::msg::ProfilesExecuteMsg as ExecuteMsg;
+
+use super::{add_alias, create_profile, exec, setup};
+use crate::error::ContractError;
+use crate::state::{
+    ADDRESS_TO_PROFILE, CONFIG, HOLDER_SUBNAMES, PENDING_TRANSFERS, RECEIVER_TRANSFERS,
+    RESERVED_USER_IDS, SUBNAMES, SUBNAME_POLICIES, USERID_TO_ADDRESS,
+};
+
+fn move_user_id(user_id: &str, from: &str, to: &str) -> ExecuteMsg {
+    ExecuteMsg::MoveUserId {
+        user_id: user_id.to_string(),
+        from: Addr::unchecked(from),
+        to: Addr::unchecked(to),
+        pubkey: Some(format!("{}-pubkey", to)),
+    }
+}
+
+#[test]
+fn the_only_user_id_of_a_profile_cant_be_sold() {
+    let mut deps = setup();
+    create_profile(&mut deps, "alice", "alice");
+
+    let err = exec(&mut deps, move_user_id("alice", "alice", "bob")).unwrap_err();
+    assert_eq!(err, ContractError::SoleUserId {});
+    assert!(ADDRESS_TO_PROFILE.has(&deps.storage, Addr::unchecked("alice")));
+}
+
+#[test]
+fn selling_the_primary_user_id_promotes_an_alias() {
+    let mut deps = setup();
+    create_profile(&mut deps, "alice", "alice");
+    add_alias(&mut deps, "alice", "ally");
+
+    exec(&mut deps, move_user_id("alice", "alice", "bob")).unwrap();
+
+    let seller = ADDRESS_TO_PROFILE
+        .load(&deps.storage, Addr::unchecked("alice"))
+        .unwrap();
+    assert_eq!(seller.user_id, "ally");
+    assert!(seller.aliases.is_empty());
+    let buyer = USERID_TO_ADDRESS
+        .load(&deps.storage, "alice".to_string())
+        .unwrap();
+    assert_eq!(buyer, Addr::unchecked("bob"));
+}
+
+#[test]
+fn subnames_dont_follow_a_sold_user_id() {
+    let mut deps = setup();
+    create_profile(&mut deps, "alice", "alice");
+    add_alias(&mut deps, "alice", "ally");
+    exec(
+        &mut deps,
+        ExecuteMsg::CreateSubname {
+            address: Addr::unchecked("alice"),
+            parent: "ally".to_string(),
+            label: "pay".to_string(),
+            holder: Addr::unchecked("alice"),
+            pubkey: "alice-pubkey".to_string(),
+        },
+    )
+    .unwrap();
+    exec(
+        &mut deps,
+        ExecuteMsg::SetSubnamePolicy {
+            address: Addr::unchecked("alice"),
+            parent: "ally".to_string(),
+            holders_can_change_pubkey: true,
+        },
+    )
+    .unwrap();
+
+    exec(&mut deps, move_user_id("ally", "alice", "bob")).unwrap();
+
+    assert!(!SUBNAMES.has(&deps.storage, ("ally".to_string(), "pay".to_string())));
+    assert!(!SUBNAME_POLICIES.has(&deps.storage, "ally".to_string()));
+}
+
+#[test]
+fn user_ids_offered_to_a_recovered_address_follow_it() {
+    let mut deps = setup();
+    create_profile(&mut deps, "alice", "alice");
+    create_profile(&mut deps, "bob", "bob");
+    add_alias(&mut deps, "bob", "bobby");
+    exec(
+        &mut deps,
+        ExecuteMsg::TransferUserId {
+            address: Addr::unchecked("bob"),
+            user_id: "bobby".to_string(),
+            to: Addr::unchecked("alice"),
+        },
+    )
+    .unwrap();
+
+    exec(
+        &mut deps,
+        ExecuteMsg::MoveProfile {
+            from: Addr::unchecked("alice"),
+            to: Addr::unchecked("alice2"),
+        },
+    )
+    .unwrap();
+
+    let to = PENDING_TRANSFERS
+        .load(&deps.storage, "bobby".to_string())
+        .unwrap();
+    assert_eq!(to, Addr::unchecked("alice2"));
+    assert!(!RECEIVER_TRANSFERS.has(
+        &deps.storage,
+        (Addr::unchecked("alice"), "bobby".to_string())
+    ));
+    assert!(RECEIVER_TRANSFERS.has(
+        &deps.storage,
+        (Addr::unchecked("alice2"), "bobby".to_string())
+    ));
+}
+
+#[test]
+fn redirects_and_subnames_follow_a_moved_profile() {
+    let mut deps = setup();
+    CONFIG
+        .update(&mut deps.storage, |mut config| -> StdResult<_> {
+            config.redirect_period = 100;
+            Ok(config)
+        })
+        .unwrap();
+    create_profile(&mut deps, "alice", "alice");
+    create_profile(&mut deps, "bob", "bob");
+    exec(
+        &mut deps,
+        ExecuteMsg::CreateSubname {
+            address: Addr::unchecked("bob"),
+            parent: "bob".to_string(),
+            label: "pay".to_string(),
+            holder: Addr::unchecked("alice"),
+            pubkey: "alice-pubkey".to_string(),
+        },
+    )
+    .unwrap();
+    let change = |address: &str, user_id: &str| ExecuteMsg::ChangeUserId {
+        address: Addr::unchecked(address),
+        user_id: user_id.to_string(),
+    };
+    exec(&mut deps, change("alice", "wonderland")).unwrap();
+
+    exec(
+        &mut deps,
+        ExecuteMsg::MoveProfile {
+            from: Addr::unchecked("alice"),
+            to: Addr::unchecked("alice2"),
+        },
+    )
+    .unwrap();
+
+    let reservation = RESERVED_USER_IDS
+        .load(&deps.storage, "alice".to_string())
+        .unwrap();
+    assert_eq!(reservation.redirect, Some(Addr::unchecked("alice2")));
+    let subname = SUBNAMES
+        .load(&deps.storage, ("bob".to_string(), "pay".to_string()))
+        .unwrap();
+    assert_eq!(subname.holder, Addr::unchecked("alice2"));
+    let holder_key = |holder: &str| {
+        (
+            Addr::unchecked(holder),
+            "bob".to_string(),
+            "pay".to_string(),
+        )
+    };
+    assert!(!HOLDER_SUBNAMES.has(&deps.storage, holder_key("alice")));
+    assert!(HOLDER_SUBNAMES.has(&deps.storage, holder_key("alice2")));
+
+    // The moved profile can still take its replaced user id back.
+    exec(&mut deps, change("alice2", "alice")).unwrap();
+}
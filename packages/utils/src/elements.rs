@@ -1,10 +1,49 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Binary, Coin, Addr};
+use cosmwasm_std::{Binary, Coin, Addr, Timestamp};
 
 #[cw_serde]
 pub struct Profile {
     pub user_id: String,
     pub pubkey: String,
+    /// Extra user ids that resolve to the same address as `user_id`.
+    pub aliases: Vec<String>,
+    /// Deactivated profiles keep their user id but their inbox rejects new messages.
+    pub active: bool,
+    /// Hidden profiles are left out of directory listings but still resolve by exact user id.
+    pub hidden: bool,
+    /// Private profiles hold a single blinded user id. `user_id` is its hash and never the plain name.
+    pub private: bool,
+    /// Addresses on other chains proven to belong to the owner of the profile.
+    pub linked_addresses: Vec<String>,
+}
+
+/// A name `label.parent` handed out by the owner of the top-level user id `parent`.
+#[cw_serde]
+pub struct Subname {
+    pub holder: Addr,
+    pub pubkey: String,
+}
+
+/// A claim such as "domain verified" made about a profile by a verifier.
+#[cw_serde]
+pub struct Attestation {
+    pub verifier: Addr,
+    pub kind: String,
+    pub expires: Timestamp,
+}
+
+/// Encrypted copy of a profile's private key. `version` goes up by one on every write.
+#[cw_serde]
+pub struct KeyBackup {
+    pub data: Binary,
+    pub version: u64,
+}
+
+/// A secret share of a profile's private key, encrypted to the pubkey of `guardian`'s profile.
+#[cw_serde]
+pub struct KeyShare {
+    pub guardian: Addr,
+    pub share: Binary,
 }
 
 #[cw_serde]
@@ -1,9 +1,43 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Binary};
+use cosmwasm_std::{Addr, Binary, Timestamp};
 use cw_ownable::cw_ownable_execute;
 
+use crate::elements::KeyShare;
+
+#[cw_serde]
+pub struct ProfilesInstantiateMsg {
+    /// Seconds a deleted user id stays reserved before anyone can register it again.
+    pub user_id_cooldown: u64,
+    /// Maximum number of aliases a profile can hold on top of its primary user id.
+    pub max_aliases: u64,
+    /// Seconds after expiry during which the holder can still renew an expired user id.
+    pub grace_period: u64,
+    /// Minimum seconds between two changes of a profile's primary user id.
+    pub change_cooldown: u64,
+    /// Seconds a replaced user id stays reserved and keeps resolving to the profile that replaced it.
+    pub redirect_period: u64,
+}
+
 #[cw_serde]
-pub struct ProfilesInstantiateMsg {}
+pub struct ImportEntry {
+    pub address: Addr,
+    pub user_id: String,
+    pub pubkey: String,
+}
+
+#[cw_serde]
+pub struct ImportProfilesResponse {
+    /// User ids of the imported entries.
+    pub imported: Vec<String>,
+    pub failures: Vec<ImportFailure>,
+}
+
+#[cw_serde]
+pub struct ImportFailure {
+    pub address: Addr,
+    pub user_id: String,
+    pub error: String,
+}
 
 #[cw_serde]
 pub struct MessagesInstantiateMsg {
@@ -14,10 +48,27 @@ pub struct MessagesInstantiateMsg {
 #[cw_ownable_execute]
 #[cw_serde]
 pub enum ProfilesExecuteMsg {
+    /// `term` is the number of seconds the user id is registered for, or forever if not set. An
+    /// existing profile of `address` is replaced if all its user ids are past their grace period.
     CreateProfile {
         address: Addr,
         user_id: String,
         pubkey: String,
+        term: Option<u64>,
+    },
+    /// Creates a profile whose user id is only stored as `user_id_hash`. Private profiles can't have
+    /// aliases or change their user id, and are registered without a term. Fails if the hash is
+    /// the one of a registered public user id.
+    CreatePrivateProfile {
+        address: Addr,
+        user_id_hash: String,
+        pubkey: String,
+    },
+    /// Registers each entry like `CreateProfile`. Failing entries are skipped and reported in the
+    /// `ImportProfilesResponse` set as response data.
+    ImportProfiles {
+        entries: Vec<ImportEntry>,
+        term: Option<u64>,
     },
     ChangeUserId {
         address: Addr,
@@ -27,6 +78,168 @@ pub enum ProfilesExecuteMsg {
         address: Addr,
         pubkey: String,
     },
+    AddAlias {
+        address: Addr,
+        alias: String,
+        term: Option<u64>,
+    },
+    RemoveAlias {
+        address: Addr,
+        alias: String,
+    },
+    CreateSubname {
+        address: Addr,
+        parent: String,
+        label: String,
+        holder: Addr,
+        pubkey: String,
+    },
+    TransferSubname {
+        address: Addr,
+        parent: String,
+        label: String,
+        holder: Addr,
+        pubkey: String,
+    },
+    RevokeSubname {
+        address: Addr,
+        parent: String,
+        label: String,
+    },
+    /// Can be sent for the parent owner, or for the holder if the parent's policy allows it.
+    ChangeSubnamePubkey {
+        address: Addr,
+        parent: String,
+        label: String,
+        pubkey: String,
+    },
+    SetSubnamePolicy {
+        address: Addr,
+        parent: String,
+        holders_can_change_pubkey: bool,
+    },
+    /// Extends the registration of a user id by `term` seconds.
+    RenewUserId {
+        user_id: String,
+        term: u64,
+    },
+    /// Starts a two-step transfer of one of `address`'s user ids to `to`.
+    TransferUserId {
+        address: Addr,
+        user_id: String,
+        to: Addr,
+    },
+    CancelUserIdTransfer {
+        address: Addr,
+        user_id: String,
+    },
+    /// Completes a pending transfer to `address`. `pubkey` is required if `address` has no profile yet,
+    /// otherwise the user id is added as an alias. Subnames under the user id are revoked.
+    AcceptUserId {
+        address: Addr,
+        user_id: String,
+        pubkey: Option<String>,
+    },
+    /// Moves a user id right away, used to settle marketplace sales. Fails if it is the only user id
+    /// of `from`, and revokes its subnames like `AcceptUserId`.
+    MoveUserId {
+        user_id: String,
+        from: Addr,
+        to: Addr,
+        pubkey: Option<String>,
+    },
+    SetDirectoryListing {
+        address: Addr,
+        listed: bool,
+    },
+    DeactivateProfile {
+        address: Addr,
+    },
+    ReactivateProfile {
+        address: Addr,
+    },
+    DeleteProfile {
+        address: Addr,
+    },
+    /// Links `external_address` to the profile after checking `signature` over its link challenge.
+    /// Cosmos accounts sign the sha256 of the challenge and provide their compressed `pubkey`. EVM
+    /// accounts sign it with `personal_sign` and the pubkey is recovered from the signature.
+    LinkAddress {
+        address: Addr,
+        external_address: String,
+        pubkey: Option<Binary>,
+        signature: Binary,
+    },
+    UnlinkAddress {
+        address: Addr,
+        external_address: String,
+    },
+    /// `pubkey` is the compressed secp256k1 pubkey the verifier signs its attestations with.
+    AddVerifier {
+        verifier: Addr,
+        pubkey: Binary,
+    },
+    /// Removes the verifier. Its attestations stop being listed.
+    RemoveVerifier {
+        verifier: Addr,
+    },
+    /// Attests `kind` about the profile of `address` until `expires`, replacing the verifier's previous
+    /// attestation of the same kind. `signature` is the verifier's signature of the sha256 of the
+    /// message returned by the `AttestationChallenge` query, which counts the revocations of the
+    /// verifier's attestations about the profile so a revoked one can't be submitted again.
+    Attest {
+        address: Addr,
+        verifier: Addr,
+        kind: String,
+        expires: Timestamp,
+        signature: Binary,
+    },
+    RevokeAttestation {
+        address: Addr,
+        verifier: Addr,
+        kind: String,
+    },
+    /// Replaces the key backup of the profile. Fails unless `expected_version` is the version currently
+    /// stored, 0 if there is none.
+    SetKeyBackup {
+        address: Addr,
+        data: Binary,
+        expected_version: u64,
+    },
+    /// Replaces the guardian shares of the profile's private key and cancels any pending key recovery.
+    SetKeyShares {
+        address: Addr,
+        shares: Vec<KeyShare>,
+        threshold: u64,
+    },
+    /// Asks the guardians to re-encrypt their shares to `new_pubkey`.
+    RequestKeyRecovery {
+        address: Addr,
+        new_pubkey: String,
+    },
+    CancelKeyRecovery {
+        address: Addr,
+    },
+    /// Approves the key recovery of `address` with the guardian's share re-encrypted to the new pubkey.
+    SubmitKeyShare {
+        address: Addr,
+        guardian: Addr,
+        share: Binary,
+    },
+    /// Moves the profile of `from` and all its user ids to `to`, used for account recovery. User
+    /// ids offered to `from` are offered to `to` instead, replaced user ids redirect to `to`, and
+    /// subnames held by `from` are held by `to`.
+    MoveProfile {
+        from: Addr,
+        to: Addr,
+    },
+    ChangeConfig {
+        user_id_cooldown: u64,
+        max_aliases: u64,
+        grace_period: u64,
+        change_cooldown: u64,
+        redirect_period: u64,
+    },
 }
 
 #[cw_ownable_execute]
@@ -43,6 +256,27 @@ pub enum MessagesExecuteMsg {
     DeleteMessages {
         message_ids: Vec<u64>
     },
+    CloseInbox {
+        address: Addr,
+    },
+    OpenInbox {
+        address: Addr,
+    },
+    /// Removes every message of the inbox. Unclaimed funds go to the inbox owner,
+    /// or back to each message sender when `refund` is set. Fails while claims are paused.
+    ClearInbox {
+        address: Addr,
+        refund: bool,
+    },
+    /// Appends the inbox of `from`, unclaimed funds included, to the inbox of `to`.
+    MoveInbox {
+        from: Addr,
+        to: Addr,
+    },
+    /// Stops claiming, deleting messages and clearing inboxes, which all pay out message funds.
+    SetClaimsPaused {
+        paused: bool,
+    },
     ChangeConfig {
         default_query_limit: u64,
         max_query_limit: u64,    
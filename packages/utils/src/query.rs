@@ -1,7 +1,7 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::Addr;
+use cosmwasm_std::{Addr, Empty, Timestamp};
 
-use crate::elements::Message;
+use crate::elements::{Attestation, KeyBackup, KeyShare, Message};
 
 #[cw_serde]
 #[derive(QueryResponses)]
@@ -10,13 +10,161 @@ pub enum ProfilesQueryMsg {
     UserInfo { user_id: String },
     #[returns(ProfileInfo)]
     AddressInfo { address: Addr },
+    /// Resolves a private profile. `user_id_hash` is the hex encoded sha256 of the profiles contract
+    /// address followed by the user id.
+    #[returns(ProfileInfo)]
+    UserInfoByHash { user_id_hash: String },
+    #[returns(ProfileInfo)]
+    ProfileByPubkey { pubkey: String },
+    /// Resolves a Cosmos or EVM address linked to a profile.
+    #[returns(ProfileInfo)]
+    ProfileByLinkedAddress { external_address: String },
+    /// The message the external account must sign to be linked to the profile of `address`.
+    #[returns(LinkChallengeResponse)]
+    LinkChallenge {
+        address: Addr,
+        external_address: String,
+    },
+    /// The message `verifier` must sign to attest `kind` about the profile of `address`.
+    #[returns(AttestationChallengeResponse)]
+    AttestationChallenge {
+        address: Addr,
+        verifier: Addr,
+        kind: String,
+        expires: Timestamp,
+    },
+    /// Lists the user ids of visible profiles in ascending order, aliases included.
+    #[returns(DirectoryResponse)]
+    ListProfiles {
+        start_after: Option<String>,
+        limit: Option<u64>,
+    },
+    /// Like `ListProfiles`, for the user ids starting with `prefix`.
+    #[returns(DirectoryResponse)]
+    SearchByPrefix {
+        prefix: String,
+        start_after: Option<String>,
+        limit: Option<u64>,
+    },
+    /// Visible profiles ordered by address, for migrating them elsewhere.
+    #[returns(ExportProfilesResponse)]
+    ExportProfiles {
+        start_after: Option<Addr>,
+        limit: Option<u64>,
+    },
+    #[returns(PendingTransferResponse)]
+    PendingTransfer { user_id: String },
+    /// Unlike `UserInfo`, this also answers for user ids that have expired but were not reclaimed yet.
+    #[returns(ExpirationResponse)]
+    Expiration { user_id: String },
+    /// Succeeds if `user_id` could be registered for `address` right now, as an alias of its
+    /// profile or as a new profile with `pubkey` if it has none. Fails with the reason otherwise.
+    #[returns(Empty)]
+    CheckRegistration {
+        address: Addr,
+        user_id: String,
+        pubkey: Option<String>,
+    },
+    #[returns(VerifiersResponse)]
+    Verifiers {},
+    #[returns(KeyBackup)]
+    KeyBackup { address: Addr },
+    #[returns(KeySharesResponse)]
+    KeyShares { address: Addr },
+    /// Shares submitted by guardians are only returned once the threshold is reached.
+    #[returns(KeyRecoveryResponse)]
+    KeyRecovery { address: Addr },
 }
 
 #[cw_serde]
 pub struct ProfileInfo {
     pub address: Addr,
+    /// Primary user id of the profile, also when it was resolved through an alias.
     pub user_id: String,
+    pub aliases: Vec<String>,
     pub pubkey: String,
+    pub active: bool,
+    /// Expiry of the primary user id, if it was registered for a limited term.
+    pub expires: Option<Timestamp>,
+    /// Unexpired attestations made by current verifiers.
+    pub attestations: Vec<Attestation>,
+    pub linked_addresses: Vec<String>,
+}
+
+#[cw_serde]
+pub struct DirectoryResponse {
+    pub entries: Vec<DirectoryEntry>,
+    /// Set when the query stopped before the end, to be passed as `start_after` to continue. Fewer
+    /// than `limit` entries can come with it, as a query only reads so many user ids.
+    pub next_start_after: Option<String>,
+}
+
+#[cw_serde]
+pub struct DirectoryEntry {
+    /// The listed user id, which may be an alias of `profile.user_id`.
+    pub user_id: String,
+    pub profile: ProfileInfo,
+}
+
+#[cw_serde]
+pub struct ExportProfilesResponse {
+    pub profiles: Vec<ExportEntry>,
+    /// Set when the query stopped before the end, to be passed as `start_after` to continue. Fewer
+    /// than `limit` profiles can come with it, as a query only reads so many profiles.
+    pub next_start_after: Option<Addr>,
+}
+
+#[cw_serde]
+pub struct ExportEntry {
+    pub address: Addr,
+    pub user_id: String,
+    pub aliases: Vec<String>,
+    pub pubkey: String,
+    /// Whether the profile was imported from the legacy directory.
+    pub imported: bool,
+}
+
+#[cw_serde]
+pub struct ExpirationResponse {
+    pub address: Addr,
+    pub expires: Option<Timestamp>,
+    /// After this time anyone can register the user id again.
+    pub grace_end: Option<Timestamp>,
+}
+
+#[cw_serde]
+pub struct LinkChallengeResponse {
+    pub challenge: String,
+}
+
+#[cw_serde]
+pub struct AttestationChallengeResponse {
+    pub challenge: String,
+}
+
+#[cw_serde]
+pub struct VerifiersResponse {
+    pub verifiers: Vec<Addr>,
+}
+
+#[cw_serde]
+pub struct KeySharesResponse {
+    pub shares: Vec<KeyShare>,
+    pub threshold: u64,
+}
+
+#[cw_serde]
+pub struct KeyRecoveryResponse {
+    pub new_pubkey: String,
+    pub approvals: Vec<Addr>,
+    pub threshold: u64,
+    /// Shares re-encrypted to `new_pubkey`, available once `threshold` guardians have approved.
+    pub shares: Option<Vec<KeyShare>>,
+}
+
+#[cw_serde]
+pub struct PendingTransferResponse {
+    pub to: Option<Addr>,
 }
 
 #[cw_serde]
@@ -30,6 +178,8 @@ pub enum MessagesQueryMsg {
     },
     #[returns(TotalMessagesResponse)]
     TotalMessages { address: Addr },
+    #[returns(bool)]
+    ClaimsPaused {},
 }
 
 #[cw_serde]